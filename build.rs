@@ -0,0 +1,76 @@
+// Generates the table of data files the `embedded` feature builds into the binary (see
+// src/embedded.rs). Only OUT_DIR is written to.
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Files embedded from each data/<year>/dayN/ directory, and from data/<year>/
+const DAY_FILES: [&str; 2] = ["input.txt", "example.txt"];
+const YEAR_FILES: [&str; 1] = ["answers.txt"];
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let embedded = env::var_os("CARGO_FEATURE_EMBEDDED").is_some();
+    let files = if embedded {
+        embedded_files(&root.join("data"))
+    } else {
+        Vec::new()
+    };
+    let mut table = String::from("pub static FILES: &[(&str, &str)] = &[\n");
+    for (key, path) in files {
+        writeln!(
+            table,
+            "    ({:?}, include_str!({:?})),",
+            key,
+            path.display()
+        )
+        .unwrap();
+    }
+    table.push_str("];\n");
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out.join("embedded.rs"), table).expect("Unable to write the embedded file table");
+}
+
+// (data/<year>/..., absolute path) of every file to embed. Only the directories that are read are
+// watched - data/<year>/ itself also holds runtimes.txt, which the CLI rewrites on every run.
+fn embedded_files(data: &Path) -> Vec<(String, PathBuf)> {
+    let mut years: Vec<u16> = fs::read_dir(data)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse().ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    years.sort_unstable();
+
+    let mut files = Vec::new();
+    for year in years {
+        let year_directory = data.join(year.to_string());
+        let mut add = |key: String, path: PathBuf| {
+            if path.is_file() {
+                println!("cargo:rerun-if-changed={}", path.display());
+                files.push((key, path));
+            }
+        };
+        for name in YEAR_FILES {
+            add(format!("data/{}/{}", year, name), year_directory.join(name));
+        }
+        for day in 1..=25 {
+            let day_directory = year_directory.join(format!("day{}", day));
+            if !day_directory.is_dir() {
+                continue;
+            }
+            println!("cargo:rerun-if-changed={}", day_directory.display());
+            for name in DAY_FILES {
+                add(
+                    format!("data/{}/day{}/{}", year, day, name),
+                    day_directory.join(name),
+                );
+            }
+        }
+    }
+    files
+}
@@ -0,0 +1,38 @@
+// Runs the differential harness from the command line, e.g. to soak test with many more cases
+// than the unit tests use:
+//
+//     cargo run --release --features testing --example differential -- <iterations> <seed>
+
+use lib_aoc2021::testing::differential;
+use lib_aoc2021::{registry, y2021};
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let iterations: u64 = args
+        .next()
+        .map_or(1000, |n| n.parse().expect("Invalid iterations"));
+    let seed: u64 = args.next().map_or_else(
+        || {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+        },
+        |n| n.parse().expect("Invalid seed"),
+    );
+
+    println!("Running {} cases per day with seed {}", iterations, seed);
+    let mut total = 0;
+    for day in registry::days(y2021::YEAR) {
+        let mismatches = differential::run(day, iterations, seed);
+        println!("Day {}: {} mismatches", day, mismatches.len());
+        for mismatch in &mismatches {
+            println!("{}", mismatch);
+        }
+        total += mismatches.len();
+    }
+
+    if total > 0 {
+        std::process::exit(1);
+    }
+}
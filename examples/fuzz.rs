@@ -0,0 +1,57 @@
+// Plain std fuzz driver for machines without cargo-fuzz / libFuzzer. Feeds random bytes and
+// mutated puzzle inputs to a day's fuzz target and saves anything that crashes it:
+//
+//     cargo run --release --features testing --example fuzz -- [day|all] [iterations] [seed]
+//
+// Crashing inputs are written to fuzz/artifacts/dayN/. Once fixed, move them into
+// fuzz/regressions/dayN/ so `cargo test` keeps replaying them.
+
+use lib_aoc2021::testing::fuzz;
+use lib_aoc2021::{registry, y2021};
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let days: Vec<u8> = match args.next().as_deref() {
+        None | Some("all") => registry::days(y2021::YEAR),
+        Some(day) => vec![day.parse().expect("Invalid day")],
+    };
+    let iterations: u64 = args
+        .next()
+        .map_or(100_000, |n| n.parse().expect("Invalid iterations"));
+    let seed: u64 = args.next().map_or_else(
+        || {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+        },
+        |n| n.parse().expect("Invalid seed"),
+    );
+
+    // The crashes are reported below, so don't print every panic as it happens
+    std::panic::set_hook(Box::new(|_| {}));
+
+    let mut total = 0;
+    for day in days {
+        let crashes = fuzz::drive(day, iterations, seed);
+        println!(
+            "Day {}: {} crashes in {} cases (seed {})",
+            day,
+            crashes.len(),
+            iterations,
+            seed
+        );
+        for crash in &crashes {
+            let directory = format!("fuzz/artifacts/day{}", day);
+            std::fs::create_dir_all(&directory).expect("Unable to create artifacts directory");
+            let path = format!("{}/crash-{}", directory, crash.seed);
+            std::fs::write(&path, &crash.input).expect("Unable to save crash");
+            println!("  {}: {}", path, crash.message);
+        }
+        total += crashes.len();
+    }
+
+    if total > 0 {
+        std::process::exit(1);
+    }
+}
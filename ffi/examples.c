@@ -0,0 +1,107 @@
+/*
+ * Checks the puzzle examples through the C API in include/aoc2021.h. Run it with `make -C ffi`,
+ * which builds the static library first, or pass the data directory as the only argument.
+ */
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "aoc2021.h"
+
+struct example {
+    uint8_t day;
+    const char *answers[2];
+};
+
+static const struct example EXAMPLES[] = {
+    {1, {"7", "5"}},
+    {2, {"150", "900"}},
+    {3, {"198", "230"}},
+    {4, {"4512", "1924"}},
+    {5, {"5", "12"}},
+    {6, {"5934", "26984457539"}},
+    {7, {"37", "168"}},
+    {8, {"26", "61229"}},
+};
+
+static char *read_file(const char *path, size_t *len) {
+    FILE *file = fopen(path, "rb");
+    if (!file) {
+        return NULL;
+    }
+    size_t capacity = 4096;
+    char *data = malloc(capacity);
+    *len = 0;
+    size_t read;
+    while (data && (read = fread(data + *len, 1, capacity - *len, file)) > 0) {
+        *len += read;
+        if (*len == capacity) {
+            capacity *= 2;
+            data = realloc(data, capacity);
+        }
+    }
+    fclose(file);
+    return data;
+}
+
+static int compiled(uint8_t day, const uint8_t *days, size_t count) {
+    for (size_t i = 0; i < count; i++) {
+        if (days[i] == day) {
+            return 1;
+        }
+    }
+    return 0;
+}
+
+int main(int argc, char **argv) {
+    const char *data = argc > 1 ? argv[1] : "../data/2021";
+    int failures = 0;
+
+    uint8_t days[25];
+    size_t count = aoc_days(2021, days, sizeof days);
+    if (count > sizeof days) {
+        count = sizeof days;
+    }
+    printf("%zu days compiled in\n", count);
+
+    for (size_t i = 0; i < sizeof EXAMPLES / sizeof EXAMPLES[0]; i++) {
+        const struct example *example = &EXAMPLES[i];
+        if (!compiled(example->day, days, count)) {
+            continue;
+        }
+        char path[1024];
+        snprintf(path, sizeof path, "%s/day%u/example.txt", data, (unsigned)example->day);
+        size_t len;
+        char *input = read_file(path, &len);
+        if (!input) {
+            printf("Day %u: unable to read %s\n", (unsigned)example->day, path);
+            failures++;
+            continue;
+        }
+
+        for (uint8_t part = 1; part <= 2; part++) {
+            char *output = NULL;
+            AocStatus status =
+                aoc_solve(2021, example->day, part, (const uint8_t *)input, len, &output);
+            const char *expected = example->answers[part - 1];
+            int pass = status == AOC_OK && output && strcmp(output, expected) == 0;
+            printf("Day %u part %u: %s (%s)\n", (unsigned)example->day, (unsigned)part,
+                   output ? output : "(null)", pass ? "pass" : "FAIL");
+            failures += !pass;
+            aoc_free_string(output);
+        }
+        free(input);
+    }
+
+    /* Errors come back the same way as answers */
+    char *output = NULL;
+    if (aoc_solve(2021, 26, 1, (const uint8_t *)"", 0, &output) != AOC_UNKNOWN_DAY) {
+        printf("Day 26 should be unknown\n");
+        failures++;
+    }
+    aoc_free_string(output);
+
+    printf("%d failures\n", failures);
+    return failures == 0 ? EXIT_SUCCESS : EXIT_FAILURE;
+}
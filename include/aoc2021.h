@@ -0,0 +1,72 @@
+/* Advent of Code 2021 solutions as a C library, see ffi/examples.c for a caller */
+
+#ifndef AOC2021_H
+#define AOC2021_H
+
+/* Generated from src/ffi.rs, edit that and run its tests with AOC_UPDATE_HEADER=1 */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef enum AocStatus {
+  /**
+   * *output holds the answer
+   */
+  AOC_OK = 0,
+  /**
+   * *output holds the solver's error
+   */
+  AOC_SOLVE_ERROR = 1,
+  /**
+   * The day isn't compiled in or has no such part, *output holds a message
+   */
+  AOC_UNKNOWN_DAY = 2,
+  /**
+   * A null pointer or an input that isn't UTF-8, *output holds a message if it can
+   */
+  AOC_INVALID_ARGUMENT = 3,
+} AocStatus;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Writes the days compiled in for `year` to `days`, up to `capacity` of them, and returns how
+ * many there are in total - call with a null `days` to size the buffer.
+ *
+ * # Safety
+ * `days` is null or points to at least `capacity` writable bytes.
+ */
+size_t aoc_days(uint16_t year, uint8_t *days, size_t capacity);
+
+/**
+ * Solves one part of a day for the `len` bytes of `input`. On return *output is the answer or
+ * an error message for the caller to release with aoc_free_string.
+ *
+ * # Safety
+ * `input` points to `len` readable bytes (or `len` is 0) and `output` is null or writable.
+ */
+enum AocStatus aoc_solve(uint16_t year,
+                         uint8_t day,
+                         uint8_t part,
+                         const uint8_t *input,
+                         size_t len,
+                         char **output);
+
+/**
+ * Releases a string returned by aoc_solve. Null is ignored.
+ *
+ * # Safety
+ * `text` is null or came from aoc_solve and hasn't been released already.
+ */
+void aoc_free_string(char *text);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* AOC2021_H */
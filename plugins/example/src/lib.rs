@@ -0,0 +1,123 @@
+// Example plugin for the aoc_2021 CLI - day 1 of 2021 again, with plain loops instead of the
+// iterator chains of the built in day so the two can be compared. Build it with
+// `cargo build -p aoc_plugin_example` and copy the library from target/debug into plugins/, or
+// run the CLI with --plugins target/debug.
+//
+// The types mirror the C ABI described in the main crate's src/plugin.rs.
+
+use std::ffi::{c_char, CString};
+use std::panic;
+
+type SolveExtern = unsafe extern "C" fn(*const u8, usize, *mut *mut c_char) -> i32;
+
+#[repr(C)]
+pub struct AocSolver {
+    year: u16,
+    day: u8,
+    part: u8,
+    solve: SolveExtern,
+}
+
+#[repr(C)]
+pub struct AocPlugin {
+    abi_version: u32,
+    solvers: *const AocSolver,
+    count: usize,
+    free_string: unsafe extern "C" fn(*mut c_char),
+}
+
+// The raw pointer only ever points at the static SOLVERS, so sharing it between threads is fine
+struct Registration(AocPlugin);
+unsafe impl Sync for Registration {}
+
+static SOLVERS: [AocSolver; 2] = [
+    AocSolver {
+        year: 2021,
+        day: 1,
+        part: 1,
+        solve: part1,
+    },
+    AocSolver {
+        year: 2021,
+        day: 1,
+        part: 2,
+        solve: part2,
+    },
+];
+
+static PLUGIN: Registration = Registration(AocPlugin {
+    abi_version: 1,
+    solvers: &SOLVERS as *const [AocSolver; 2] as *const AocSolver,
+    count: 2,
+    free_string,
+});
+
+#[no_mangle]
+pub extern "C" fn aoc_register() -> *const AocPlugin {
+    &PLUGIN.0
+}
+
+fn depths(input: &[u8]) -> Result<Vec<u32>, String> {
+    let text = std::str::from_utf8(input).map_err(|e| e.to_string())?;
+    let mut depths = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        match line.trim().parse() {
+            Ok(depth) => depths.push(depth),
+            Err(_) => return Err(format!("Line {}: invalid depth {:?}", i + 1, line)),
+        }
+    }
+    Ok(depths)
+}
+
+// Comparing sums of three measurement windows only depends on the measurements three apart
+fn increases(depths: &[u32], gap: usize) -> usize {
+    let mut count = 0;
+    for i in gap..depths.len() {
+        if depths[i] > depths[i - gap] {
+            count += 1;
+        }
+    }
+    count
+}
+
+// Runs a solver on the input, catching any panic, and hands the answer or error to the host
+unsafe fn respond(
+    input: *const u8,
+    len: usize,
+    output: *mut *mut c_char,
+    solve: fn(&[u8]) -> Result<String, String>,
+) -> i32 {
+    let input = if input.is_null() {
+        &[]
+    } else {
+        std::slice::from_raw_parts(input, len)
+    };
+    let result = panic::catch_unwind(|| solve(input))
+        .unwrap_or_else(|_| Err(String::from("example plugin panicked")));
+    let (status, text) = match result {
+        Ok(answer) => (0, answer),
+        Err(e) => (1, e),
+    };
+    if !output.is_null() {
+        *output = CString::new(text).unwrap_or_default().into_raw();
+    }
+    status
+}
+
+unsafe extern "C" fn part1(input: *const u8, len: usize, output: *mut *mut c_char) -> i32 {
+    respond(input, len, output, |input| {
+        depths(input).map(|depths| increases(&depths, 1).to_string())
+    })
+}
+
+unsafe extern "C" fn part2(input: *const u8, len: usize, output: *mut *mut c_char) -> i32 {
+    respond(input, len, output, |input| {
+        depths(input).map(|depths| increases(&depths, 3).to_string())
+    })
+}
+
+unsafe extern "C" fn free_string(text: *mut c_char) {
+    if !text.is_null() {
+        drop(CString::from_raw(text));
+    }
+}
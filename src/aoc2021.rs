@@ -1,11 +1,55 @@
-pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
+// Solutions are organised by year, see registry for what got compiled in
+pub mod y2021;
+
+// The 2021 days from before the library hosted more than one year
+#[cfg(feature = "day1")]
+pub use y2021::day1;
+#[cfg(feature = "day2")]
+pub use y2021::day2;
+#[cfg(feature = "day3")]
+pub use y2021::day3;
+#[cfg(feature = "day4")]
+pub use y2021::day4;
+#[cfg(feature = "day5")]
+pub use y2021::day5;
+#[cfg(feature = "day6")]
+pub use y2021::day6;
+#[cfg(feature = "day7")]
+pub use y2021::day7;
+#[cfg(feature = "day8")]
+pub use y2021::day8;
+
+pub mod ffi;
+#[cfg(feature = "plugins")]
+pub mod plugin;
+pub mod pool;
+pub mod registry;
+
+// Only the CLI uses these
+#[cfg(feature = "cli")]
+pub mod bench;
+#[cfg(feature = "cli")]
+pub mod cli;
+#[cfg(feature = "cli")]
+pub mod embedded;
+#[cfg(feature = "cli")]
+pub mod leaderboard;
+#[cfg(feature = "cli")]
+pub mod memory;
+#[cfg(feature = "cli")]
+pub mod records;
+#[cfg(feature = "cli")]
+pub mod report;
+#[cfg(feature = "cli")]
+pub mod run;
+#[cfg(feature = "cli")]
+pub mod status;
+// The reference solvers and the differential, property and fuzz harnesses, for the unit tests and
+// for the examples and fuzz targets built with the testing feature
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+#[cfg(feature = "cli")]
+pub mod tui;
 
 // Utilities for all the solutions
 pub mod utils {
@@ -14,4 +58,15 @@ pub mod utils {
     pub fn read_file(filename: std::path::PathBuf) -> String {
         fs::read_to_string(filename).expect("Unable to open file")
     }
+
+    // The message a caught panic was raised with
+    pub fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+        if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            String::from("unknown panic")
+        }
+    }
 }
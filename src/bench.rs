@@ -0,0 +1,240 @@
+// Benchmarks every compiled in day of a year by repeating a full run. Runtimes are the minimum and
+// mean over the iterations, and with memory counting enabled each part also gets its allocation
+// count, bytes allocated and peak live bytes (taken from the first iteration, as they don't vary).
+
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::memory::{format_bytes, MemStats};
+use crate::records::format_runtime;
+use crate::run::{self, Verdict};
+
+#[derive(Debug, Clone)]
+pub struct BenchRow {
+    pub day: u8,
+    pub plugin: Option<&'static str>,
+    pub part: u8,
+    pub min: Duration,
+    pub mean: Duration,
+    pub memory: Option<MemStats>,
+    pub error: Option<String>,
+    // Whether the first iteration's answer matched answers.txt
+    pub verified: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Bench {
+    pub year: u16,
+    pub iterations: u32,
+    pub rows: Vec<BenchRow>,
+    pub warnings: Vec<String>,
+}
+
+pub fn bench_year(data: &Path, year: u16, iterations: u32) -> Bench {
+    let iterations = iterations.max(1);
+    let first = run::run_year(data, year);
+    let mut warnings = first.warnings.clone();
+    for day in &first.days {
+        for warning in &day.warnings {
+            warnings.push(format!("Day {}: {}", day.day, warning));
+        }
+    }
+
+    let mut rows: Vec<BenchRow> = first
+        .days
+        .iter()
+        .flat_map(|day| day.parts.iter().map(move |part| (day, part)))
+        .map(|(day, part)| BenchRow {
+            day: day.day,
+            plugin: day.plugin,
+            part: part.part,
+            min: part.runtime,
+            mean: part.runtime,
+            memory: part.memory,
+            error: match &part.verdict {
+                Verdict::Error(e) => Some(e.clone()),
+                _ => None,
+            },
+            verified: part.verdict == Verdict::Pass,
+        })
+        .collect();
+
+    let mut totals: Vec<Duration> = rows.iter().map(|row| row.mean).collect();
+    for _ in 1..iterations {
+        let repeat = run::run_year(data, year);
+        for ((row, total), (_, part)) in rows.iter_mut().zip(&mut totals).zip(repeat.parts()) {
+            row.min = row.min.min(part.runtime);
+            *total += part.runtime;
+        }
+    }
+    for (row, total) in rows.iter_mut().zip(totals) {
+        row.mean = total / iterations;
+    }
+
+    Bench {
+        year,
+        iterations,
+        rows,
+        warnings,
+    }
+}
+
+pub fn render(bench: &Bench) -> String {
+    let memory = bench.rows.iter().any(|row| row.memory.is_some());
+    let mut output = String::new();
+    writeln!(
+        output,
+        "Advent of Code {}, {} iterations",
+        bench.year, bench.iterations
+    )
+    .unwrap();
+    write!(
+        output,
+        "{:>3}  {:>4}  {:>9}  {:>9}",
+        "Day", "Part", "Min", "Mean"
+    )
+    .unwrap();
+    if memory {
+        write!(output, "  {:>8}  {:>10}  {:>10}", "Allocs", "Bytes", "Peak").unwrap();
+    }
+    writeln!(output).unwrap();
+
+    for row in &bench.rows {
+        write!(
+            output,
+            "{:>3}  {:>4}  {:>9}  {:>9}",
+            row.day,
+            row.part,
+            format_runtime(row.min),
+            format_runtime(row.mean)
+        )
+        .unwrap();
+        if let Some(stats) = row.memory {
+            write!(
+                output,
+                "  {:>8}  {:>10}  {:>10}",
+                stats.allocations,
+                format_bytes(stats.bytes),
+                format_bytes(stats.peak)
+            )
+            .unwrap();
+        }
+        if let Some(plugin) = row.plugin {
+            write!(output, "  ({} plugin)", plugin).unwrap();
+        }
+        if let Some(e) = &row.error {
+            write!(output, "  Error: {}", e).unwrap();
+        }
+        writeln!(output).unwrap();
+    }
+    for warning in &bench.warnings {
+        writeln!(output, "Warning: {}", warning).unwrap();
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(day: u8, part: u8, memory: Option<MemStats>) -> BenchRow {
+        BenchRow {
+            day,
+            plugin: None,
+            part,
+            min: Duration::from_micros(800),
+            mean: Duration::from_micros(1_200),
+            memory,
+            error: None,
+            verified: true,
+        }
+    }
+
+    fn bench(rows: Vec<BenchRow>) -> Bench {
+        Bench {
+            year: 2021,
+            iterations: 3,
+            rows,
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_render_heading() {
+        let rendered = render(&bench(Vec::new()));
+        assert_eq!(
+            rendered,
+            "Advent of Code 2021, 3 iterations\nDay  Part        Min       Mean\n"
+        );
+    }
+
+    #[test]
+    fn test_render_runtimes() {
+        let rendered = render(&bench(vec![row(1, 1, None)]));
+        assert!(rendered.contains("\n  1     1      800us      1.2ms\n"));
+    }
+
+    #[test]
+    fn test_memory_columns_only_when_measured() {
+        assert!(!render(&bench(vec![row(1, 1, None)])).contains("Allocs"));
+        let stats = MemStats {
+            allocations: 12,
+            bytes: 2048,
+            peak: 1024,
+        };
+        let measured = render(&bench(vec![row(1, 1, Some(stats)), row(1, 2, None)]));
+        assert!(measured.contains("Allocs"));
+        assert!(measured.contains("12     2.0 KiB     1.0 KiB"));
+        // A part without stats leaves its columns empty
+        assert!(measured.ends_with("  1     2      800us      1.2ms\n"));
+    }
+
+    #[test]
+    fn test_render_plugin_and_error() {
+        let mut broken = row(3, 2, None);
+        broken.plugin = Some("faster");
+        broken.error = Some(String::from("Bad input"));
+        assert!(render(&bench(vec![broken])).contains("1.2ms  (faster plugin)  Error: Bad input\n"));
+    }
+
+    #[test]
+    fn test_render_warnings() {
+        let mut warned = bench(Vec::new());
+        warned.warnings.push(String::from("Day 4: Unable to read"));
+        assert!(render(&warned).ends_with("Warning: Day 4: Unable to read\n"));
+    }
+
+    #[cfg(feature = "day1")]
+    #[test]
+    fn test_bench_compiled_days() {
+        let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("data");
+        let bench = bench_year(&data, 2021, 2);
+        assert_eq!(
+            bench.rows.len(),
+            crate::registry::days(2021).len() * 2,
+            "{:?}",
+            bench.warnings
+        );
+        assert!(bench.rows.iter().all(|row| row.min <= row.mean));
+    }
+
+    #[cfg(feature = "day1")]
+    #[test]
+    fn test_bench_at_least_one_iteration() {
+        let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("data");
+        assert_eq!(bench_year(&data, 2021, 0).iterations, 1);
+    }
+
+    #[cfg(feature = "day1")]
+    #[test]
+    fn test_bench_without_inputs_warns() {
+        let data = std::env::temp_dir().join(format!("aoc_bench_missing_{}", std::process::id()));
+        let bench = bench_year(&data, 2021, 1);
+        assert!(bench.rows.is_empty());
+        assert!(bench
+            .warnings
+            .iter()
+            .any(|warning| warning.starts_with("Day 1: Unable to read")));
+    }
+}
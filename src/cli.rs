@@ -0,0 +1,900 @@
+// Description of the command line interface. main.rs takes its StructOpt names and help from the
+// options and commands here, and the shell completions and man page are rendered from the same
+// descriptions. Defaults and value parsing still live in main.rs, whose tests check each option
+// and command here against what StructOpt actually accepts.
+
+use std::fmt::Write;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::registry;
+
+pub const BIN: &str = env!("CARGO_PKG_NAME");
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const ABOUT: &str = "Runs the Advent of Code 2021 solutions";
+
+// What a shell should offer when completing a value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Values {
+    // A flag, which takes no value
+    None,
+    Years,
+    // Days compiled in for any year
+    Days,
+    Parts,
+    // Any path under data/
+    DataFiles,
+    Files,
+    Shells,
+    Formats,
+    Aggregates,
+    // Anomaly detection methods, which also take a :THRESHOLD
+    Methods,
+    // Any number, nothing to offer
+    Numbers,
+}
+
+impl Values {
+    pub fn candidates(&self) -> Vec<String> {
+        match self {
+            Values::Years => registry::years().iter().map(|y| y.to_string()).collect(),
+            Values::Days => {
+                let mut days: Vec<u8> = registry::solutions().iter().map(|s| s.day).collect();
+                days.sort_unstable();
+                days.dedup();
+                days.iter().map(|d| d.to_string()).collect()
+            }
+            Values::Parts => vec![String::from("1"), String::from("2")],
+            Values::None | Values::DataFiles | Values::Files | Values::Numbers => Vec::new(),
+            Values::Shells => SHELLS.iter().map(|s| s.to_string()).collect(),
+            Values::Formats => FORMATS.iter().map(|f| f.to_string()).collect(),
+            Values::Aggregates => AGGREGATES.iter().map(|a| a.to_string()).collect(),
+            Values::Methods => METHODS.iter().map(|m| m.to_string()).collect(),
+        }
+    }
+}
+
+pub struct Opt {
+    pub short: &'static str,
+    pub long: &'static str,
+    pub value: &'static str,
+    pub help: &'static str,
+    pub values: Values,
+}
+
+impl Opt {
+    pub fn takes_value(&self) -> bool {
+        self.values != Values::None
+    }
+}
+
+pub struct Command {
+    pub name: &'static str,
+    pub about: &'static str,
+    // Positional argument name and its values
+    pub arg: Option<(&'static str, Values)>,
+    pub options: &'static [Opt],
+}
+
+pub const YEAR: Opt = Opt {
+    short: "y",
+    long: "year",
+    value: "YEAR",
+    help: "Year the day belongs to",
+    values: Values::Years,
+};
+
+pub const DAY: Opt = Opt {
+    short: "d",
+    long: "day",
+    value: "DAY",
+    help: "Day to run",
+    values: Values::Days,
+};
+
+pub const PART: Opt = Opt {
+    short: "p",
+    long: "part",
+    value: "PART",
+    help: "Part to run, both parts when not given",
+    values: Values::Parts,
+};
+
+pub const FILE: Opt = Opt {
+    short: "f",
+    long: "file",
+    value: "FILE",
+    help: "Puzzle input, a directory of inputs or - for stdin, defaults to data/<YEAR>/day<DAY>/input.txt",
+    values: Values::DataFiles,
+};
+
+pub const ALL: Opt = Opt {
+    short: "a",
+    long: "all",
+    value: "",
+    help: "Runs every compiled in day of the year against its input",
+    values: Values::None,
+};
+
+pub const MEM: Opt = Opt {
+    short: "m",
+    long: "mem",
+    value: "",
+    help: "Counts allocations, bytes allocated and peak live bytes for each part, on one thread",
+    values: Values::None,
+};
+
+pub const RECORD: Opt = Opt {
+    short: "R",
+    long: "record",
+    value: "",
+    help: "Records the runtime of each part whose answer is verified in data/<YEAR>/runtimes.txt, \
+           for status, and removes it for parts whose answer isn't",
+    values: Values::None,
+};
+
+pub const JOBS: Opt = Opt {
+    short: "j",
+    long: "jobs",
+    value: "N",
+    help: "Threads to run parts, and days that split up their work, on",
+    values: Values::Numbers,
+};
+
+pub const PLUGINS: Opt = Opt {
+    short: "P",
+    long: "plugins",
+    value: "DIR",
+    help: "Loads the solution libraries in DIR, running their days alongside the built in ones",
+    values: Values::Files,
+};
+
+pub const EMBEDDED: Opt = Opt {
+    short: "e",
+    long: "embedded",
+    value: "",
+    help: "Reads inputs and answers built into the binary (the embedded feature) instead of data/",
+    values: Values::None,
+};
+
+pub const WINDOW: Opt = Opt {
+    short: "w",
+    long: "window",
+    value: "N",
+    help: "Day 1 only: compares sliding windows of N readings instead of running the parts, or \
+           with --anomalies judges each reading against N either side",
+    values: Values::Numbers,
+};
+
+pub const AGGREGATE: Opt = Opt {
+    short: "g",
+    long: "aggregate",
+    value: "AGGREGATE",
+    help: "Day 1 only: how each --window is reduced before comparing, sum when not given",
+    values: Values::Aggregates,
+};
+
+pub const PROFILE: Opt = Opt {
+    short: "r",
+    long: "profile",
+    value: "",
+    help: "Day 1 only: reports runs, jumps, plateaus and a histogram of the depth changes",
+    values: Values::None,
+};
+
+pub const EXPLAIN: Opt = Opt {
+    short: "x",
+    long: "explain",
+    value: "",
+    help:
+        "Day 1 only: lists each reading and three-reading sum as increased, decreased or no change",
+    values: Values::None,
+};
+
+pub const TOLERANCE: Opt = Opt {
+    short: "t",
+    long: "tolerance",
+    value: "T",
+    help: "Day 1 only: reads decimal depths, counting changes of T or less as no change",
+    values: Values::Numbers,
+};
+
+pub const PLOT: Opt = Opt {
+    short: "c",
+    long: "plot",
+    value: "PLOT",
+    help: "Day 1 only: charts the depths and window sums as braille or ascii, or to an .svg file",
+    values: Values::Files,
+};
+
+pub const ANOMALIES: Opt = Opt {
+    short: "A",
+    long: "anomalies",
+    value: "METHOD",
+    help: "Day 1 only: flags spikes by zscore, mad or delta:LIMIT and reanswers without them",
+    values: Values::Methods,
+};
+
+pub const TRAJECTORY: Opt = Opt {
+    short: "T",
+    long: "trajectory",
+    value: "OUT",
+    help: "Day 2 only: sums up the sub's path for --part's model, given summary or a .csv or .svg \
+           file to write it to",
+    values: Values::Files,
+};
+
+pub const OPTIONS: [Opt; 18] = [
+    YEAR, DAY, PART, FILE, ALL, MEM, RECORD, JOBS, PLUGINS, EMBEDDED, WINDOW, AGGREGATE, PROFILE,
+    EXPLAIN, TOLERANCE, PLOT, ANOMALIES, TRAJECTORY,
+];
+
+pub const COMPLETIONS: Command = Command {
+    name: "completions",
+    about: "Prints a completion script for bash, zsh or fish",
+    arg: Some(("SHELL", Values::Shells)),
+    options: &[],
+};
+
+pub const MAN: Command = Command {
+    name: "man",
+    about: "Prints the man page in roff format",
+    arg: None,
+    options: &[],
+};
+
+pub const STATUS: Command = Command {
+    name: "status",
+    about: "Shows stars, data files and runtimes for every day of the year",
+    arg: None,
+    options: &[],
+};
+
+pub const FORMAT: Opt = Opt {
+    short: "F",
+    long: "format",
+    value: "FORMAT",
+    help: "Output format",
+    values: Values::Formats,
+};
+
+pub const LEADERBOARD: Command = Command {
+    name: "leaderboard",
+    about: "Ranks a private leaderboard exported as JSON and recalculates its local scores",
+    arg: Some(("FILE", Values::Files)),
+    options: &[FORMAT],
+};
+
+pub const OUT: Opt = Opt {
+    short: "o",
+    long: "out",
+    value: "FILE",
+    help: "Report to write, a .html or .md file",
+    values: Values::Files,
+};
+
+pub const REPORT: Command = Command {
+    name: "report",
+    about: "Runs every compiled in day of the year and writes an HTML or Markdown report",
+    arg: None,
+    options: &[OUT],
+};
+
+pub const ITERATIONS: Opt = Opt {
+    short: "n",
+    long: "iterations",
+    value: "N",
+    help: "Number of full runs to time",
+    values: Values::Numbers,
+};
+
+pub const BENCH: Command = Command {
+    name: "bench",
+    about: "Times every compiled in day of the year, add --mem for memory columns",
+    arg: None,
+    options: &[ITERATIONS],
+};
+
+pub const TUI: Command = Command {
+    name: "tui",
+    about: "Browses the year's days, running parts against the input or example",
+    arg: None,
+    options: &[],
+};
+
+pub const COMMANDS: [Command; 7] = [COMPLETIONS, MAN, STATUS, LEADERBOARD, REPORT, BENCH, TUI];
+
+pub const SHELLS: [&str; 3] = ["bash", "zsh", "fish"];
+
+pub const FORMATS: [&str; 2] = ["table", "json"];
+
+pub const AGGREGATES: [&str; 5] = ["sum", "mean", "min", "max", "median"];
+
+pub const METHODS: [&str; 3] = ["zscore", "mad", "delta"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+#[derive(Debug, Error)]
+#[error("Unknown shell {0}, expected one of bash, zsh or fish")]
+pub struct UnknownShell(String);
+
+impl FromStr for Shell {
+    type Err = UnknownShell;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            _ => Err(UnknownShell(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Table,
+    Json,
+}
+
+#[derive(Debug, Error)]
+#[error("Unknown format {0}, expected table or json")]
+pub struct UnknownFormat(String);
+
+impl FromStr for Format {
+    type Err = UnknownFormat;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            _ => Err(UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+pub fn completions(shell: Shell) -> String {
+    match shell {
+        Shell::Bash => bash(),
+        Shell::Zsh => zsh(),
+        Shell::Fish => fish(),
+    }
+}
+
+fn bash_reply(values: Values, indent: &str) -> String {
+    match values {
+        Values::DataFiles => format!(
+            "{indent}compopt -o filenames 2>/dev/null\n\
+             {indent}COMPREPLY=($(compgen -f -- \"${{cur:-data/}}\"))\n",
+            indent = indent
+        ),
+        Values::Files => format!(
+            "{indent}compopt -o filenames 2>/dev/null\n\
+             {indent}COMPREPLY=($(compgen -f -- \"$cur\"))\n",
+            indent = indent
+        ),
+        values => format!(
+            "{}COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n",
+            indent,
+            values.candidates().join(" ")
+        ),
+    }
+}
+
+fn option_words(options: &[Opt]) -> Vec<String> {
+    options
+        .iter()
+        .flat_map(|opt| [format!("-{}", opt.short), format!("--{}", opt.long)])
+        .collect()
+}
+
+fn bash() -> String {
+    let function = format!("_{}", BIN);
+    let mut script = String::new();
+    writeln!(script, "{}() {{", function).unwrap();
+    writeln!(script, "    local cur=\"${{COMP_WORDS[COMP_CWORD]}}\"").unwrap();
+    writeln!(script, "    local prev=\"${{COMP_WORDS[COMP_CWORD-1]}}\"").unwrap();
+
+    // Option values first, every option name is unique across the commands
+    script.push_str("    case \"$prev\" in\n");
+    let command_options = COMMANDS.iter().flat_map(|command| command.options.iter());
+    for opt in OPTIONS
+        .iter()
+        .chain(command_options)
+        .filter(|opt| opt.takes_value())
+    {
+        writeln!(script, "        -{}|--{})", opt.short, opt.long).unwrap();
+        script.push_str(&bash_reply(opt.values, "            "));
+        script.push_str("            return\n            ;;\n");
+    }
+    script.push_str("    esac\n");
+
+    // Then a command's positional argument and options, or the top level commands and options
+    script.push_str("    case \"${COMP_WORDS[1]}\" in\n");
+    for command in COMMANDS.iter() {
+        writeln!(script, "        {})", command.name).unwrap();
+        let words = option_words(command.options).join(" ");
+        match command.arg {
+            Some((_, values)) => {
+                script.push_str(
+                    "            if [[ \"$cur\" != -* && \"$COMP_CWORD\" -eq 2 ]]; then\n",
+                );
+                script.push_str(&bash_reply(values, "                "));
+                script.push_str("            else\n");
+                writeln!(
+                    script,
+                    "                COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))",
+                    words
+                )
+                .unwrap();
+                script.push_str("            fi\n");
+            }
+            None => writeln!(
+                script,
+                "            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))",
+                words
+            )
+            .unwrap(),
+        }
+        script.push_str("            return\n            ;;\n");
+    }
+    script.push_str("    esac\n");
+
+    let mut words: Vec<String> = COMMANDS.iter().map(|c| c.name.to_string()).collect();
+    words.extend(option_words(&OPTIONS));
+    writeln!(
+        script,
+        "    COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))",
+        words.join(" ")
+    )
+    .unwrap();
+    script.push_str("}\n");
+    writeln!(script, "complete -F {} {}", function, BIN).unwrap();
+    script
+}
+
+// Single quotes can't be escaped inside a single quoted zsh string, so they're closed around it
+fn zsh_quote(text: &str) -> String {
+    text.replace('\'', "'\\''")
+}
+
+// Help text inside an _arguments spec or a _describe entry, where brackets end the help and a
+// colon starts the next field. Backslashed like clap_complete does, then quoted
+fn zsh_help(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('[', "\\[")
+        .replace(']', "\\]")
+        .replace(':', "\\:")
+        .replace('$', "\\$")
+        .replace('`', "\\`");
+    zsh_quote(&escaped)
+}
+
+fn zsh_action(values: Values) -> String {
+    match values {
+        Values::DataFiles => String::from("_files -W data -P data/"),
+        Values::Files => String::from("_files"),
+        values => format!("({})", values.candidates().join(" ")),
+    }
+}
+
+fn zsh_option(opt: &Opt) -> String {
+    if !opt.takes_value() {
+        return format!(
+            "'(-{short} --{long})'{{-{short},--{long}}}'[{help}]'",
+            short = opt.short,
+            long = opt.long,
+            help = zsh_help(opt.help)
+        );
+    }
+    format!(
+        "'(-{short} --{long})'{{-{short},--{long}}}'[{help}]:{value}:{action}'",
+        short = opt.short,
+        long = opt.long,
+        help = zsh_help(opt.help),
+        value = opt.value,
+        action = zsh_action(opt.values)
+    )
+}
+
+fn zsh() -> String {
+    let mut script = String::new();
+    writeln!(script, "#compdef {}", BIN).unwrap();
+    writeln!(script).unwrap();
+    writeln!(script, "_{}() {{", BIN).unwrap();
+    script.push_str("    local state\n");
+    script.push_str("    _arguments -C \\\n");
+    for opt in OPTIONS.iter() {
+        writeln!(script, "        {} \\", zsh_option(opt)).unwrap();
+    }
+    script.push_str("        '1: :->command' \\\n");
+    script.push_str("        '*:: :->argument'\n");
+    script.push_str("    case $state in\n");
+    script.push_str("        command)\n");
+    script.push_str("            local -a commands\n");
+    script.push_str("            commands=(\n");
+    for command in COMMANDS.iter() {
+        writeln!(
+            script,
+            "                '{}:{}'",
+            command.name,
+            zsh_help(command.about)
+        )
+        .unwrap();
+    }
+    script.push_str("            )\n");
+    script.push_str("            _describe command commands\n");
+    script.push_str("            ;;\n");
+    script.push_str("        argument)\n");
+    script.push_str("            case $words[1] in\n");
+    for command in COMMANDS.iter() {
+        let mut specs: Vec<String> = command.options.iter().map(zsh_option).collect();
+        if let Some((name, values)) = command.arg {
+            specs.push(format!("'1:{}:{}'", name, zsh_action(values)));
+        }
+        if !specs.is_empty() {
+            writeln!(
+                script,
+                "                {}) _arguments {} ;;",
+                command.name,
+                specs.join(" ")
+            )
+            .unwrap();
+        }
+    }
+    script.push_str("            esac\n");
+    script.push_str("            ;;\n");
+    script.push_str("    esac\n");
+    script.push_str("}\n");
+    writeln!(script).unwrap();
+    writeln!(script, "_{} \"$@\"", BIN).unwrap();
+    script
+}
+
+fn fish_action(values: Values) -> String {
+    match values {
+        Values::DataFiles => String::from(
+            "-r -f -a \"(__fish_complete_path (commandline -ct | string replace -r '^\\$' data/))\"",
+        ),
+        Values::Files => String::from("-r -F"),
+        Values::None => String::from("-f"),
+        values => format!("-x -a \"{}\"", values.candidates().join(" ")),
+    }
+}
+
+fn fish_option(condition: &str, opt: &Opt) -> String {
+    format!(
+        "complete -c {} -n {} -s {} -l {} {} -d '{}'\n",
+        BIN,
+        condition,
+        opt.short,
+        opt.long,
+        fish_action(opt.values),
+        opt.help.replace('\'', "\\'")
+    )
+}
+
+fn fish() -> String {
+    let mut script = String::new();
+    for opt in OPTIONS.iter() {
+        script.push_str(&fish_option("__fish_use_subcommand", opt));
+    }
+    for command in COMMANDS.iter() {
+        writeln!(
+            script,
+            "complete -c {} -n __fish_use_subcommand -f -a {} -d '{}'",
+            BIN,
+            command.name,
+            command.about.replace('\'', "\\'")
+        )
+        .unwrap();
+        let condition = format!("\"__fish_seen_subcommand_from {}\"", command.name);
+        match command.arg {
+            Some((_, values)) => writeln!(
+                script,
+                "complete -c {} -n {} {}",
+                BIN,
+                condition,
+                fish_action(values)
+            )
+            .unwrap(),
+            None => writeln!(script, "complete -c {} -n {} -f", BIN, condition).unwrap(),
+        }
+        for opt in command.options {
+            script.push_str(&fish_option(&condition, opt));
+        }
+    }
+    script
+}
+
+// Escapes text for roff - backslashes, dashes and a leading dot or quote
+fn roff(text: &str) -> String {
+    let escaped = text.replace('\\', "\\e").replace('-', "\\-");
+    if escaped.starts_with('.') || escaped.starts_with('\'') {
+        format!("\\&{}", escaped)
+    } else {
+        escaped
+    }
+}
+
+// The option as it appears in the synopsis
+fn man_usage(opt: &Opt) -> String {
+    if opt.takes_value() {
+        format!(
+            "[\\fB\\-{}\\fR \\fI{}\\fR]",
+            roff(opt.short),
+            roff(opt.value)
+        )
+    } else {
+        format!("[\\fB\\-{}\\fR]", roff(opt.short))
+    }
+}
+
+fn man_option(page: &mut String, opt: &Opt) {
+    page.push_str(".TP\n");
+    write!(
+        page,
+        "\\fB\\-{}\\fR, \\fB\\-\\-{}\\fR",
+        roff(opt.short),
+        roff(opt.long)
+    )
+    .unwrap();
+    if opt.takes_value() {
+        write!(page, " \\fI{}\\fR", roff(opt.value)).unwrap();
+    }
+    page.push('\n');
+    writeln!(page, "{}", roff(opt.help)).unwrap();
+    let candidates = opt.values.candidates();
+    if !candidates.is_empty() {
+        writeln!(page, "Possible values: {}", candidates.join(", ")).unwrap();
+    }
+}
+
+pub fn man_page() -> String {
+    let mut page = String::new();
+    writeln!(
+        page,
+        ".TH {} 1 \"\" \"{} {}\" \"User Commands\"",
+        roff(&BIN.to_uppercase()),
+        roff(BIN),
+        VERSION
+    )
+    .unwrap();
+
+    page.push_str(".SH NAME\n");
+    writeln!(page, "{} \\- {}", roff(BIN), roff(ABOUT)).unwrap();
+
+    page.push_str(".SH SYNOPSIS\n");
+    writeln!(page, ".B {}", roff(BIN)).unwrap();
+    for opt in OPTIONS.iter() {
+        writeln!(page, "{}", man_usage(opt)).unwrap();
+    }
+    for command in COMMANDS.iter() {
+        page.push_str(".br\n");
+        writeln!(page, ".B {}", roff(BIN)).unwrap();
+        writeln!(page, "\\fB{}\\fR", roff(command.name)).unwrap();
+        for opt in command.options {
+            writeln!(page, "{}", man_usage(opt)).unwrap();
+        }
+        if let Some((name, _)) = command.arg {
+            writeln!(page, "\\fI{}\\fR", roff(name)).unwrap();
+        }
+    }
+
+    page.push_str(".SH OPTIONS\n");
+    for opt in OPTIONS.iter() {
+        man_option(&mut page, opt);
+    }
+
+    page.push_str(".SH COMMANDS\n");
+    for command in COMMANDS.iter() {
+        page.push_str(".TP\n");
+        match command.arg {
+            Some((name, values)) => {
+                writeln!(
+                    page,
+                    "\\fB{}\\fR \\fI{}\\fR",
+                    roff(command.name),
+                    roff(name)
+                )
+                .unwrap();
+                writeln!(page, "{}", roff(command.about)).unwrap();
+                let candidates = values.candidates();
+                if !candidates.is_empty() {
+                    writeln!(page, "Possible values: {}", candidates.join(", ")).unwrap();
+                }
+            }
+            None => {
+                writeln!(page, "\\fB{}\\fR", roff(command.name)).unwrap();
+                writeln!(page, "{}", roff(command.about)).unwrap();
+            }
+        }
+        if !command.options.is_empty() {
+            page.push_str(".RS\n");
+            for opt in command.options {
+                man_option(&mut page, opt);
+            }
+            page.push_str(".RE\n");
+        }
+    }
+
+    page.push_str(".SH FILES\n");
+    page.push_str(".TP\n");
+    writeln!(page, "{}", roff("data/<YEAR>/day<DAY>/input.txt")).unwrap();
+    page.push_str("Default puzzle input for each day\n");
+    page
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compiled_days() -> String {
+        registry::days(crate::y2021::YEAR)
+            .iter()
+            .map(|d| d.to_string())
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    fn every_option() -> impl Iterator<Item = &'static Opt> {
+        OPTIONS
+            .iter()
+            .chain(COMMANDS.iter().flat_map(|command| command.options.iter()))
+    }
+
+    #[test]
+    fn test_completions_list_compiled_days() {
+        for shell in SHELLS {
+            let script = completions(shell.parse().unwrap());
+            assert!(
+                script.contains(&compiled_days()),
+                "{} script is missing days",
+                shell
+            );
+        }
+    }
+
+    #[test]
+    fn test_completions_offer_data_files() {
+        for shell in SHELLS {
+            let script = completions(shell.parse().unwrap());
+            assert!(
+                script.contains("data/"),
+                "{} script is missing data/",
+                shell
+            );
+        }
+    }
+
+    #[test]
+    fn test_completions_list_commands() {
+        for shell in SHELLS {
+            let script = completions(shell.parse().unwrap());
+            for command in COMMANDS.iter() {
+                assert!(script.contains(command.name), "{} {}", shell, command.name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bash_completion() {
+        let script = completions(Shell::Bash);
+        assert!(script.starts_with(&format!("_{}() {{\n", BIN)));
+        assert!(script.ends_with(&format!("complete -F _{} {}\n", BIN, BIN)));
+        assert!(script.contains("        -y|--year)\n"));
+    }
+
+    #[test]
+    fn test_zsh_completion() {
+        let script = completions(Shell::Zsh);
+        assert!(script.starts_with(&format!("#compdef {}\n", BIN)));
+        assert!(script.contains("'(-p --part)'{-p,--part}'["));
+        assert!(script.contains(":PART:(1 2)'"));
+    }
+
+    #[test]
+    fn test_fish_completion() {
+        let script = completions(Shell::Fish);
+        assert!(script.contains(&format!(
+            "complete -c {} -n __fish_use_subcommand -s p -l part -x -a \"1 2\"",
+            BIN
+        )));
+        assert!(script
+            .lines()
+            .all(|line| line.starts_with(&format!("complete -c {} ", BIN))));
+    }
+
+    #[test]
+    fn test_zsh_quote() {
+        assert_eq!(zsh_quote("it's"), "it'\\''s");
+    }
+
+    #[test]
+    fn test_zsh_help_escapes_brackets_and_colons() {
+        let opt = Opt {
+            help: "Bounds [low:high], or it's $ALL",
+            ..PART
+        };
+        assert_eq!(
+            zsh_option(&opt),
+            "'(-p --part)'{-p,--part}'[Bounds \\[low\\:high\\], or it'\\''s \\$ALL]:PART:(1 2)'"
+        );
+        assert_eq!(zsh_help("a\\b"), "a\\\\b");
+    }
+
+    #[test]
+    fn test_unknown_shell() {
+        assert_eq!(
+            "powershell".parse::<Shell>().unwrap_err().to_string(),
+            "Unknown shell powershell, expected one of bash, zsh or fish"
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!("json".parse::<Format>().unwrap(), Format::Json);
+        assert!("yaml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_candidates() {
+        assert_eq!(Values::Parts.candidates(), ["1", "2"]);
+        assert_eq!(Values::Days.candidates().join(" "), compiled_days());
+        assert!(Values::Numbers.candidates().is_empty());
+        assert_eq!(Values::Methods.candidates(), METHODS);
+    }
+
+    #[test]
+    fn test_option_names_are_unique() {
+        // The bash script completes values by option name alone
+        let mut names: Vec<&str> = every_option()
+            .flat_map(|opt| [opt.short, opt.long])
+            .collect();
+        let count = names.len();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), count);
+    }
+
+    #[test]
+    fn test_roff() {
+        assert_eq!(roff("--day"), "\\-\\-day");
+        assert_eq!(roff("a\\b"), "a\\eb");
+        assert_eq!(roff(".hidden"), "\\&.hidden");
+        assert_eq!(roff("'quoted'"), "\\&'quoted'");
+    }
+
+    #[test]
+    fn test_man_page_header() {
+        let page = man_page();
+        assert!(page.starts_with(".TH AOC_2021 1"));
+        assert!(page.contains(".SH SYNOPSIS\n"));
+    }
+
+    #[test]
+    fn test_man_page_escapes_dashes() {
+        let page = man_page();
+        assert!(page.contains("\\fB\\-\\-day\\fR \\fIDAY\\fR"));
+        assert!(page.contains("\\fBcompletions\\fR \\fISHELL\\fR"));
+        assert!(!page.contains(" --"));
+    }
+
+    #[test]
+    fn test_man_page_documents_everything() {
+        let page = man_page();
+        for opt in every_option() {
+            assert!(page.contains(&format!("\\fB\\-\\-{}\\fR", roff(opt.long))));
+        }
+        for command in COMMANDS.iter() {
+            assert!(page.contains(&format!("\\fB{}\\fR", command.name)));
+        }
+    }
+}
@@ -0,0 +1,73 @@
+// Puzzle data built into the binary for demos, so it runs without a data directory. With the
+// `embedded` feature build.rs includes every data/<year>/dayN/input.txt and example.txt, and each
+// year's answers.txt, and `enable` (the CLI's --embedded flag) makes `read` use them instead of the
+// filesystem. Files missing at build time are simply left out and read as "no embedded input".
+
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// (data/<year>/..., contents), empty without the feature
+include!(concat!(env!("OUT_DIR"), "/embedded.rs"));
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn available() -> bool {
+    cfg!(feature = "embedded")
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+// The embedded file the path ends in, wherever the data directory is
+pub fn file(path: &Path) -> Option<&'static str> {
+    FILES
+        .iter()
+        .find(|(key, _)| path.ends_with(key))
+        .map(|&(_, contents)| contents)
+}
+
+// Reads a data file from the binary once enabled, otherwise from the filesystem
+pub fn read(path: &Path) -> io::Result<String> {
+    if !enabled() {
+        return std::fs::read_to_string(path);
+    }
+    file(path)
+        .map(String::from)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no embedded input"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "embedded")]
+    #[test]
+    fn test_embedded_matches_data() {
+        let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("data");
+        for name in [
+            "2021/day1/input.txt",
+            "2021/day1/example.txt",
+            "2021/answers.txt",
+        ] {
+            let path = data.join(name);
+            assert_eq!(
+                file(&path),
+                Some(std::fs::read_to_string(&path).unwrap().as_str())
+            );
+            assert_eq!(file(&Path::new("data").join(name)), file(&path));
+        }
+    }
+
+    #[test]
+    fn test_missing_days_are_not_embedded() {
+        assert!(file(Path::new("data/2021/day25/input.txt")).is_none());
+        assert!(file(Path::new("elsewhere/input.txt")).is_none());
+        assert_eq!(FILES.is_empty(), !available());
+    }
+}
@@ -0,0 +1,219 @@
+// C API for calling the solutions from other languages, built as a cdylib or staticlib with
+// `cargo rustc --lib --crate-type`. include/aoc2021.h is generated from this file with cbindgen
+// and checked in. The tests fail when it's out of date, and rewrite it when run with
+// AOC_UPDATE_HEADER=1. ffi/examples.c is a C program using it.
+//
+// Answers and errors are returned as NUL terminated strings allocated by the library, which the
+// caller owns and must release with aoc_free_string (not free). Nothing panics across the boundary.
+//
+// Unlike the rest of the crate the API items have /// comments, which cbindgen copies into the
+// header for C callers.
+
+use std::ffi::{c_char, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use crate::registry;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AocStatus {
+    /// *output holds the answer
+    AocOk = 0,
+    /// *output holds the solver's error
+    AocSolveError = 1,
+    /// The day isn't compiled in or has no such part, *output holds a message
+    AocUnknownDay = 2,
+    /// A null pointer or an input that isn't UTF-8, *output holds a message if it can
+    AocInvalidArgument = 3,
+}
+
+fn to_c_string(text: String) -> *mut c_char {
+    // Answers and errors never contain NUL, but drop any rather than lose the whole message
+    CString::new(text.replace('\0', ""))
+        .map(CString::into_raw)
+        .unwrap_or(ptr::null_mut())
+}
+
+/// Writes the days compiled in for `year` to `days`, up to `capacity` of them, and returns how
+/// many there are in total - call with a null `days` to size the buffer.
+///
+/// # Safety
+/// `days` is null or points to at least `capacity` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_days(year: u16, days: *mut u8, capacity: usize) -> usize {
+    let compiled = registry::days(year);
+    if !days.is_null() {
+        for (i, &day) in compiled.iter().take(capacity).enumerate() {
+            *days.add(i) = day;
+        }
+    }
+    compiled.len()
+}
+
+/// Solves one part of a day for the `len` bytes of `input`. On return *output is the answer or
+/// an error message for the caller to release with aoc_free_string.
+///
+/// # Safety
+/// `input` points to `len` readable bytes (or `len` is 0) and `output` is null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    year: u16,
+    day: u8,
+    part: u8,
+    input: *const u8,
+    len: usize,
+    output: *mut *mut c_char,
+) -> AocStatus {
+    if output.is_null() {
+        return AocStatus::AocInvalidArgument;
+    }
+    let (status, text) = solve(year, day, part, input, len);
+    *output = to_c_string(text);
+    status
+}
+
+unsafe fn solve(year: u16, day: u8, part: u8, input: *const u8, len: usize) -> (AocStatus, String) {
+    let bytes = match (input.is_null(), len) {
+        (_, 0) => &[],
+        (true, _) => return (AocStatus::AocInvalidArgument, String::from("Input is null")),
+        (false, _) => std::slice::from_raw_parts(input, len),
+    };
+    let input = match std::str::from_utf8(bytes) {
+        Ok(input) => input,
+        Err(e) => {
+            return (
+                AocStatus::AocInvalidArgument,
+                format!("Input is not UTF-8: {}", e),
+            )
+        }
+    };
+    let solution = match registry::find(year, day) {
+        Some(solution) if solution.part(part).is_some() => solution,
+        _ => {
+            return (
+                AocStatus::AocUnknownDay,
+                format!("Day {} part {} of {} is not compiled in", day, part, year),
+            )
+        }
+    };
+    let result = panic::catch_unwind(AssertUnwindSafe(|| solution.solve(part, input)))
+        .unwrap_or_else(|_| Err(String::from("Solver panicked")));
+    match result {
+        Ok(answer) => (AocStatus::AocOk, answer),
+        Err(e) => (AocStatus::AocSolveError, e),
+    }
+}
+
+/// Releases a string returned by aoc_solve. Null is ignored.
+///
+/// # Safety
+/// `text` is null or came from aoc_solve and hasn't been released already.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_free_string(text: *mut c_char) {
+    if !text.is_null() {
+        drop(CString::from_raw(text));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "day1")]
+    fn call(day: u8, part: u8, input: &[u8]) -> (AocStatus, String) {
+        use std::ffi::CStr;
+
+        let mut output = ptr::null_mut();
+        let status =
+            unsafe { aoc_solve(2021, day, part, input.as_ptr(), input.len(), &mut output) };
+        let text = unsafe { CStr::from_ptr(output) }
+            .to_string_lossy()
+            .into_owned();
+        unsafe { aoc_free_string(output) };
+        (status, text)
+    }
+
+    #[cfg(feature = "day1")]
+    const EXAMPLE: &[u8] = b"199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+
+    #[test]
+    fn test_header_is_current() {
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+        let config = cbindgen::Config::from_file(root.join("cbindgen.toml")).unwrap();
+        let mut generated = Vec::new();
+        cbindgen::Builder::new()
+            .with_config(config)
+            .with_src(root.join("src/ffi.rs"))
+            .generate()
+            .unwrap()
+            .write(&mut generated);
+        let path = root.join("include/aoc2021.h");
+        if std::env::var_os("AOC_UPDATE_HEADER").is_some() {
+            std::fs::write(&path, &generated).unwrap();
+        }
+        assert!(
+            std::fs::read(&path).unwrap() == generated,
+            "include/aoc2021.h is out of date, rerun with AOC_UPDATE_HEADER=1"
+        );
+    }
+
+    #[test]
+    fn test_days_count_without_buffer() {
+        assert_eq!(
+            unsafe { aoc_days(2021, ptr::null_mut(), 0) },
+            registry::days(2021).len()
+        );
+    }
+
+    #[test]
+    fn test_days_fill_caller_buffer() {
+        let compiled = registry::days(2021);
+        let mut days = [0u8; 2];
+        let count = unsafe { aoc_days(2021, days.as_mut_ptr(), days.len()) };
+        assert_eq!(count, compiled.len());
+        for (written, day) in days.iter().zip(&compiled) {
+            assert_eq!(written, day);
+        }
+    }
+
+    #[test]
+    fn test_days_of_unknown_year() {
+        let mut days = [0u8; 2];
+        assert_eq!(unsafe { aoc_days(1999, days.as_mut_ptr(), 2) }, 0);
+    }
+
+    #[cfg(feature = "day1")]
+    #[test]
+    fn test_solve_answers() {
+        assert_eq!(call(1, 1, EXAMPLE), (AocStatus::AocOk, String::from("7")));
+        assert_eq!(call(1, 2, EXAMPLE), (AocStatus::AocOk, String::from("5")));
+    }
+
+    #[cfg(feature = "day1")]
+    #[test]
+    fn test_solve_error() {
+        assert_eq!(call(1, 1, b"12\nabc\n").0, AocStatus::AocSolveError);
+    }
+
+    #[cfg(feature = "day1")]
+    #[test]
+    fn test_unknown_part() {
+        assert_eq!(call(1, 3, EXAMPLE).0, AocStatus::AocUnknownDay);
+    }
+
+    #[cfg(feature = "day1")]
+    #[test]
+    fn test_input_must_be_utf8() {
+        assert_eq!(call(1, 1, b"\xff\n").0, AocStatus::AocInvalidArgument);
+    }
+
+    #[cfg(feature = "day1")]
+    #[test]
+    fn test_null_output() {
+        assert_eq!(
+            unsafe { aoc_solve(2021, 1, 1, EXAMPLE.as_ptr(), 3, ptr::null_mut()) },
+            AocStatus::AocInvalidArgument
+        );
+    }
+}
@@ -0,0 +1,411 @@
+// Private leaderboard statistics from the JSON adventofcode.com exports at
+// /<year>/leaderboard/private/view/<id>.json - the ranking, how long each member took to get from
+// part 1 to part 2 of every day and the local score recalculated from the star timestamps.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum LeaderboardError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("Invalid leaderboard JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Member {member}: invalid day {day:?}")]
+    InvalidDay { member: u64, day: String },
+    #[error("Member {member}: invalid part {part:?} on day {day}")]
+    InvalidPart { member: u64, day: u8, part: String },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    #[serde(deserialize_with = "number")]
+    pub owner_id: i64,
+    pub members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Member {
+    #[serde(deserialize_with = "number")]
+    pub id: u64,
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "number")]
+    pub stars: u32,
+    #[serde(default, deserialize_with = "number")]
+    pub local_score: u64,
+    // Day -> part -> star, both keyed as strings in the export
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<String, BTreeMap<String, Star>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Star {
+    #[serde(deserialize_with = "number")]
+    pub get_star_ts: i64,
+}
+
+// Older exports quote their numbers
+fn number<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + FromStr,
+    T::Err: fmt::Display,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Number<T> {
+        Number(T),
+        Text(String),
+    }
+
+    match Number::deserialize(deserializer)? {
+        Number::Number(n) => Ok(n),
+        Number::Text(text) => text.parse().map_err(serde::de::Error::custom),
+    }
+}
+
+impl Member {
+    // Anonymous members are shown the way adventofcode.com shows them
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    // Star timestamps keyed on (day, part)
+    pub fn stars(&self) -> Result<BTreeMap<(u8, u8), i64>, LeaderboardError> {
+        let mut stars = BTreeMap::new();
+        for (day, parts) in &self.completion_day_level {
+            let day: u8 = day.parse().map_err(|_| LeaderboardError::InvalidDay {
+                member: self.id,
+                day: day.clone(),
+            })?;
+            for (part, star) in parts {
+                let part = match part.as_str() {
+                    "1" => 1,
+                    "2" => 2,
+                    _ => {
+                        return Err(LeaderboardError::InvalidPart {
+                            member: self.id,
+                            day,
+                            part: part.clone(),
+                        })
+                    }
+                };
+                stars.insert((day, part), star.get_star_ts);
+            }
+        }
+        Ok(stars)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Standing {
+    pub rank: usize,
+    pub id: u64,
+    pub name: String,
+    pub stars: usize,
+    pub local_score: u64,
+    // The score in the export, which only differs from ours if the export is stale
+    pub reported_local_score: u64,
+    // Seconds from part 1 to part 2, for each day with both stars
+    pub gaps: BTreeMap<u8, i64>,
+    #[serde(skip)]
+    last_star: i64,
+}
+
+pub fn load(path: &Path) -> Result<Leaderboard, LeaderboardError> {
+    parse(&fs::read_to_string(path)?)
+}
+
+pub fn parse(json: &str) -> Result<Leaderboard, LeaderboardError> {
+    Ok(serde_json::from_str(json)?)
+}
+
+// The local score gives the first member to get a star N points (N being the number of members),
+// the second N - 1 and so on, for every star of every day
+pub fn standings(leaderboard: &Leaderboard) -> Result<Vec<Standing>, LeaderboardError> {
+    let members: Vec<&Member> = leaderboard.members.values().collect();
+    let stars = members
+        .iter()
+        .map(|member| member.stars())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut scores = vec![0u64; members.len()];
+    // (timestamp, member id, member index) of everyone with each (day, part) star
+    let mut finishers = BTreeMap::new();
+    for (i, member_stars) in stars.iter().enumerate() {
+        for (&key, &ts) in member_stars {
+            finishers
+                .entry(key)
+                .or_insert_with(Vec::new)
+                .push((ts, members[i].id, i));
+        }
+    }
+    for mut finished in finishers.into_values() {
+        // Ties on the timestamp go to the lower member id so the score is deterministic
+        finished.sort_unstable();
+        for (place, &(_, _, i)) in finished.iter().enumerate() {
+            scores[i] += (members.len() - place) as u64;
+        }
+    }
+
+    let mut standings: Vec<Standing> = members
+        .iter()
+        .zip(&stars)
+        .zip(scores)
+        .map(|((member, member_stars), local_score)| {
+            let gaps = member_stars
+                .iter()
+                .filter(|((_, part), _)| *part == 2)
+                .filter_map(|(&(day, _), &part2)| {
+                    member_stars
+                        .get(&(day, 1))
+                        .map(|&part1| (day, part2 - part1))
+                })
+                .collect();
+            Standing {
+                rank: 0,
+                id: member.id,
+                name: member.display_name(),
+                stars: member_stars.len(),
+                local_score,
+                reported_local_score: member.local_score,
+                gaps,
+                last_star: member_stars.values().copied().max().unwrap_or(0),
+            }
+        })
+        .collect();
+
+    // Like the site, equal scores are ordered by who got their last star first
+    standings.sort_by(|a, b| {
+        b.local_score
+            .cmp(&a.local_score)
+            .then(a.last_star.cmp(&b.last_star))
+            .then(a.id.cmp(&b.id))
+    });
+    for (i, standing) in standings.iter_mut().enumerate() {
+        standing.rank = i + 1;
+    }
+    Ok(standings)
+}
+
+pub fn format_gap(seconds: i64) -> String {
+    let sign = if seconds < 0 { "-" } else { "" };
+    let seconds = seconds.unsigned_abs();
+    format!(
+        "{}{}:{:02}:{:02}",
+        sign,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+pub fn render_table(event: &str, standings: &[Standing]) -> String {
+    let name_width = standings
+        .iter()
+        .map(|standing| standing.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Member".len());
+
+    let mut output = String::new();
+    writeln!(output, "Private leaderboard {}", event).unwrap();
+    writeln!(
+        output,
+        "{:>4}  {:<width$}  {:>5}  {:>5}  {:>8}",
+        "Rank",
+        "Member",
+        "Stars",
+        "Score",
+        "Reported",
+        width = name_width
+    )
+    .unwrap();
+    for standing in standings {
+        writeln!(
+            output,
+            "{:>4}  {:<width$}  {:>5}  {:>5}  {:>8}",
+            standing.rank,
+            standing.name,
+            standing.stars,
+            standing.local_score,
+            standing.reported_local_score,
+            width = name_width
+        )
+        .unwrap();
+    }
+
+    let mut days: Vec<u8> = standings
+        .iter()
+        .flat_map(|standing| standing.gaps.keys().copied())
+        .collect();
+    days.sort_unstable();
+    days.dedup();
+    if days.is_empty() {
+        return output;
+    }
+
+    writeln!(output).unwrap();
+    writeln!(output, "Part 1 to part 2").unwrap();
+    write!(output, "{:<width$}", "Member", width = name_width).unwrap();
+    for day in &days {
+        write!(output, "  {:>8}", format!("Day {}", day)).unwrap();
+    }
+    writeln!(output).unwrap();
+    for standing in standings {
+        write!(output, "{:<width$}", standing.name, width = name_width).unwrap();
+        for day in &days {
+            let gap = standing
+                .gaps
+                .get(day)
+                .map_or_else(|| String::from("-"), |&gap| format_gap(gap));
+            write!(output, "  {:>8}", gap).unwrap();
+        }
+        writeln!(output).unwrap();
+    }
+    output
+}
+
+pub fn render_json(standings: &[Standing]) -> String {
+    // Serializing plain structs and maps with string or integer keys can't fail
+    serde_json::to_string_pretty(standings).expect("Unable to serialize standings")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn sample() -> Leaderboard {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/leaderboard/sample.json");
+        load(&path).unwrap()
+    }
+
+    // One member with both stars of day 1, 60 seconds apart, the way 2017 exported it
+    const OLD_FORMAT: &str = r#"{"event": "2017", "owner_id": "7", "members": {"7": {
+        "id": "7", "name": "Old", "stars": "2", "local_score": "2", "global_score": "0",
+        "last_star_ts": "160",
+        "completion_day_level": {"1": {"1": {"get_star_ts": "100"}, "2": {"get_star_ts": "160"}}}
+    }}}"#;
+
+    #[test]
+    fn test_sample_ranking() {
+        let standings = standings(&sample()).unwrap();
+        let ranking: Vec<(&str, u64, usize)> = standings
+            .iter()
+            .map(|s| (s.name.as_str(), s.local_score, s.stars))
+            .collect();
+        assert_eq!(
+            ranking,
+            vec![
+                ("Ada", 27, 7),
+                ("Brook", 16, 5),
+                ("(anonymous user #1003)", 4, 2),
+                ("Cass", 0, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sample_scores_match_reported() {
+        assert!(standings(&sample())
+            .unwrap()
+            .iter()
+            .all(|s| s.local_score == s.reported_local_score));
+    }
+
+    #[test]
+    fn test_gaps_only_for_days_with_both_stars() {
+        let standings = standings(&sample()).unwrap();
+        assert_eq!(standings[0].gaps[&3], 49 * 60 + 37);
+        assert!(!standings[1].gaps.contains_key(&3));
+    }
+
+    #[test]
+    fn test_old_format_quotes_every_number() {
+        let leaderboard = parse(OLD_FORMAT).unwrap();
+        assert_eq!(leaderboard.owner_id, 7);
+        let member = &leaderboard.members["7"];
+        assert_eq!((member.id, member.stars, member.local_score), (7, 2, 2));
+        let standings = standings(&leaderboard).unwrap();
+        assert_eq!(standings[0].gaps[&1], 60);
+        assert_eq!(standings[0].local_score, standings[0].reported_local_score);
+    }
+
+    #[test]
+    fn test_quoted_number_must_parse() {
+        let json = OLD_FORMAT.replace(r#""stars": "2""#, r#""stars": "two""#);
+        assert!(matches!(parse(&json), Err(LeaderboardError::Json(_))));
+    }
+
+    #[test]
+    fn test_null_name_is_anonymous() {
+        let json = OLD_FORMAT.replace(r#""name": "Old""#, r#""name": null"#);
+        let standings = standings(&parse(&json).unwrap()).unwrap();
+        assert_eq!(standings[0].name, "(anonymous user #7)");
+    }
+
+    #[test]
+    fn test_missing_counts_default_to_zero() {
+        let json =
+            r#"{"event": "2021", "owner_id": 1, "members": {"1": {"id": 1, "name": "New"}}}"#;
+        let standings = standings(&parse(json).unwrap()).unwrap();
+        assert_eq!(
+            (standings[0].stars, standings[0].reported_local_score),
+            (0, 0)
+        );
+        assert!(standings[0].gaps.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_part() {
+        let bad = OLD_FORMAT.replace(r#""2": {"get_star_ts""#, r#""3": {"get_star_ts""#);
+        assert!(matches!(
+            standings(&parse(&bad).unwrap()),
+            Err(LeaderboardError::InvalidPart { day: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_day() {
+        let bad = OLD_FORMAT.replace(r#"{"1": {"1""#, r#"{"first": {"1""#);
+        assert!(matches!(
+            standings(&parse(&bad).unwrap()),
+            Err(LeaderboardError::InvalidDay { member: 7, .. })
+        ));
+    }
+
+    #[test]
+    fn test_format_gap() {
+        assert_eq!(format_gap(60), "0:01:00");
+        assert_eq!(format_gap(49 * 60 + 37), "0:49:37");
+        assert_eq!(format_gap(-3_661), "-1:01:01");
+    }
+
+    #[test]
+    fn test_table_skips_gaps_without_both_stars() {
+        let json =
+            r#"{"event": "2021", "owner_id": 1, "members": {"1": {"id": 1, "name": "New"}}}"#;
+        let table = render_table("2021", &standings(&parse(json).unwrap()).unwrap());
+        assert!(table.contains("   1  New         0      0         0"));
+        assert!(!table.contains("Part 1 to part 2"));
+    }
+
+    #[test]
+    fn test_json_leaves_out_last_star() {
+        let json = render_json(&standings(&parse(OLD_FORMAT).unwrap()).unwrap());
+        assert!(json.contains("\"reported_local_score\": 2"));
+        assert!(!json.contains("last_star"));
+    }
+}
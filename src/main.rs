@@ -1,155 +1,897 @@
 // This will be the core cli for launching each day. Removing the need for binaries for each part.
-// Need to support args for data file, and day and part to run.
+// Need to support args for data file, and year, day and part to run.
 // part should be optional and if not provided run both parts
+//
+// Days are compiled in through cargo features (see Cargo.toml), so the days on offer come from
+// the registry rather than being listed here.
 
+use std::io::{self, Read};
+use std::path::Path;
+use std::time::Duration;
+
+use lib_aoc2021::{
+    bench, cli, embedded, leaderboard, memory, plugin, pool, records, registry, report, run,
+    status, tui,
+};
 use structopt::StructOpt;
 
-use lib_aoc2021;
+// Only counts once --mem enables it
+#[global_allocator]
+static ALLOCATOR: memory::CountingAllocator = memory::CountingAllocator;
 
-fn execute_exercise(input: &str, day: u8, part: Option<u8>) {
-    match day {
-        1 => match part {
-            Some(1) => {
-                println!("Part 1: {}", lib_aoc2021::day1::part1(input));
-            }
-            Some(2) => {
-                println!("Part 2: {}", lib_aoc2021::day1::part2(input));
-            }
-            None => {
-                println!("Part 1: {}", lib_aoc2021::day1::part1(input));
-                println!("Part 2: {}", lib_aoc2021::day1::part2(input));
-            }
-            Some(_) => {
-                println!("Invalid part");
-            }
-        },
-        2 => match part {
-            Some(1) => {
-                println!("Part 1: {}", lib_aoc2021::day2::part1(input));
-            }
-            Some(2) => {
-                println!("Part 2: {}", lib_aoc2021::day2::part2(input));
-            }
-            None => {
-                println!("Part 1: {}", lib_aoc2021::day2::part1(input));
-                println!("Part 2: {}", lib_aoc2021::day2::part2(input));
-            }
-            Some(_) => {
-                println!("Invalid part");
-            }
-        },
-        3 => match part {
-            Some(1) => {
-                println!("Part 1: {}", lib_aoc2021::day3::part1(input));
-            }
-            Some(2) => {
-                println!("Part 2: {}", lib_aoc2021::day3::part2(input));
-            }
-            None => {
-                println!("Part 1: {}", lib_aoc2021::day3::part1(input));
-                println!("Part 2: {}", lib_aoc2021::day3::part2(input));
-            }
-            Some(_) => {
-                println!("Invalid part");
-            }
-        },
-        4 => match part {
-            Some(1) => {
-                println!("Part 1: {}", lib_aoc2021::day4::part1(input).unwrap());
-            }
-            Some(2) => {
-                println!("Part 2: {}", lib_aoc2021::day4::part2(input).unwrap());
-            }
-            None => {
-                println!("Part 1: {}", lib_aoc2021::day4::part1(input).unwrap());
-                println!("Part 2: {}", lib_aoc2021::day4::part2(input).unwrap());
-            }
-            Some(_) => {
-                println!("Invalid part");
-            }
-        },
-        5 => match part {
-            Some(1) => {
-                println!("Part 1: {}", lib_aoc2021::day5::part1(input).unwrap());
-            }
-            Some(2) => {
-                println!("Part 2: {}", lib_aoc2021::day5::part2(input).unwrap());
-            }
-            None => {
-                println!("Part 1: {}", lib_aoc2021::day5::part1(input).unwrap());
-                println!("Part 2: {}", lib_aoc2021::day5::part2(input).unwrap());
-            }
-            Some(_) => {
-                println!("Invalid part");
-            }
-        },
-        6 => match part {
-            Some(1) => {
-                println!("Part 1: {}", lib_aoc2021::day6::part1(input));
-            }
-            Some(2) => {
-                println!("Part 2: {}", lib_aoc2021::day6::part2(input));
-            }
-            None => {
-                println!("Part 1: {}", lib_aoc2021::day6::part1(input));
-                println!("Part 2: {}", lib_aoc2021::day6::part2(input));
-            }
-            Some(_) => {
-                println!("Invalid part");
-            }
-        },
-        7 => match part {
-            Some(1) => {
-                println!("Part 1: {}", lib_aoc2021::day7::part1(input));
-            }
-            Some(2) => {
-                println!("Part 2: {}", lib_aoc2021::day7::part2(input));
-            }
-            None => {
-                println!("Part 1: {}", lib_aoc2021::day7::part1(input));
-                println!("Part 2: {}", lib_aoc2021::day7::part2(input));
-            }
-            Some(_) => {
-                println!("Invalid part");
-            }
-        },
-        8 => match part {
-            Some(1) => {
-                println!("Part 1: {}", lib_aoc2021::day8::part1(input));
-            }
-            Some(2) => {
-                println!("Part 2: {}", lib_aoc2021::day8::part2(input));
-            }
-            None => {
-                println!("Part 1: {}", lib_aoc2021::day8::part1(input));
-                println!("Part 2: {}", lib_aoc2021::day8::part2(input));
-            }
-            Some(_) => {
-                println!("Invalid part");
-            }
+// Runs the requested parts, checking them against `answers` when given
+fn execute_exercise(
+    input: &str,
+    solution: registry::Solution,
+    part: Option<u8>,
+    answers: Option<&records::Records>,
+) -> Vec<run::PartRun> {
+    let parts = match part {
+        Some(part) if solution.part(part).is_some() => vec![part],
+        Some(_) => {
+            println!("Invalid part");
+            return Vec::new();
+        }
+        // Plugin days can leave a part out
+        None => [1, 2]
+            .into_iter()
+            .filter(|&part| solution.part(part).is_some())
+            .collect(),
+    };
+    let mut runs = Vec::new();
+    for part in parts {
+        let expected = answers.and_then(|answers| answers.get(&(solution.day, part)));
+        let run = run::run_part(solution, part, input, expected);
+        match (&run.answer, &run.verdict) {
+            (Some(answer), _) => {
+                println!("Part {}: {}{}", part, answer, memory_summary(run.memory))
+            }
+            (None, run::Verdict::Error(e)) => println!("Part {}: Error: {}", part, e),
+            (None, _) => {}
+        }
+        runs.push(run);
+    }
+    runs
+}
+
+const DATA: &str = "data";
+
+fn memory_summary(stats: Option<memory::MemStats>) -> String {
+    match stats {
+        Some(stats) => format!(
+            " ({} allocations, {} allocated, {} peak)",
+            stats.allocations,
+            memory::format_bytes(stats.bytes),
+            memory::format_bytes(stats.peak)
+        ),
+        None => String::new(),
+    }
+}
+
+// Records the parts whose answers matched and forgets the rest, the runtimes `status` shows are
+// only for verified runs
+fn record_runtimes(year: u16, day: u8, runs: impl IntoIterator<Item = (u8, Duration, bool)>) {
+    // Embedded runs leave the filesystem alone
+    if embedded::enabled() {
+        return;
+    }
+    let path = records::runtimes_path(Path::new(DATA), year);
+    for (part, runtime, verified) in runs {
+        if let Err(e) = records::record_run(&path, day, part, runtime, verified) {
+            eprintln!("Unable to record runtime in {}: {}", path.display(), e);
+        }
+    }
+}
+
+// Plugin days are left out, the runtimes shown by `status` are for the built in days
+fn record_year(run: &run::YearRun) {
+    for day in run.days.iter().filter(|day| day.plugin.is_none()) {
+        let parts = day
+            .parts
+            .iter()
+            .map(|part| (part.part, part.runtime, part.verdict == run::Verdict::Pass));
+        record_runtimes(run.year, day.day, parts);
+    }
+}
+
+// The fastest of the bench's iterations
+fn record_bench(bench: &bench::Bench) {
+    for row in bench.rows.iter().filter(|row| row.plugin.is_none()) {
+        record_runtimes(bench.year, row.day, [(row.part, row.min, row.verified)]);
+    }
+}
+
+fn print_days(days: &[run::DayRun], label: impl Fn(&run::DayRun) -> String) {
+    for day in days {
+        println!("{}", label(day));
+        for warning in &day.warnings {
+            println!("  Warning: {}", warning);
+        }
+        for part in &day.parts {
+            let result = match &part.verdict {
+                run::Verdict::Pass => String::from("pass"),
+                run::Verdict::Fail(expected) => format!("FAIL, expected {}", expected),
+                run::Verdict::Unverified => String::from("unverified"),
+                run::Verdict::Error(e) => format!("Error: {}", e),
+            };
+            println!(
+                "  Part {}: {} ({}, {}){}",
+                part.part,
+                part.answer.as_deref().unwrap_or("-"),
+                records::format_runtime(part.runtime),
+                result,
+                memory_summary(part.memory)
+            );
+        }
+    }
+}
+
+fn print_run(run: &run::YearRun) {
+    for warning in &run.warnings {
+        println!("Warning: {}", warning);
+    }
+    print_days(&run.days, run::DayRun::label);
+}
+
+// The day 1 options that show something other than the answers, most specific first
+#[cfg(feature = "day1")]
+fn show_sonar(args: &Cli, input: &str) {
+    if let Some(method) = &args.anomalies {
+        find_anomalies(input, method, args.window, args.tolerance);
+    } else if let Some(plot) = &args.plot {
+        plot_sonar(input, plot, args.window.unwrap_or(3), args.tolerance);
+    } else if args.explain {
+        explain_sonar(input, args.tolerance);
+    } else if args.profile {
+        profile_sonar(input, args.tolerance);
+    } else {
+        analyse_sonar(
+            input,
+            args.window.unwrap_or(1),
+            args.aggregate.as_deref(),
+            args.tolerance,
+        );
+    }
+}
+
+// Day 1 with --window or --aggregate, comparing each window with the next. Day 1 readings are
+// read as i64, or as f64 once --tolerance is given.
+#[cfg(feature = "day1")]
+fn analyse_sonar(input: &str, window: usize, aggregate: Option<&str>, tolerance: Option<f64>) {
+    use lib_aoc2021::day1;
+
+    let aggregate = match aggregate.map(str::parse).transpose() {
+        Ok(aggregate) => aggregate.unwrap_or(day1::analysis::Aggregate::Sum),
+        Err(e) => {
+            println!("Error: {}", e);
+            return;
+        }
+    };
+    let mut sonar = day1::analysis::Sonar::new(window, aggregate, 1);
+    sonar.tolerance = tolerance.unwrap_or(0.0);
+    let changes = match tolerance {
+        Some(_) => sonar.analyse_input::<f64>(input),
+        None => sonar.analyse_input::<i64>(input),
+    };
+    match changes {
+        Ok(changes) => println!(
+            "Window of {} ({}): {} increased, {} decreased, {} unchanged",
+            sonar.window, sonar.aggregate, changes.increased, changes.decreased, changes.unchanged
+        ),
+        Err(e) => println!("Error: {}", e),
+    }
+}
+
+#[cfg(feature = "day1")]
+fn profile_sonar(input: &str, tolerance: Option<f64>) {
+    use lib_aoc2021::day1::profile::{render_report, report};
+
+    let rendered = match tolerance {
+        Some(tolerance) => report::<f64>(input, tolerance).map(|report| render_report(&report)),
+        None => report::<i64>(input, 0.0).map(|report| render_report(&report)),
+    };
+    match rendered {
+        Ok(rendered) => print!("{}", rendered),
+        Err(e) => println!("Error: {}", e),
+    }
+}
+
+#[cfg(feature = "day1")]
+fn explain_sonar(input: &str, tolerance: Option<f64>) {
+    use lib_aoc2021::day1::explain::explain;
+
+    let listing = match tolerance {
+        Some(tolerance) => explain::<f64>(input, tolerance),
+        None => explain::<i64>(input, 0.0),
+    };
+    match listing {
+        Ok(listing) => print!("{}", listing),
+        Err(e) => println!("Error: {}", e),
+    }
+}
+
+// A terminal chart, or an SVG written to the path given
+#[cfg(feature = "day1")]
+fn plot_sonar(input: &str, plot: &str, window: usize, tolerance: Option<f64>) {
+    use lib_aoc2021::day1::{parse_input, plot};
+    use std::io::IsTerminal;
+
+    let glyphs = match plot {
+        "braille" => Some(plot::Glyphs::Braille),
+        "ascii" => Some(plot::Glyphs::Ascii),
+        _ if plot.ends_with(".svg") => None,
+        _ => {
+            println!(
+                "Unknown plot {}, expected braille, ascii or an .svg file",
+                plot
+            );
+            return;
+        }
+    };
+    let chart = plot::TextChart {
+        width: 72,
+        height: 12,
+        glyphs: glyphs.unwrap_or(plot::Glyphs::Braille),
+        colour: io::stdout().is_terminal(),
+    };
+    let rendered = match tolerance {
+        Some(tolerance) => parse_input::<f64>(input).map(|depths| match glyphs {
+            Some(_) => chart.render(&depths, window, tolerance),
+            None => plot::render_svg(&depths, window, tolerance),
+        }),
+        None => parse_input::<i64>(input).map(|depths| match glyphs {
+            Some(_) => chart.render(&depths, window, 0.0),
+            None => plot::render_svg(&depths, window, 0.0),
+        }),
+    };
+    match (rendered, glyphs) {
+        (Ok(chart), Some(_)) => print!("{}", chart),
+        (Ok(svg), None) => match std::fs::write(plot, svg) {
+            Ok(()) => println!("Wrote {}", plot),
+            Err(e) => println!("Unable to write {}: {}", plot, e),
         },
-        _ => println!("Day not yet implemented or invalid"),
+        (Err(e), _) => println!("Error: {}", e),
+    }
+}
+
+// The rolling methods judge each reading against --window readings either side of it
+#[cfg(feature = "day1")]
+fn find_anomalies(input: &str, method: &str, window: Option<usize>, tolerance: Option<f64>) {
+    use lib_aoc2021::day1::anomaly;
+
+    let mut detector = match method.parse() {
+        Ok(method) => anomaly::Detector::new(method),
+        Err(e) => {
+            println!("Error: {}", e);
+            return;
+        }
+    };
+    detector.neighbours = window.unwrap_or(anomaly::NEIGHBOURS);
+    detector.tolerance = tolerance.unwrap_or(0.0);
+    let rendered = match tolerance {
+        Some(_) => detector
+            .find::<f64>(input)
+            .map(|(depths, anomalies)| detector.render(&depths, &anomalies)),
+        None => detector
+            .find::<i64>(input)
+            .map(|(depths, anomalies)| detector.render(&depths, &anomalies)),
+    };
+    match rendered {
+        Ok(rendered) => print!("{}", rendered),
+        Err(e) => println!("Error: {}", e),
+    }
+}
+
+// Day 1 from stdin, printing the running totals as each reading arrives
+#[cfg(feature = "day1")]
+fn stream_sonar(window: usize, aggregate: Option<&str>, tolerance: Option<f64>) {
+    if aggregate.is_some_and(|aggregate| aggregate != "sum") {
+        println!("Only summed windows can be counted from stdin");
+        return;
+    }
+    match tolerance {
+        Some(tolerance) => stream_depths::<f64>(window, tolerance),
+        None => stream_depths::<i64>(window, 0.0),
+    }
+}
+
+#[cfg(feature = "day1")]
+fn stream_depths<T: lib_aoc2021::day1::Depth>(window: usize, tolerance: f64) {
+    use lib_aoc2021::day1;
+    use std::io::BufRead;
+
+    let mut error = None;
+    let depths = io::stdin()
+        .lock()
+        .lines()
+        .enumerate()
+        .map_while(|(i, line)| match line {
+            Ok(line) => day1::parse_depth::<T>(i + 1, &line)
+                .map_err(|e| error = Some(e.to_string()))
+                .ok(),
+            Err(e) => {
+                error = Some(format!("Unable to read stdin: {}", e));
+                None
+            }
+        })
+        .flatten();
+    let counter = day1::stream::IncreaseCounter::new(depths, window, tolerance);
+    let window = window.max(1);
+    for totals in counter {
+        println!(
+            "Reading {}: {} increases, {} in windows of {}",
+            totals.readings, totals.increases, totals.window_increases, window
+        );
+    }
+    if let Some(e) = error {
+        println!("Error: {}", e);
+    }
+}
+
+// Part 1 moves the simple sub and anything else the aimed one. Includes resolve against the
+// input file's directory when there is one
+#[cfg(feature = "day2")]
+fn record_trajectory(input: &str, path: Option<&Path>, out: &str, part: Option<u8>) {
+    use lib_aoc2021::day2::{self, script, trajectory, Submarine};
+
+    let export: Option<fn(&trajectory::Trajectory) -> String> = match out {
+        "summary" => None,
+        _ if out.ends_with(".csv") => Some(trajectory::Trajectory::to_csv),
+        _ if out.ends_with(".svg") => Some(trajectory::render_svg),
+        _ => {
+            println!(
+                "Unknown trajectory output {}, expected summary or a .csv or .svg file",
+                out
+            );
+            return;
+        }
+    };
+    let commands = match path {
+        Some(path) => script::parse_file(input, path),
+        None => script::parse(input),
+    };
+    let commands = match commands {
+        Ok(commands) => commands,
+        Err(e) => {
+            println!("Error: {}", e);
+            return;
+        }
+    };
+    let recorded = match part {
+        Some(1) => {
+            trajectory::Trajectory::record(&mut Submarine::new(day2::Simple::default()), &commands)
+        }
+        _ => trajectory::Trajectory::record(&mut Submarine::new(day2::Aimed::default()), &commands),
+    };
+    let recorded = match recorded {
+        Ok(recorded) => recorded,
+        Err(e) => {
+            println!("Error: {}", e);
+            return;
+        }
+    };
+    print!("{}", recorded.summary().render());
+    if let Some(export) = export {
+        match std::fs::write(out, export(&recorded)) {
+            Ok(()) => println!("Wrote {}", out),
+            Err(e) => println!("Unable to write {}: {}", out, e),
+        }
     }
 }
 
+fn list<T: ToString>(items: &[T]) -> String {
+    if items.is_empty() {
+        String::from("none")
+    } else {
+        items
+            .iter()
+            .map(|item| item.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+// Option names and help come from lib_aoc2021::cli, which also renders the completions and man page
 #[derive(Debug, StructOpt)]
+#[structopt(about = cli::ABOUT)]
 struct Cli {
-    #[structopt(short = "d", long = "day", default_value = "1")]
+    #[structopt(
+        short = cli::YEAR.short,
+        long = cli::YEAR.long,
+        value_name = cli::YEAR.value,
+        help = cli::YEAR.help,
+        default_value = "2021"
+    )]
+    year: u16,
+
+    #[structopt(
+        short = cli::DAY.short,
+        long = cli::DAY.long,
+        value_name = cli::DAY.value,
+        help = cli::DAY.help,
+        default_value = "1"
+    )]
     day: u8,
 
-    #[structopt(short = "p", long = "part")]
+    #[structopt(
+        short = cli::PART.short,
+        long = cli::PART.long,
+        value_name = cli::PART.value,
+        help = cli::PART.help
+    )]
     part: Option<u8>,
 
-    #[structopt(short = "f", long = "file", default_value = "data/day1/input.txt")]
-    file: std::path::PathBuf,
+    #[structopt(
+        short = cli::FILE.short,
+        long = cli::FILE.long,
+        value_name = cli::FILE.value,
+        help = cli::FILE.help
+    )]
+    file: Option<std::path::PathBuf>,
+
+    #[structopt(short = cli::ALL.short, long = cli::ALL.long, help = cli::ALL.help)]
+    all: bool,
+
+    #[structopt(short = cli::MEM.short, long = cli::MEM.long, help = cli::MEM.help)]
+    mem: bool,
+
+    #[structopt(short = cli::RECORD.short, long = cli::RECORD.long, help = cli::RECORD.help)]
+    record: bool,
+
+    #[structopt(
+        short = cli::JOBS.short,
+        long = cli::JOBS.long,
+        value_name = cli::JOBS.value,
+        help = cli::JOBS.help,
+        default_value = "1"
+    )]
+    jobs: usize,
+
+    #[structopt(
+        short = cli::PLUGINS.short,
+        long = cli::PLUGINS.long,
+        value_name = cli::PLUGINS.value,
+        help = cli::PLUGINS.help
+    )]
+    plugins: Option<std::path::PathBuf>,
+
+    #[structopt(short = cli::EMBEDDED.short, long = cli::EMBEDDED.long, help = cli::EMBEDDED.help)]
+    embedded: bool,
+
+    #[structopt(
+        short = cli::WINDOW.short,
+        long = cli::WINDOW.long,
+        value_name = cli::WINDOW.value,
+        help = cli::WINDOW.help
+    )]
+    window: Option<usize>,
+
+    #[structopt(
+        short = cli::AGGREGATE.short,
+        long = cli::AGGREGATE.long,
+        value_name = cli::AGGREGATE.value,
+        help = cli::AGGREGATE.help,
+        possible_values = &cli::AGGREGATES
+    )]
+    aggregate: Option<String>,
+
+    #[structopt(short = cli::PROFILE.short, long = cli::PROFILE.long, help = cli::PROFILE.help)]
+    profile: bool,
+
+    #[structopt(short = cli::EXPLAIN.short, long = cli::EXPLAIN.long, help = cli::EXPLAIN.help)]
+    explain: bool,
+
+    #[structopt(
+        short = cli::TOLERANCE.short,
+        long = cli::TOLERANCE.long,
+        value_name = cli::TOLERANCE.value,
+        help = cli::TOLERANCE.help
+    )]
+    tolerance: Option<f64>,
+
+    #[structopt(
+        short = cli::PLOT.short,
+        long = cli::PLOT.long,
+        value_name = cli::PLOT.value,
+        help = cli::PLOT.help
+    )]
+    plot: Option<String>,
+
+    #[structopt(
+        short = cli::ANOMALIES.short,
+        long = cli::ANOMALIES.long,
+        value_name = cli::ANOMALIES.value,
+        help = cli::ANOMALIES.help
+    )]
+    anomalies: Option<String>,
+
+    #[structopt(
+        short = cli::TRAJECTORY.short,
+        long = cli::TRAJECTORY.long,
+        value_name = cli::TRAJECTORY.value,
+        help = cli::TRAJECTORY.help
+    )]
+    trajectory: Option<String>,
+
+    #[structopt(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, StructOpt)]
+enum Command {
+    #[structopt(name = "completions", about = cli::COMPLETIONS.about)]
+    Completions {
+        #[structopt(value_name = "SHELL", possible_values = &cli::SHELLS)]
+        shell: cli::Shell,
+    },
+    #[structopt(name = "man", about = cli::MAN.about)]
+    Man,
+    #[structopt(name = "status", about = cli::STATUS.about)]
+    Status,
+    #[structopt(name = "leaderboard", about = cli::LEADERBOARD.about)]
+    Leaderboard {
+        #[structopt(value_name = "FILE")]
+        file: std::path::PathBuf,
+
+        #[structopt(
+            short = cli::FORMAT.short,
+            long = cli::FORMAT.long,
+            value_name = cli::FORMAT.value,
+            help = cli::FORMAT.help,
+            possible_values = &cli::FORMATS,
+            default_value = "table"
+        )]
+        format: cli::Format,
+    },
+    #[structopt(name = "report", about = cli::REPORT.about)]
+    Report {
+        #[structopt(
+            short = cli::OUT.short,
+            long = cli::OUT.long,
+            value_name = cli::OUT.value,
+            help = cli::OUT.help
+        )]
+        out: std::path::PathBuf,
+    },
+    #[structopt(name = "tui", about = cli::TUI.about)]
+    Tui,
+    #[structopt(name = "bench", about = cli::BENCH.about)]
+    Bench {
+        #[structopt(
+            short = cli::ITERATIONS.short,
+            long = cli::ITERATIONS.long,
+            value_name = cli::ITERATIONS.value,
+            help = cli::ITERATIONS.help,
+            default_value = "10"
+        )]
+        iterations: u32,
+    },
 }
 
 fn main() {
     let args = Cli::from_args();
+    // The memory counters only see the thread they're measured on
+    if args.mem {
+        memory::enable();
+        if args.jobs > 1 {
+            eprintln!("Warning: --mem runs on one thread, ignoring --jobs");
+        }
+    }
+    pool::set_jobs(if args.mem { 1 } else { args.jobs });
+    if args.embedded {
+        if !embedded::available() {
+            println!("Built without embedded inputs, rebuild with --features embedded");
+            return;
+        }
+        embedded::enable();
+    }
+    // Loading a library runs its code, so only ever from a directory the user names
+    if let Some(directory) = &args.plugins {
+        for (_, result) in plugin::load_dir(directory) {
+            if let Err(e) = result {
+                eprintln!("Warning: {}", e);
+            }
+        }
+    }
+    match args.command {
+        Some(Command::Completions { shell }) => {
+            print!("{}", cli::completions(shell));
+            return;
+        }
+        Some(Command::Man) => {
+            print!("{}", cli::man_page());
+            return;
+        }
+        Some(Command::Leaderboard { file, format }) => {
+            let standings = leaderboard::load(&file)
+                .and_then(|board| Ok((leaderboard::standings(&board)?, board.event)));
+            match (standings, format) {
+                (Ok((standings, event)), cli::Format::Table) => {
+                    print!("{}", leaderboard::render_table(&event, &standings))
+                }
+                (Ok((standings, _)), cli::Format::Json) => {
+                    println!("{}", leaderboard::render_json(&standings))
+                }
+                (Err(e), _) => println!("Error: {}", e),
+            }
+            return;
+        }
+        Some(Command::Report { out }) => {
+            let run = run::run_year(Path::new(DATA), args.year);
+            if args.record {
+                record_year(&run);
+            }
+            match report::write(&out, &run) {
+                Ok(()) => println!("Wrote {}", out.display()),
+                Err(e) => println!("Error: {}", e),
+            }
+            return;
+        }
+        Some(Command::Bench { iterations }) => {
+            let bench = bench::bench_year(Path::new(DATA), args.year, iterations);
+            print!("{}", bench::render(&bench));
+            if args.record {
+                record_bench(&bench);
+            }
+            return;
+        }
+        Some(Command::Tui) => {
+            if let Err(e) = tui::run(Path::new(DATA), args.year) {
+                println!("Error: {}", e);
+            }
+            return;
+        }
+        Some(Command::Status) => {
+            match status::collect(Path::new(DATA), args.year) {
+                Ok(days) => print!("{}", status::render(args.year, &days)),
+                Err(e) => println!("Error: {}", e),
+            }
+            return;
+        }
+        None => {}
+    }
+
+    if args.all {
+        let run = run::run_year(Path::new(DATA), args.year);
+        if args.record {
+            record_year(&run);
+        }
+        print_run(&run);
+        return;
+    }
+
+    let year = args.year;
     let day = args.day;
     let part = args.part;
-    let file = args.file;
+    let sonar = args.window.is_some() || args.aggregate.is_some() || args.tolerance.is_some();
+    let views = args.profile || args.explain || args.plot.is_some() || args.anomalies.is_some();
+    if (sonar || views) && (year, day) != (2021, 1) {
+        println!("The day 1 options only apply to day 1 of 2021");
+        return;
+    }
+    if args.trajectory.is_some() && (year, day) != (2021, 2) {
+        println!("--trajectory only applies to day 2 of 2021");
+        return;
+    }
+
+    // The built in day first, then any plugins for it
+    let solutions = registry::find_all(year, day);
+    match solutions.first() {
+        Some(_) => {}
+        None if registry::days(year).is_empty() => {
+            println!(
+                "No days compiled in for {}. Available years: {}",
+                year,
+                list(&registry::years())
+            );
+            return;
+        }
+        None => {
+            println!(
+                "Day {} of {} not yet implemented or not compiled in. Available days: {}",
+                day,
+                year,
+                list(&registry::days(year))
+            );
+            return;
+        }
+    }
+
+    let default_file = Path::new(DATA)
+        .join(year.to_string())
+        .join(format!("day{}", day))
+        .join("input.txt");
+    let file = args.file.clone().unwrap_or_else(|| default_file.clone());
+    let stdin = file == Path::new("-");
+    // The day 1 and 2 options are only reachable with those days compiled in, without them
+    // registry::find_all has already turned the day away
+    #[cfg(feature = "day1")]
+    if stdin && (year, day) == (2021, 1) && !views {
+        // Both parts' totals come out as the readings arrive, and nothing is measured
+        if part.is_some() || args.mem {
+            println!("--part and --mem don't apply to day 1 read from stdin");
+            return;
+        }
+        stream_sonar(
+            args.window.unwrap_or(3),
+            args.aggregate.as_deref(),
+            args.tolerance,
+        );
+        return;
+    }
+    if file.is_dir() {
+        let parts = match part {
+            Some(part) => vec![part],
+            None => vec![1, 2],
+        };
+        for &solution in &solutions {
+            match run::run_batch(solution, &parts, &file) {
+                Ok(days) => print_days(&days, |day| match day.plugin {
+                    Some(plugin) => format!("{} ({} plugin)", day.input.display(), plugin),
+                    None => day.input.display().to_string(),
+                }),
+                Err(e) => {
+                    println!("Unable to read {}: {}", file.display(), e);
+                    return;
+                }
+            }
+        }
+        return;
+    }
+    let input = if stdin {
+        let mut input = String::new();
+        if let Err(e) = io::stdin().read_to_string(&mut input) {
+            println!("Unable to read stdin: {}", e);
+            return;
+        }
+        input
+    } else if args.embedded {
+        match embedded::read(&file) {
+            Ok(input) => input,
+            Err(e) => {
+                println!("Day {} of {}: {}", day, year, e);
+                return;
+            }
+        }
+    } else {
+        lib_aoc2021::utils::read_file(file.clone())
+    };
+    #[cfg(feature = "day2")]
+    if let Some(out) = &args.trajectory {
+        let path = (!stdin && !args.embedded).then_some(file.as_path());
+        record_trajectory(&input, path, out, part);
+        return;
+    }
+    #[cfg(feature = "day1")]
+    if sonar || views {
+        show_sonar(&args, &input);
+        return;
+    }
+    // The parts only take plain commands, so a day 2 plan read from a file is expanded here, where
+    // its includes resolve the same way as for --trajectory
+    #[cfg(feature = "day2")]
+    let input = match (year, day) {
+        (2021, 2) if !stdin && !args.embedded => {
+            match lib_aoc2021::day2::script::expand_file(&input, &file) {
+                Ok(expanded) => expanded,
+                Err(e) => {
+                    println!("Error: {}", e);
+                    return;
+                }
+            }
+        }
+        _ => input,
+    };
+    // Only built in runs against the real input count towards the runtimes shown by `status`
+    let record = args.record && file == default_file;
+    let answers_path = records::answers_path(Path::new(DATA), year);
+    let answers = match record.then(|| records::load(&answers_path)) {
+        Some(Err(e)) => {
+            println!("Unable to load {}: {}", answers_path.display(), e);
+            return;
+        }
+        answers => answers.map(Result::unwrap),
+    };
+    for solution in solutions {
+        if let Some(plugin) = solution.plugin() {
+            if part.is_some_and(|part| solution.part(part).is_none()) {
+                continue;
+            }
+            println!("Day {} ({} plugin)", day, plugin);
+        }
+        let runs = execute_exercise(&input, solution, part, answers.as_ref());
+        if record && solution.plugin().is_none() {
+            let parts = runs
+                .iter()
+                .map(|run| (run.part, run.runtime, run.verdict == run::Verdict::Pass));
+            record_runtimes(year, day, parts);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A value each kind of option accepts
+    fn sample(values: cli::Values) -> &'static str {
+        match values {
+            cli::Values::None => "",
+            cli::Values::Years => "2021",
+            cli::Values::Days | cli::Values::Parts | cli::Values::Numbers => "1",
+            cli::Values::DataFiles | cli::Values::Files => "x",
+            cli::Values::Shells => cli::SHELLS[0],
+            cli::Values::Formats => cli::FORMATS[0],
+            cli::Values::Aggregates => cli::AGGREGATES[0],
+            cli::Values::Methods => cli::METHODS[0],
+        }
+    }
+
+    fn parses(args: &[&str]) -> bool {
+        Cli::clap().get_matches_from_safe(args).is_ok()
+    }
 
-    let input = lib_aoc2021::utils::read_file(file);
-    execute_exercise(&input, day, part);
+    fn words(text: &str) -> String {
+        text.split_whitespace().collect::<Vec<&str>>().join(" ")
+    }
+
+    // The long help, which clap hands back as an error
+    fn help(command: &[&str]) -> String {
+        let mut args = command.to_vec();
+        args.push("--help");
+        let error = Cli::clap().get_matches_from_safe(args).unwrap_err();
+        assert_eq!(error.kind, structopt::clap::ErrorKind::HelpDisplayed);
+        words(&error.message)
+    }
+
+    // Checks StructOpt accepts each of `options` by both names, taking a value only where cli.rs
+    // says it takes one, and that the help shows cli.rs's help text and value name
+    fn check_options(command: &[&str], options: &[cli::Opt]) {
+        let help = help(command);
+        for opt in options {
+            for name in [format!("-{}", opt.short), format!("--{}", opt.long)] {
+                let mut args = command.to_vec();
+                args.push(&name);
+                let value = sample(opt.values);
+                if opt.takes_value() {
+                    args.push(value);
+                    assert!(parses(&args), "{} {} doesn't parse", name, value);
+                } else {
+                    assert!(parses(&args), "{} doesn't parse", name);
+                    args.push("x");
+                    assert!(!parses(&args), "{} took a value", name);
+                }
+            }
+            assert!(help.contains(&words(opt.help)), "--{} help", opt.long);
+            if opt.takes_value() {
+                assert!(help.contains(&format!("<{}>", opt.value)), "--{}", opt.long);
+            }
+        }
+    }
+
+    #[test]
+    fn test_options_match_cli() {
+        check_options(&[cli::BIN], &cli::OPTIONS);
+    }
+
+    #[test]
+    fn test_commands_match_cli() {
+        let top = help(&[cli::BIN]);
+        for command in cli::COMMANDS {
+            assert!(
+                top.contains(&words(command.about)),
+                "{} about",
+                command.name
+            );
+            let mut args = vec![cli::BIN, command.name];
+            if let Some((_, values)) = command.arg {
+                args.push(sample(values));
+            }
+            // Report needs its --out, which check_options then supplies
+            if command.options.is_empty() {
+                assert!(parses(&args), "{} doesn't parse", command.name);
+            }
+            check_options(&args, command.options);
+        }
+    }
 }
@@ -0,0 +1,231 @@
+// Counting global allocator for memory profiling. The binary always installs it, but it only
+// counts inside `measure` once `enable` has been called (the CLI's --mem flag) - otherwise every
+// call goes straight through to the system allocator.
+//
+// Counters are per thread so that a solver measured on one thread isn't charged for allocations
+// made by another. They're plain `Cell`s in a const thread local, which never allocate themselves.
+// That means work a solver hands to pool threads goes uncounted, so the CLI runs everything on
+// one thread with --mem.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MemStats {
+    pub allocations: u64,
+    pub bytes: u64,
+    // Most bytes live at once, counting from the start of the measurement
+    pub peak: u64,
+}
+
+struct Counters {
+    // Set while this thread is inside a measurement
+    counting: Cell<bool>,
+    allocations: Cell<u64>,
+    bytes: Cell<u64>,
+    // Signed as memory allocated before the measurement may be freed during it
+    live: Cell<i64>,
+    peak: Cell<i64>,
+}
+
+thread_local! {
+    static COUNTERS: Counters = const {
+        Counters {
+            counting: Cell::new(false),
+            allocations: Cell::new(0),
+            bytes: Cell::new(0),
+            live: Cell::new(0),
+            peak: Cell::new(0),
+        }
+    };
+}
+
+fn record_alloc(size: usize) {
+    // try_with as the allocator is still called while a thread's locals are torn down
+    let _ = COUNTERS.try_with(|counters| {
+        if !counters.counting.get() {
+            return;
+        }
+        counters.allocations.set(counters.allocations.get() + 1);
+        counters.bytes.set(counters.bytes.get() + size as u64);
+        let live = counters.live.get() + size as i64;
+        counters.live.set(live);
+        counters.peak.set(counters.peak.get().max(live));
+    });
+}
+
+fn record_dealloc(size: usize) {
+    let _ = COUNTERS.try_with(|counters| {
+        if counters.counting.get() {
+            counters.live.set(counters.live.get() - size as i64);
+        }
+    });
+}
+
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_dealloc(layout.size());
+    }
+
+    // A reallocation counts as freeing the old block and allocating the new one
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+// Runs `f` and returns what it allocated on this thread, None unless counting is enabled
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<MemStats>) {
+    if !enabled() {
+        return (f(), None);
+    }
+    let (value, stats) = count(f);
+    (value, Some(stats))
+}
+
+// Counts what `f` allocates on this thread whether or not counting is enabled, leaving the
+// process-wide switch alone
+fn count<T>(f: impl FnOnce() -> T) -> (T, MemStats) {
+    COUNTERS.with(|counters| {
+        counters.allocations.set(0);
+        counters.bytes.set(0);
+        counters.live.set(0);
+        counters.peak.set(0);
+        counters.counting.set(true);
+    });
+    let value = f();
+    let stats = COUNTERS.with(|counters| {
+        counters.counting.set(false);
+        MemStats {
+            allocations: counters.allocations.get(),
+            bytes: counters.bytes.get(),
+            peak: counters.peak.get().max(0) as u64,
+        }
+    });
+    (value, stats)
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_allocations() {
+        let (total, stats) = count(|| {
+            let first: Vec<u64> = Vec::with_capacity(100);
+            drop(first);
+            let second: Vec<u64> = Vec::with_capacity(50);
+            second.capacity()
+        });
+        assert_eq!(total, 50);
+        assert_eq!((stats.allocations, stats.bytes), (2, 150 * 8));
+    }
+
+    #[test]
+    fn test_peak_is_most_live_at_once() {
+        let (_, stats) = count(|| {
+            let first: Vec<u64> = Vec::with_capacity(100);
+            drop(first);
+            Vec::<u64>::with_capacity(50)
+        });
+        // The first vector was freed before the second was allocated
+        assert_eq!(stats.peak, 100 * 8);
+    }
+
+    #[test]
+    fn test_freeing_earlier_memory_isnt_negative() {
+        let earlier: Vec<u64> = Vec::with_capacity(100);
+        let (_, stats) = count(|| drop(earlier));
+        assert_eq!(stats, MemStats::default());
+    }
+
+    #[test]
+    fn test_realloc_frees_then_allocates() {
+        let (_, stats) = count(|| {
+            let mut grown: Vec<u8> = Vec::with_capacity(16);
+            grown.reserve_exact(64);
+            grown
+        });
+        assert_eq!(
+            (stats.allocations, stats.bytes, stats.peak),
+            (2, 16 + 64, 64)
+        );
+    }
+
+    #[test]
+    fn test_other_threads_arent_counted() {
+        let (_, stats) = count(|| {
+            std::thread::scope(|scope| {
+                scope.spawn(|| Vec::<u64>::with_capacity(1_000));
+            })
+        });
+        // Starting the thread allocates a little on this one, but not the vector
+        assert!(stats.bytes < 1_000 * 8);
+    }
+
+    #[test]
+    fn test_measure_is_none_until_enabled() {
+        let (value, stats) = measure(|| Vec::<u64>::with_capacity(10).capacity());
+        assert_eq!((value, stats), (10, None));
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
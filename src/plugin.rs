@@ -0,0 +1,335 @@
+// Solutions loaded at runtime from shared libraries (cdylib) in a plugins directory, so
+// experimental days can live outside this crate. Loading a library runs its code, so the CLI only
+// loads them from a directory named with --plugins. A plugin day is listed after the built in day
+// it shares a (year, day) with, so both run (see registry::solutions). Each library exports a
+// C-ABI registration
+// function, `aoc_register`, returning a static description of its solvers:
+//
+//     struct AocSolver { uint16_t year; uint8_t day; uint8_t part;
+//                        int32_t (*solve)(const uint8_t *input, size_t len, char **output); };
+//     struct AocPlugin { uint32_t abi_version; const AocSolver *solvers; size_t count;
+//                        void (*free_string)(char *); };
+//     const struct AocPlugin *aoc_register(void);
+//
+// `solve` stores a NUL terminated string in `output` - the answer when it returns 0, otherwise the
+// error - which is handed back to the plugin's `free_string` once copied. Plugins must catch their
+// own panics, nothing may unwind across the boundary. plugins/example is a plugin built this way.
+//
+// Libraries are never unloaded, so their solvers stay valid for the rest of the process and can be
+// used wherever a built in solver can.
+
+use std::collections::BTreeMap;
+use std::ffi::{c_char, CStr};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::ptr;
+use std::sync::Mutex;
+
+use libloading::Library;
+use thiserror::Error;
+
+use crate::registry::{Solution, Solver};
+
+pub const ABI_VERSION: u32 = 1;
+pub const REGISTER: &str = "aoc_register";
+
+type SolveExtern = unsafe extern "C" fn(*const u8, usize, *mut *mut c_char) -> i32;
+type FreeExtern = unsafe extern "C" fn(*mut c_char);
+
+#[repr(C)]
+pub struct AocSolver {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub solve: SolveExtern,
+}
+
+#[repr(C)]
+pub struct AocPlugin {
+    pub abi_version: u32,
+    pub solvers: *const AocSolver,
+    pub count: usize,
+    pub free_string: FreeExtern,
+}
+
+#[derive(Debug, Error)]
+pub enum PluginError {
+    #[error("Unable to read {path}: {source}")]
+    ReadDir { path: String, source: io::Error },
+    #[error("Unable to load {path}: {source}")]
+    Load {
+        path: String,
+        source: libloading::Error,
+    },
+    #[error("{0} returned no registration")]
+    NoRegistration(String),
+    #[error("{path} uses plugin ABI version {found}, expected {ABI_VERSION}")]
+    Abi { path: String, found: u32 },
+    #[error("{path} registers part {part} of day {day} of {year}")]
+    InvalidPart {
+        path: String,
+        year: u16,
+        day: u8,
+        part: u8,
+    },
+}
+
+pub struct PluginSolver {
+    // File name of the library, without any lib prefix or extension
+    pub plugin: &'static str,
+    solve: SolveExtern,
+    free_string: FreeExtern,
+}
+
+impl PluginSolver {
+    pub fn solve(&self, input: &str) -> Result<String, String> {
+        let mut output: *mut c_char = ptr::null_mut();
+        // Safety: the library is never unloaded and the plugin only reads `len` bytes of input
+        let status = unsafe { (self.solve)(input.as_ptr(), input.len(), &mut output) };
+        let text = if output.is_null() {
+            String::new()
+        } else {
+            // Safety: a non null output is a NUL terminated string owned by the plugin
+            let text = unsafe { CStr::from_ptr(output) }
+                .to_string_lossy()
+                .into_owned();
+            unsafe { (self.free_string)(output) };
+            text
+        };
+        match status {
+            0 => Ok(text),
+            _ if text.is_empty() => Err(format!("{} plugin failed with {}", self.plugin, status)),
+            _ => Err(text),
+        }
+    }
+}
+
+// Loaded libraries and the solutions they registered, kept for the life of the process
+static LIBRARIES: Mutex<Vec<Library>> = Mutex::new(Vec::new());
+static SOLUTIONS: Mutex<Vec<Solution>> = Mutex::new(Vec::new());
+
+// Days from every loaded plugin, in load order
+pub fn solutions() -> Vec<Solution> {
+    SOLUTIONS.lock().unwrap().clone()
+}
+
+pub fn is_library(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some(std::env::consts::DLL_EXTENSION)
+}
+
+fn plugin_name(path: &Path) -> String {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    stem.strip_prefix(std::env::consts::DLL_PREFIX)
+        .unwrap_or(&stem)
+        .to_string()
+}
+
+// Opens a library and reads its solutions without adding them to the registry. Once its
+// registration checks out the library stays loaded for the rest of the process.
+pub fn open(path: &Path) -> Result<Vec<Solution>, PluginError> {
+    let display = path.display().to_string();
+    let load_error = |source| PluginError::Load {
+        path: display.clone(),
+        source,
+    };
+    // Safety: loading runs the library's initialisers, which is the point of a plugin
+    let library = unsafe { Library::new(path) }.map_err(load_error)?;
+    let registration = {
+        let register = unsafe {
+            library.get::<unsafe extern "C" fn() -> *const AocPlugin>(REGISTER.as_bytes())
+        }
+        .map_err(load_error)?;
+        // Safety: the registration is static data in the library, which is never unloaded
+        unsafe { register().as_ref() }
+            .ok_or_else(|| PluginError::NoRegistration(display.clone()))?
+    };
+    if registration.abi_version != ABI_VERSION {
+        return Err(PluginError::Abi {
+            path: display,
+            found: registration.abi_version,
+        });
+    }
+    let solvers = if registration.solvers.is_null() || registration.count == 0 {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(registration.solvers, registration.count) }
+    };
+
+    let name: &'static str = Box::leak(plugin_name(path).into_boxed_str());
+    let mut days: BTreeMap<(u16, u8), [Solver; 2]> = BTreeMap::new();
+    for solver in solvers {
+        if !(1..=2).contains(&solver.part) {
+            return Err(PluginError::InvalidPart {
+                path: display,
+                year: solver.year,
+                day: solver.day,
+                part: solver.part,
+            });
+        }
+        let plugin_solver: &'static PluginSolver = Box::leak(Box::new(PluginSolver {
+            plugin: name,
+            solve: solver.solve,
+            free_string: registration.free_string,
+        }));
+        days.entry((solver.year, solver.day))
+            .or_insert([Solver::Missing; 2])[solver.part as usize - 1] =
+            Solver::Plugin(plugin_solver);
+    }
+    LIBRARIES.lock().unwrap().push(library);
+
+    Ok(days
+        .into_iter()
+        .map(|((year, day), [part1, part2])| Solution {
+            year,
+            day,
+            part1,
+            part2,
+        })
+        .collect())
+}
+
+// Opens a library and adds its solutions to the registry
+pub fn load(path: &Path) -> Result<Vec<Solution>, PluginError> {
+    let solutions = open(path)?;
+    SOLUTIONS.lock().unwrap().extend(solutions.iter().copied());
+    Ok(solutions)
+}
+
+// Loads every shared library directly inside `directory`, in file name order. A library that
+// fails to load doesn't stop the rest.
+pub fn load_dir(directory: &Path) -> Vec<(PathBuf, Result<Vec<Solution>, PluginError>)> {
+    let entries = match fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(source) => {
+            let path = directory.display().to_string();
+            return vec![(
+                directory.to_path_buf(),
+                Err(PluginError::ReadDir { path, source }),
+            )];
+        }
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && is_library(path))
+        .collect();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let result = load(&path);
+            (path, result)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use std::process::Command;
+
+    // Builds plugins/example into its own target directory, as the one running the tests is locked
+    fn example_plugin() -> PathBuf {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let target = root.join("target/plugin-tests");
+        let status = Command::new(env!("CARGO"))
+            .args(["build", "--quiet", "--manifest-path"])
+            .arg(root.join("plugins/example/Cargo.toml"))
+            .arg("--target-dir")
+            .arg(&target)
+            .status()
+            .expect("Unable to run cargo");
+        assert!(status.success(), "Unable to build the example plugin");
+        target.join("debug").join(format!(
+            "{}aoc_plugin_example.{}",
+            std::env::consts::DLL_PREFIX,
+            std::env::consts::DLL_EXTENSION
+        ))
+    }
+
+    const EXAMPLE: &str = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+
+    #[test]
+    fn test_example_plugin_registers_day_1() {
+        let solutions = open(&example_plugin()).unwrap();
+        assert_eq!(solutions.len(), 1);
+        let solution = solutions[0];
+        assert_eq!((solution.year, solution.day), (2021, 1));
+        assert_eq!(solution.plugin(), Some("aoc_plugin_example"));
+    }
+
+    #[test]
+    fn test_example_plugin_solves() {
+        let solution = open(&example_plugin()).unwrap()[0];
+        assert_eq!(solution.solve(1, EXAMPLE), Ok(String::from("7")));
+        assert_eq!(solution.solve(2, EXAMPLE), Ok(String::from("5")));
+    }
+
+    #[test]
+    fn test_plugin_errors_are_passed_on() {
+        let solution = open(&example_plugin()).unwrap()[0];
+        assert_eq!(
+            solution.solve(1, "199\n200\nabc\n"),
+            Err(String::from("Line 3: invalid depth \"abc\""))
+        );
+    }
+
+    fn builtin() -> Solution {
+        Solution {
+            year: 2021,
+            day: 1,
+            part1: Solver::Builtin(|_| Ok(String::from("built in"))),
+            part2: Solver::Builtin(|_| Ok(String::from("built in"))),
+        }
+    }
+
+    // What --day runs once --plugins has loaded the example, which is also built in
+    #[test]
+    fn test_plugin_day_sits_next_to_built_in() {
+        let solutions = registry::with_plugins(vec![builtin()], open(&example_plugin()).unwrap());
+        assert_eq!(solutions.len(), 2);
+        assert_eq!(solutions[0].plugin(), None);
+        assert_eq!(solutions[0].solve(1, EXAMPLE), Ok(String::from("built in")));
+        assert_eq!(solutions[1].plugin(), Some("aoc_plugin_example"));
+        assert_eq!(solutions[1].solve(1, EXAMPLE), Ok(String::from("7")));
+    }
+
+    #[test]
+    fn test_plugin_days_keep_their_order() {
+        let other = Solution {
+            day: 2,
+            ..builtin()
+        };
+        let example = open(&example_plugin()).unwrap();
+        let solutions = registry::with_plugins(vec![builtin(), other], example.iter().copied());
+        let order: Vec<(u8, Option<&str>)> = solutions
+            .iter()
+            .map(|solution| (solution.day, solution.plugin()))
+            .collect();
+        assert_eq!(
+            order,
+            [(1, None), (1, Some("aoc_plugin_example")), (2, None)]
+        );
+    }
+
+    #[test]
+    fn test_missing_directory_is_an_error() {
+        let missing = Path::new(env!("CARGO_MANIFEST_DIR")).join("no-such-plugins");
+        let results = load_dir(&missing);
+        assert_eq!(results.len(), 1);
+        assert!(matches!(results[0].1, Err(PluginError::ReadDir { .. })));
+    }
+
+    #[test]
+    fn test_missing_library() {
+        let missing = Path::new(env!("CARGO_MANIFEST_DIR")).join("no-such-plugins");
+        assert!(matches!(
+            open(&missing.join("libnothing.so")),
+            Err(PluginError::Load { .. })
+        ));
+    }
+}
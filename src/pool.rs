@@ -0,0 +1,178 @@
+// Runs independent tasks on a fixed number of std threads. Each thread takes the next task off a
+// shared counter, and results are handed back in task order however the threads interleave, so
+// output is the same as running the tasks one after another.
+//
+// The number of threads comes from the CLI's --jobs option. It's kept here rather than passed
+// down so solvers, which only see their input, can split their own work with the same setting.
+//
+// Maps can nest, e.g. `run` spreads parts over the jobs and day 5 then spreads its lines. The
+// threads every map starts come out of one shared count, so however deep they nest no more than
+// `jobs` threads are working at once - an inner map with no threads left over runs on its caller.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+static JOBS: AtomicUsize = AtomicUsize::new(1);
+// Threads started by maps that are still running, on top of the threads that called them
+static STARTED: AtomicUsize = AtomicUsize::new(0);
+
+pub fn set_jobs(jobs: usize) {
+    JOBS.store(jobs.max(1), Ordering::Relaxed);
+}
+
+pub fn jobs() -> usize {
+    JOBS.load(Ordering::Relaxed)
+}
+
+// Takes up to `wanted` more threads, keeping the total working under `jobs`
+fn start(jobs: usize, wanted: usize) -> usize {
+    let mut started = STARTED.load(Ordering::Relaxed);
+    loop {
+        let threads = wanted.min(jobs.saturating_sub(1).saturating_sub(started));
+        if threads == 0 {
+            return 0;
+        }
+        match STARTED.compare_exchange_weak(
+            started,
+            started + threads,
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+        ) {
+            Ok(_) => return threads,
+            Err(current) => started = current,
+        }
+    }
+}
+
+// Hands the threads back when the map finishes, or unwinds
+struct Started(usize);
+
+impl Drop for Started {
+    fn drop(&mut self) {
+        STARTED.fetch_sub(self.0, Ordering::Relaxed);
+    }
+}
+
+// Applies `f` to every task on the calling thread and up to `jobs - 1` more, fewer if enclosing
+// maps already have threads working. With one job (or one task) everything runs on the calling
+// thread. A panic in `f` is passed on once the other threads have finished, so callers wanting
+// to isolate failures should catch them inside `f`.
+pub fn map<T: Sync, R: Send>(jobs: usize, tasks: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let started = Started(start(jobs, tasks.len().saturating_sub(1)));
+    if started.0 == 0 {
+        return tasks.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let results: Vec<Mutex<Option<R>>> = tasks.iter().map(|_| Mutex::new(None)).collect();
+    let work = || loop {
+        let i = next.fetch_add(1, Ordering::Relaxed);
+        match tasks.get(i) {
+            Some(task) => *results[i].lock().unwrap() = Some(f(task)),
+            None => break,
+        }
+    };
+    thread::scope(|scope| {
+        for _ in 0..started.0 {
+            scope.spawn(work);
+        }
+        work();
+    });
+    drop(started);
+    results
+        .into_iter()
+        .map(|result| {
+            result
+                .into_inner()
+                .unwrap()
+                .expect("Every task is run before the scope ends")
+        })
+        .collect()
+}
+
+// Splits `items` into at most `jobs` contiguous chunks of near equal size, for work that's
+// cheaper to share out in blocks than one item at a time
+pub fn chunks<T>(jobs: usize, items: &[T]) -> Vec<&[T]> {
+    let size = items.len().div_ceil(jobs.max(1)).max(1);
+    items.chunks(size).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_results_in_task_order() {
+        // Earlier tasks take longer so they finish last
+        let tasks: Vec<u64> = (0..12).collect();
+        let squares = map(4, &tasks, |&n| {
+            thread::sleep(Duration::from_millis(12 - n));
+            n * n
+        });
+        assert_eq!(squares, tasks.iter().map(|n| n * n).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn test_one_job_runs_on_the_caller() {
+        let tasks: Vec<u64> = (0..12).collect();
+        let caller = thread::current().id();
+        assert!(map(1, &tasks, |_| thread::current().id())
+            .iter()
+            .all(|&id| id == caller));
+    }
+
+    #[test]
+    fn test_no_tasks() {
+        assert!(map(8, &[] as &[u64], |&n| n).is_empty());
+    }
+
+    #[test]
+    fn test_nested_maps_share_the_jobs() {
+        let working = AtomicUsize::new(0);
+        let most = AtomicUsize::new(0);
+        let outer: Vec<u64> = (0..4).collect();
+        let inner: Vec<u64> = (0..8).collect();
+        let sums = map(4, &outer, |&i| {
+            map(4, &inner, |&j| {
+                let now = working.fetch_add(1, Ordering::SeqCst) + 1;
+                most.fetch_max(now, Ordering::SeqCst);
+                thread::sleep(Duration::from_millis(2));
+                working.fetch_sub(1, Ordering::SeqCst);
+                i * 10 + j
+            })
+            .iter()
+            .sum::<u64>()
+        });
+        assert_eq!(sums, vec![28, 108, 188, 268]);
+        assert!(most.load(Ordering::SeqCst) <= 4);
+    }
+
+    #[test]
+    fn test_panic_is_passed_on() {
+        let tasks: Vec<u64> = (0..8).collect();
+        let result = std::panic::catch_unwind(|| map(4, &tasks, |&n| assert!(n != 5)));
+        assert!(result.is_err());
+        assert_eq!(map(4, &tasks, |&n| n).len(), 8);
+    }
+
+    #[test]
+    fn test_chunks_near_equal() {
+        let items: Vec<u8> = (0..10).collect();
+        let sizes: Vec<usize> = chunks(4, &items).iter().map(|c| c.len()).collect();
+        assert_eq!(sizes, vec![3, 3, 3, 1]);
+    }
+
+    #[test]
+    fn test_chunks_zero_jobs_is_one_chunk() {
+        let items: Vec<u8> = (0..10).collect();
+        assert_eq!(chunks(0, &items).len(), 1);
+    }
+
+    #[test]
+    fn test_chunks_more_jobs_than_items() {
+        let items: Vec<u8> = (0..10).collect();
+        assert_eq!(chunks(20, &items).len(), 10);
+    }
+}
@@ -0,0 +1,264 @@
+// Per-year record files under data/<year>/. Both are plain text with one `<day> <part> <value>`
+// line per entry, blank lines and `#` comments are ignored:
+//
+//     answers.txt   the verified answer for each part, checked in
+//     runtimes.txt  how long the CLI last took to solve each part in microseconds, written with
+//                   --record for parts whose answer matched answers.txt and removed for parts
+//                   whose answer didn't, local only
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::embedded;
+
+#[derive(Debug, Error)]
+pub enum RecordError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("Line {line}: expected <day> <part> <value>, found {found:?}")]
+    InvalidLine { line: usize, found: String },
+    #[error("Day {day} part {part}: invalid runtime {found:?}")]
+    InvalidRuntime { day: u8, part: u8, found: String },
+}
+
+// Values keyed on (day, part)
+pub type Records = BTreeMap<(u8, u8), String>;
+
+pub fn answers_path(data: &Path, year: u16) -> PathBuf {
+    data.join(year.to_string()).join("answers.txt")
+}
+
+pub fn runtimes_path(data: &Path, year: u16) -> PathBuf {
+    data.join(year.to_string()).join("runtimes.txt")
+}
+
+pub fn parse(text: &str) -> Result<Records, RecordError> {
+    let mut records = Records::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = || RecordError::InvalidLine {
+            line: i + 1,
+            found: line.to_string(),
+        };
+        let mut fields = line.splitn(3, char::is_whitespace);
+        let day = fields
+            .next()
+            .and_then(|d| d.parse().ok())
+            .ok_or_else(invalid)?;
+        let part = fields
+            .next()
+            .and_then(|p| p.parse().ok())
+            .ok_or_else(invalid)?;
+        let value = fields.next().map(str::trim).ok_or_else(invalid)?;
+        records.insert((day, part), value.to_string());
+    }
+    Ok(records)
+}
+
+// A missing file has no records
+pub fn load(path: &Path) -> Result<Records, RecordError> {
+    match embedded::read(path) {
+        Ok(text) => parse(&text),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Records::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub fn load_runtimes(path: &Path) -> Result<BTreeMap<(u8, u8), Duration>, RecordError> {
+    let mut runtimes = BTreeMap::new();
+    for ((day, part), value) in load(path)? {
+        let micros = value.parse().map_err(|_| RecordError::InvalidRuntime {
+            day,
+            part,
+            found: value.clone(),
+        })?;
+        runtimes.insert((day, part), Duration::from_micros(micros));
+    }
+    Ok(runtimes)
+}
+
+// Replaces the runtime recorded for a part, keeping the rest of the file
+pub fn record_runtime(
+    path: &Path,
+    day: u8,
+    part: u8,
+    runtime: Duration,
+) -> Result<(), RecordError> {
+    let mut runtimes = load(path)?;
+    runtimes.insert((day, part), runtime.as_micros().to_string());
+    write_runtimes(path, runtimes)
+}
+
+// Removes the runtime recorded for a part, if there is one
+pub fn forget_runtime(path: &Path, day: u8, part: u8) -> Result<(), RecordError> {
+    let mut runtimes = load(path)?;
+    match runtimes.remove(&(day, part)) {
+        Some(_) => write_runtimes(path, runtimes),
+        None => Ok(()),
+    }
+}
+
+// Records the runtime of a run whose answer matched, or forgets the part's old runtime when it
+// didn't, so a part that regresses loses its star
+pub fn record_run(
+    path: &Path,
+    day: u8,
+    part: u8,
+    runtime: Duration,
+    verified: bool,
+) -> Result<(), RecordError> {
+    match verified {
+        true => record_runtime(path, day, part, runtime),
+        false => forget_runtime(path, day, part),
+    }
+}
+
+fn write_runtimes(path: &Path, runtimes: Records) -> Result<(), RecordError> {
+    let mut text = String::from("# <day> <part> <microseconds>, written by the CLI\n");
+    for ((day, part), micros) in runtimes {
+        text.push_str(&format!("{} {} {}\n", day, part, micros));
+    }
+    fs::write(path, text)?;
+    Ok(())
+}
+
+pub fn format_runtime(runtime: Duration) -> String {
+    let micros = runtime.as_micros();
+    if micros < 1_000 {
+        format!("{}us", micros)
+    } else if micros < 1_000_000 {
+        format!("{:.1}ms", micros as f64 / 1_000.0)
+    } else {
+        format!("{:.2}s", micros as f64 / 1_000_000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("aoc_records_{}_{}.txt", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_parse() {
+        let records = parse("1 1 1752\n1 2 1781\n").unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[&(1, 1)], "1752");
+        assert_eq!(records[&(1, 2)], "1781");
+    }
+
+    #[test]
+    fn test_parse_skips_comments_and_blank_lines() {
+        let records = parse("# day part answer\n\n   \n  # indented\n1 1 1752\n").unwrap();
+        assert_eq!(records.len(), 1);
+    }
+
+    #[test]
+    fn test_values_can_have_spaces() {
+        let records = parse("4 2 no board wins  \n").unwrap();
+        assert_eq!(records[&(4, 2)], "no board wins");
+    }
+
+    #[test]
+    fn test_later_lines_replace_earlier_ones() {
+        assert_eq!(parse("1 1 5\n1 1 6\n").unwrap()[&(1, 1)], "6");
+    }
+
+    #[test]
+    fn test_invalid_part() {
+        assert!(matches!(
+            parse("1 x 5\n"),
+            Err(RecordError::InvalidLine { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_missing_value() {
+        assert!(matches!(
+            parse("# answers\n1 1\n"),
+            Err(RecordError::InvalidLine { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_day_out_of_range() {
+        assert!(parse("256 1 5\n").is_err());
+    }
+
+    #[test]
+    fn test_missing_file_has_no_records() {
+        assert!(load(&temp_file("missing")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_runtime() {
+        let path = temp_file("invalid_runtime");
+        fs::write(&path, "3 1 fast\n").unwrap();
+        let loaded = load_runtimes(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            loaded.unwrap_err().to_string(),
+            "Day 3 part 1: invalid runtime \"fast\""
+        );
+    }
+
+    #[test]
+    fn test_record_runtime_keeps_other_parts() {
+        let path = temp_file("record");
+        fs::write(&path, "1 1 100\n1 2 200\n").unwrap();
+        record_runtime(&path, 1, 2, Duration::from_micros(150)).unwrap();
+        record_runtime(&path, 2, 1, Duration::from_micros(50)).unwrap();
+        let runtimes = load_runtimes(&path);
+        fs::remove_file(&path).unwrap();
+        let micros: Vec<((u8, u8), u128)> = runtimes
+            .unwrap()
+            .into_iter()
+            .map(|(key, runtime)| (key, runtime.as_micros()))
+            .collect();
+        assert_eq!(micros, [((1, 1), 100), ((1, 2), 150), ((2, 1), 50)]);
+    }
+
+    #[test]
+    fn test_forget_runtime() {
+        let path = temp_file("forget");
+        fs::write(&path, "1 1 100\n1 2 200\n").unwrap();
+        forget_runtime(&path, 1, 2).unwrap();
+        let runtimes = load_runtimes(&path);
+        fs::remove_file(&path).unwrap();
+        let parts: Vec<(u8, u8)> = runtimes.unwrap().into_keys().collect();
+        assert_eq!(parts, [(1, 1)]);
+    }
+
+    #[test]
+    fn test_forget_without_a_file_writes_nothing() {
+        let path = temp_file("forget_missing");
+        forget_runtime(&path, 1, 1).unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_format_runtime() {
+        assert_eq!(format_runtime(Duration::from_micros(999)), "999us");
+        assert_eq!(format_runtime(Duration::from_micros(12_345)), "12.3ms");
+        assert_eq!(format_runtime(Duration::from_millis(2_500)), "2.50s");
+    }
+
+    #[test]
+    fn test_format_runtime_boundaries() {
+        assert_eq!(format_runtime(Duration::ZERO), "0us");
+        assert_eq!(format_runtime(Duration::from_micros(1_000)), "1.0ms");
+        assert_eq!(format_runtime(Duration::from_micros(1_000_000)), "1.00s");
+        // Below a microsecond rounds down
+        assert_eq!(format_runtime(Duration::from_nanos(999)), "0us");
+    }
+}
@@ -0,0 +1,260 @@
+// Registry of the days compiled into the library, keyed on (year, day). Each day is behind its
+// own cargo feature, so the CLI and test harnesses look days up here rather than naming the
+// modules directly. Days from loaded plugins (see plugin) are listed next to the built in days,
+// so a plugin day that is also built in is run, benched and listed alongside it for comparison.
+
+use std::fmt::Display;
+
+#[cfg(feature = "plugins")]
+use crate::plugin::{self, PluginSolver};
+
+// A built in solver, returning the answer or the error as a display string
+pub type SolveFn = fn(&str) -> Result<String, String>;
+
+// Draws a day's input as text, for the tui
+pub type Visualiser = fn(&str) -> Result<String, String>;
+
+// Solves one part of a day
+#[derive(Clone, Copy)]
+pub enum Solver {
+    Builtin(SolveFn),
+    #[cfg(feature = "plugins")]
+    Plugin(&'static PluginSolver),
+    // A plugin day that doesn't provide this part
+    Missing,
+}
+
+impl Solver {
+    pub fn solve(&self, input: &str) -> Result<String, String> {
+        match self {
+            Solver::Builtin(solve) => solve(input),
+            #[cfg(feature = "plugins")]
+            Solver::Plugin(solver) => solver.solve(input),
+            Solver::Missing => Err(String::from("Part not provided")),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub year: u16,
+    pub day: u8,
+    pub part1: Solver,
+    pub part2: Solver,
+}
+
+impl Solution {
+    pub fn part(&self, part: u8) -> Option<Solver> {
+        match (part, self.part1, self.part2) {
+            (1, Solver::Missing, _) | (2, _, Solver::Missing) => None,
+            (1, solver, _) | (2, _, solver) => Some(solver),
+            _ => None,
+        }
+    }
+
+    // The plugin the day was loaded from, None for built in days
+    #[cfg(feature = "plugins")]
+    pub fn plugin(&self) -> Option<&'static str> {
+        [self.part1, self.part2]
+            .iter()
+            .find_map(|solver| match solver {
+                Solver::Plugin(solver) => Some(solver.plugin),
+                _ => None,
+            })
+    }
+
+    #[cfg(not(feature = "plugins"))]
+    pub fn plugin(&self) -> Option<&'static str> {
+        None
+    }
+
+    pub fn solve(&self, part: u8, input: &str) -> Result<String, String> {
+        match self.part(part) {
+            Some(solver) => solver.solve(input),
+            None => Err(format!(
+                "Day {} of {} has no part {}",
+                self.day, self.year, part
+            )),
+        }
+    }
+}
+
+// Converts a day's own answer and error types into display strings
+pub fn answer<T: Display, E: Display>(result: Result<T, E>) -> Result<String, String> {
+    result
+        .map(|answer| answer.to_string())
+        .map_err(|e| e.to_string())
+}
+
+// All compiled in and plugin days of every year, in year then day order
+pub fn solutions() -> Vec<Solution> {
+    #[cfg(feature = "plugins")]
+    return with_plugins(crate::y2021::solutions(), plugin::solutions());
+    #[cfg(not(feature = "plugins"))]
+    crate::y2021::solutions()
+}
+
+// Adds plugin days to the built in ones. A plugin day that is also built in comes after the built
+// in one, and after any plugins loaded before it.
+#[cfg(feature = "plugins")]
+pub(crate) fn with_plugins(
+    mut solutions: Vec<Solution>,
+    plugins: impl IntoIterator<Item = Solution>,
+) -> Vec<Solution> {
+    solutions.extend(plugins);
+    solutions.sort_by_key(|solution| (solution.year, solution.day));
+    solutions
+}
+
+pub fn visualisation(year: u16, day: u8) -> Option<Visualiser> {
+    match year {
+        crate::y2021::YEAR => crate::y2021::visualisation(day),
+        _ => None,
+    }
+}
+
+pub fn years() -> Vec<u16> {
+    let mut years: Vec<u16> = solutions().iter().map(|solution| solution.year).collect();
+    years.dedup();
+    years
+}
+
+// The built in day, or the first plugin to provide it when it isn't built in
+pub fn find(year: u16, day: u8) -> Option<Solution> {
+    find_all(year, day).into_iter().next()
+}
+
+// Every solution of a day, the built in one first
+pub fn find_all(year: u16, day: u8) -> Vec<Solution> {
+    solutions()
+        .into_iter()
+        .filter(|solution| solution.year == year && solution.day == day)
+        .collect()
+}
+
+pub fn days(year: u16) -> Vec<u8> {
+    let mut days: Vec<u8> = solutions()
+        .iter()
+        .filter(|solution| solution.year == year)
+        .map(|solution| solution.day)
+        .collect();
+    days.dedup();
+    days
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn builtin(part1: Solver, part2: Solver) -> Solution {
+        Solution {
+            year: 2021,
+            day: 30,
+            part1,
+            part2,
+        }
+    }
+
+    #[test]
+    fn test_days_match_features() {
+        let compiled = [
+            cfg!(feature = "day1"),
+            cfg!(feature = "day2"),
+            cfg!(feature = "day3"),
+            cfg!(feature = "day4"),
+            cfg!(feature = "day5"),
+            cfg!(feature = "day6"),
+            cfg!(feature = "day7"),
+            cfg!(feature = "day8"),
+        ];
+        let expected: Vec<u8> = (1..=8).filter(|&day| compiled[day as usize - 1]).collect();
+        assert_eq!(days(2021), expected);
+    }
+
+    #[test]
+    fn test_unknown_day() {
+        assert!(find(2021, 9).is_none());
+        assert!(find(2021, 0).is_none());
+    }
+
+    #[cfg(feature = "day1")]
+    #[test]
+    fn test_find_all_starts_with_the_built_in_day() {
+        let found = find_all(2021, 1);
+        assert!(found[0].plugin().is_none());
+        assert_eq!(
+            find(2021, 1).map(|solution| solution.day),
+            Some(found[0].day)
+        );
+        assert!(find_all(2021, 9).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_year() {
+        assert!(days(2015).is_empty());
+        assert!(find(2015, 1).is_none());
+        assert!(visualisation(2015, 1).is_none());
+    }
+
+    #[cfg(feature = "day1")]
+    #[test]
+    fn test_years() {
+        assert_eq!(years(), [2021]);
+    }
+
+    #[test]
+    fn test_visualisations_match_features() {
+        assert_eq!(visualisation(2021, 1).is_some(), cfg!(feature = "day1"));
+        assert_eq!(visualisation(2021, 5).is_some(), cfg!(feature = "day5"));
+        assert!(visualisation(2021, 2).is_none());
+    }
+
+    #[cfg(feature = "day4")]
+    #[test]
+    fn test_errors_are_reported() {
+        let solution = find(2021, 4).unwrap();
+        assert!(solution.solve(1, "1,2,3\n\n1 2 3\n").is_err());
+    }
+
+    #[test]
+    fn test_solve_only_parts_1_and_2() {
+        let solution = builtin(Solver::Builtin(|_| Ok(String::from("1"))), Solver::Missing);
+        assert_eq!(solution.solve(1, ""), Ok(String::from("1")));
+        for part in [0, 3] {
+            assert_eq!(
+                solution.solve(part, ""),
+                Err(format!("Day 30 of 2021 has no part {}", part))
+            );
+        }
+    }
+
+    #[test]
+    fn test_missing_part() {
+        let solution = builtin(Solver::Missing, Solver::Builtin(|_| Ok(String::new())));
+        assert!(solution.part(1).is_none() && solution.part(2).is_some());
+        assert_eq!(
+            solution.solve(1, ""),
+            Err(String::from("Day 30 of 2021 has no part 1"))
+        );
+        assert_eq!(
+            Solver::Missing.solve(""),
+            Err(String::from("Part not provided"))
+        );
+    }
+
+    #[test]
+    fn test_builtin_days_have_no_plugin() {
+        assert!(crate::y2021::solutions()
+            .iter()
+            .all(|solution| solution.plugin().is_none()));
+    }
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(answer::<u32, String>(Ok(7)), Ok(String::from("7")));
+        assert_eq!(
+            answer::<u32, &str>(Err("Bad input")),
+            Err(String::from("Bad input"))
+        );
+    }
+}
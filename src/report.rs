@@ -0,0 +1,401 @@
+// Markdown and HTML reports of a full run. Both are single self-contained files - the runtime chart
+// is inline SVG and the HTML styles are inline - so they can be pasted into a wiki as they are.
+
+use std::fmt::Write;
+use std::path::Path;
+
+use thiserror::Error;
+
+use crate::records::format_runtime;
+use crate::run::{PartRun, Verdict, YearRun};
+
+#[derive(Debug, Error)]
+pub enum ReportError {
+    #[error("Unknown report type for {0}, expected a .html or .md file")]
+    UnknownExtension(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportFormat {
+    Html,
+    Markdown,
+}
+
+impl ReportFormat {
+    pub fn from_path(path: &Path) -> Result<Self, ReportError> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("html") | Some("htm") => Ok(ReportFormat::Html),
+            Some("md") | Some("markdown") => Ok(ReportFormat::Markdown),
+            _ => Err(ReportError::UnknownExtension(path.display().to_string())),
+        }
+    }
+}
+
+pub fn render(format: ReportFormat, run: &YearRun) -> String {
+    match format {
+        ReportFormat::Html => html(run),
+        ReportFormat::Markdown => markdown(run),
+    }
+}
+
+pub fn write(path: &Path, run: &YearRun) -> Result<(), ReportError> {
+    let format = ReportFormat::from_path(path)?;
+    std::fs::write(path, render(format, run))?;
+    Ok(())
+}
+
+fn verdict_label(verdict: &Verdict) -> String {
+    match verdict {
+        Verdict::Pass => String::from("pass"),
+        Verdict::Fail(expected) => format!("FAIL (expected {})", expected),
+        Verdict::Unverified => String::from("unverified"),
+        Verdict::Error(e) => format!("ERROR: {}", e),
+    }
+}
+
+fn verdict_colour(verdict: &Verdict) -> &'static str {
+    match verdict {
+        Verdict::Pass => "#2e7d32",
+        Verdict::Fail(_) => "#c62828",
+        Verdict::Unverified => "#757575",
+        Verdict::Error(_) => "#ef6c00",
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn escape_markdown(text: &str) -> String {
+    escape_html(text).replace('|', "\\|").replace('\n', " ")
+}
+
+fn summary(run: &YearRun) -> String {
+    format!(
+        "{} passed, {} failed, {} unverified, {} errors in {}",
+        run.count(|v| *v == Verdict::Pass),
+        run.count(|v| matches!(v, Verdict::Fail(_))),
+        run.count(|v| *v == Verdict::Unverified),
+        run.count(|v| matches!(v, Verdict::Error(_))),
+        format_runtime(run.total_runtime())
+    )
+}
+
+// Every warning and error in the run, prefixed with the day they belong to
+fn problems(run: &YearRun) -> Vec<String> {
+    let mut problems = run.warnings.clone();
+    for day in &run.days {
+        for warning in &day.warnings {
+            problems.push(format!("Day {}: {}", day.day, warning));
+        }
+        for part in &day.parts {
+            if let Verdict::Error(e) = &part.verdict {
+                problems.push(format!("Day {} part {}: {}", day.day, part.part, e));
+            }
+        }
+    }
+    problems
+}
+
+const BAR_HEIGHT: usize = 18;
+const LABEL_WIDTH: usize = 90;
+const CHART_WIDTH: usize = 600;
+
+// Horizontal bar per part, scaled to the slowest part and coloured by verdict
+pub fn runtime_chart(run: &YearRun) -> String {
+    let parts: Vec<(u8, &PartRun)> = run.parts().collect();
+    let slowest = parts
+        .iter()
+        .map(|(_, part)| part.runtime.as_secs_f64())
+        .fold(0.0, f64::max);
+    let bar_space = CHART_WIDTH - LABEL_WIDTH - 80;
+    let height = parts.len().max(1) * (BAR_HEIGHT + 4) + 4;
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+         viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\" font-size=\"12\">",
+        w = CHART_WIDTH,
+        h = height
+    )
+    .unwrap();
+    for (i, (day, part)) in parts.iter().enumerate() {
+        let y = 4 + i * (BAR_HEIGHT + 4);
+        let width = if slowest > 0.0 {
+            ((part.runtime.as_secs_f64() / slowest) * bar_space as f64).max(1.0)
+        } else {
+            1.0
+        };
+        let text_y = y + BAR_HEIGHT - 5;
+        writeln!(
+            svg,
+            "<text x=\"0\" y=\"{}\">Day {} part {}</text>",
+            text_y, day, part.part
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            "<rect x=\"{}\" y=\"{}\" width=\"{:.1}\" height=\"{}\" fill=\"{}\"/>",
+            LABEL_WIDTH,
+            y,
+            width,
+            BAR_HEIGHT,
+            verdict_colour(&part.verdict)
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{}\">{}</text>",
+            LABEL_WIDTH as f64 + width + 4.0,
+            text_y,
+            format_runtime(part.runtime)
+        )
+        .unwrap();
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+pub fn markdown(run: &YearRun) -> String {
+    let mut output = String::new();
+    writeln!(output, "# Advent of Code {} run\n", run.year).unwrap();
+    writeln!(output, "{}\n", summary(run)).unwrap();
+
+    output.push_str("| Day | Part | Answer | Runtime | Result |\n");
+    output.push_str("| ---: | ---: | --- | ---: | --- |\n");
+    for (day, part) in run.parts() {
+        writeln!(
+            output,
+            "| {} | {} | {} | {} | {} |",
+            day,
+            part.part,
+            escape_markdown(part.answer.as_deref().unwrap_or("-")),
+            format_runtime(part.runtime),
+            escape_markdown(&verdict_label(&part.verdict))
+        )
+        .unwrap();
+    }
+
+    output.push_str("\n## Runtimes\n\n");
+    output.push_str(&runtime_chart(run));
+
+    let problems = problems(run);
+    if !problems.is_empty() {
+        output.push_str("\n## Warnings and errors\n\n");
+        for problem in problems {
+            writeln!(output, "- {}", escape_markdown(&problem)).unwrap();
+        }
+    }
+    output
+}
+
+pub fn html(run: &YearRun) -> String {
+    let mut output = String::new();
+    output.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    writeln!(
+        output,
+        "<title>Advent of Code {} run</title>\n<style>\n\
+         body {{ font-family: sans-serif; margin: 2em; }}\n\
+         table {{ border-collapse: collapse; }}\n\
+         th, td {{ border: 1px solid #ccc; padding: 4px 8px; }}\n\
+         td.number {{ text-align: right; }}\n\
+         </style>\n</head>\n<body>",
+        run.year
+    )
+    .unwrap();
+    writeln!(output, "<h1>Advent of Code {} run</h1>", run.year).unwrap();
+    writeln!(output, "<p>{}</p>", escape_html(&summary(run))).unwrap();
+
+    output.push_str("<table>\n<tr><th>Day</th><th>Part</th><th>Answer</th><th>Runtime</th><th>Result</th></tr>\n");
+    for (day, part) in run.parts() {
+        writeln!(
+            output,
+            "<tr><td class=\"number\">{}</td><td class=\"number\">{}</td><td>{}</td>\
+             <td class=\"number\">{}</td><td style=\"color: {}\">{}</td></tr>",
+            day,
+            part.part,
+            escape_html(part.answer.as_deref().unwrap_or("-")),
+            format_runtime(part.runtime),
+            verdict_colour(&part.verdict),
+            escape_html(&verdict_label(&part.verdict))
+        )
+        .unwrap();
+    }
+    output.push_str("</table>\n");
+
+    output.push_str("<h2>Runtimes</h2>\n");
+    output.push_str(&runtime_chart(run));
+
+    let problems = problems(run);
+    if !problems.is_empty() {
+        output.push_str("<h2>Warnings and errors</h2>\n<ul>\n");
+        for problem in problems {
+            writeln!(output, "<li>{}</li>", escape_html(&problem)).unwrap();
+        }
+        output.push_str("</ul>\n");
+    }
+    output.push_str("</body>\n</html>\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run::DayRun;
+    use std::time::Duration;
+
+    fn sample_run() -> YearRun {
+        let part = |part, answer: &str, verdict| PartRun {
+            part,
+            answer: Some(answer.to_string()),
+            runtime: Duration::from_micros(250 * part as u64),
+            verdict,
+            memory: None,
+        };
+        YearRun {
+            year: 2021,
+            days: vec![DayRun {
+                day: 1,
+                plugin: None,
+                input: "data/2021/day1/input.txt".into(),
+                parts: vec![
+                    part(1, "7", Verdict::Pass),
+                    PartRun {
+                        answer: None,
+                        ..part(2, "", Verdict::Error(String::from("Line 3: <bad> input")))
+                    },
+                ],
+                warnings: vec![String::from("No verified answer for part 2")],
+            }],
+            warnings: Vec::new(),
+        }
+    }
+
+    fn empty_run() -> YearRun {
+        YearRun {
+            year: 2021,
+            days: Vec::new(),
+            warnings: vec![String::from("No days compiled in for 2021")],
+        }
+    }
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(
+            ReportFormat::from_path(Path::new("report.html")).unwrap(),
+            ReportFormat::Html
+        );
+        assert_eq!(
+            ReportFormat::from_path(Path::new("out/report.md")).unwrap(),
+            ReportFormat::Markdown
+        );
+    }
+
+    #[test]
+    fn test_unknown_extension() {
+        assert!(ReportFormat::from_path(Path::new("report.pdf")).is_err());
+        assert!(ReportFormat::from_path(Path::new("report")).is_err());
+    }
+
+    #[test]
+    fn test_summary() {
+        let summary = summary(&sample_run());
+        assert_eq!(
+            summary,
+            "1 passed, 0 failed, 0 unverified, 1 errors in 750us"
+        );
+    }
+
+    #[test]
+    fn test_chart_has_a_bar_per_part() {
+        let chart = runtime_chart(&sample_run());
+        assert_eq!(chart.matches("<rect").count(), 2);
+        // The slowest part fills the bar space and the rest are scaled to it
+        assert!(chart.contains("width=\"430.0\" height=\"18\" fill=\"#ef6c00\""));
+        assert!(chart.contains("width=\"215.0\" height=\"18\" fill=\"#2e7d32\""));
+    }
+
+    #[test]
+    fn test_chart_of_instant_parts() {
+        let mut run = sample_run();
+        for part in &mut run.days[0].parts {
+            part.runtime = Duration::ZERO;
+        }
+        let chart = runtime_chart(&run);
+        assert_eq!(chart.matches("width=\"1.0\"").count(), 2);
+        assert!(!chart.contains("NaN"));
+    }
+
+    #[test]
+    fn test_markdown_table() {
+        let report = markdown(&sample_run());
+        assert!(report.starts_with("# Advent of Code 2021 run\n"));
+        assert!(report.contains("| 1 | 1 | 7 | 250us | pass |\n"));
+        assert!(report.contains("| 1 | 2 | - | 500us | ERROR: Line 3: &lt;bad&gt; input |\n"));
+    }
+
+    #[test]
+    fn test_markdown_escapes_table_cells() {
+        let mut run = sample_run();
+        run.days[0].parts[0].answer = Some(String::from("a|b\nc"));
+        assert!(markdown(&run).contains("| a\\|b c |"));
+    }
+
+    #[test]
+    fn test_html_table() {
+        let report = html(&sample_run());
+        assert!(report.starts_with("<!DOCTYPE html>\n"));
+        assert!(report.ends_with("</body>\n</html>\n"));
+        assert!(report.contains("<td>7</td>"));
+        assert!(
+            report.contains("<td style=\"color: #ef6c00\">ERROR: Line 3: &lt;bad&gt; input</td>")
+        );
+    }
+
+    #[test]
+    fn test_problems_are_listed() {
+        for format in [ReportFormat::Html, ReportFormat::Markdown] {
+            let report = render(format, &sample_run());
+            assert!(report.contains("Day 1 part 2: Line 3: &lt;bad&gt; input"));
+            assert!(report.contains("Day 1: No verified answer for part 2"));
+        }
+    }
+
+    #[test]
+    fn test_reports_are_self_contained() {
+        for format in [ReportFormat::Html, ReportFormat::Markdown] {
+            let report = render(format, &sample_run());
+            // Nothing to fetch - no links, stylesheets, scripts or images
+            assert!(!report.contains("href=") && !report.contains("src="));
+        }
+    }
+
+    #[test]
+    fn test_report_of_an_empty_run() {
+        for format in [ReportFormat::Html, ReportFormat::Markdown] {
+            let report = render(format, &empty_run());
+            assert!(report.contains("0 passed, 0 failed, 0 unverified, 0 errors in 0us"));
+            assert_eq!(report.matches("<rect").count(), 0);
+            assert!(report.contains("No days compiled in for 2021"));
+        }
+    }
+
+    #[test]
+    fn test_write_picks_the_format() {
+        let path = std::env::temp_dir().join(format!("aoc_report_{}.md", std::process::id()));
+        write(&path, &sample_run()).unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(written.starts_with("# Advent of Code 2021 run"));
+        assert!(matches!(
+            write(Path::new("report.txt"), &sample_run()),
+            Err(ReportError::UnknownExtension(_))
+        ));
+    }
+}
@@ -0,0 +1,323 @@
+// Runs every compiled in day of a year against its data/<year>/dayN/input.txt, timing each part
+// and checking the answer against answers.txt. Nothing here stops the run - a missing input, a
+// solver error or even a panic is recorded against the day and the next day carries on. Parts
+// run in parallel with --jobs (see pool), but the results are the same as a serial run.
+
+use std::fs;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::embedded;
+use crate::memory::{self, MemStats};
+use crate::pool;
+use crate::records::{self, Records};
+use crate::registry::{self, Solution};
+use crate::utils::panic_message;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Pass,
+    // The verified answer
+    Fail(String),
+    Unverified,
+    Error(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct PartRun {
+    pub part: u8,
+    pub answer: Option<String>,
+    pub runtime: Duration,
+    pub verdict: Verdict,
+    // Only measured with memory counting enabled, see memory
+    pub memory: Option<MemStats>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DayRun {
+    pub day: u8,
+    // Set when the day came from a plugin rather than being built in
+    pub plugin: Option<&'static str>,
+    pub input: PathBuf,
+    pub parts: Vec<PartRun>,
+    // Problems with the run that aren't a wrong answer, e.g. a missing input
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct YearRun {
+    pub year: u16,
+    pub days: Vec<DayRun>,
+    pub warnings: Vec<String>,
+}
+
+impl DayRun {
+    pub fn label(&self) -> String {
+        match self.plugin {
+            Some(plugin) => format!("Day {} ({} plugin)", self.day, plugin),
+            None => format!("Day {}", self.day),
+        }
+    }
+}
+
+impl YearRun {
+    pub fn parts(&self) -> impl Iterator<Item = (u8, &PartRun)> {
+        self.days
+            .iter()
+            .flat_map(|day| day.parts.iter().map(move |part| (day.day, part)))
+    }
+
+    pub fn count(&self, matches: impl Fn(&Verdict) -> bool) -> usize {
+        self.parts()
+            .filter(|(_, part)| matches(&part.verdict))
+            .count()
+    }
+
+    pub fn total_runtime(&self) -> Duration {
+        self.parts().map(|(_, part)| part.runtime).sum()
+    }
+}
+
+pub fn run_part(solution: Solution, part: u8, input: &str, expected: Option<&String>) -> PartRun {
+    let start = Instant::now();
+    let (result, memory) = memory::measure(|| {
+        panic::catch_unwind(AssertUnwindSafe(|| solution.solve(part, input)))
+            .unwrap_or_else(|payload| Err(format!("panicked: {}", panic_message(payload))))
+    });
+    let runtime = start.elapsed();
+
+    let verdict = match (&result, expected) {
+        (Err(e), _) => Verdict::Error(e.clone()),
+        (Ok(_), None) => Verdict::Unverified,
+        (Ok(answer), Some(expected)) if answer == expected => Verdict::Pass,
+        (Ok(_), Some(expected)) => Verdict::Fail(expected.clone()),
+    };
+    PartRun {
+        part,
+        answer: result.ok(),
+        runtime,
+        verdict,
+        memory,
+    }
+}
+
+pub fn run_day(solution: Solution, input: &Path, answers: &Records) -> DayRun {
+    run_days(
+        &[(solution, input.to_path_buf())],
+        &[1, 2],
+        Some(answers),
+        1,
+    )
+    .remove(0)
+}
+
+// Runs the given parts of every (solution, input) on up to `jobs` threads, one task per part. Days come
+// back in the order given whatever order their parts finish in. Without answers (e.g. a batch of
+// extra inputs) every part is unverified and that isn't worth a warning.
+pub fn run_days(
+    days: &[(Solution, PathBuf)],
+    parts: &[u8],
+    answers: Option<&Records>,
+    jobs: usize,
+) -> Vec<DayRun> {
+    let mut runs = Vec::new();
+    let mut texts = Vec::new();
+    for (solution, input) in days {
+        let mut run = DayRun {
+            day: solution.day,
+            plugin: solution.plugin(),
+            input: input.clone(),
+            parts: Vec::new(),
+            warnings: Vec::new(),
+        };
+        match embedded::read(input) {
+            Ok(text) => texts.push(Some(text)),
+            Err(e) => {
+                run.warnings
+                    .push(format!("Unable to read {}: {}", input.display(), e));
+                texts.push(None);
+            }
+        }
+        runs.push(run);
+    }
+
+    // (index into days, part) for every part a day provides, if it has an input. Plugin days can
+    // leave a part out
+    let tasks: Vec<(usize, u8)> = texts
+        .iter()
+        .enumerate()
+        .filter(|(_, text)| text.is_some())
+        .flat_map(|(i, _)| parts.iter().map(move |&part| (i, part)))
+        .filter(|&(i, part)| days[i].0.part(part).is_some())
+        .collect();
+    let part_runs = pool::map(jobs, &tasks, |&(i, part)| {
+        let solution = days[i].0;
+        let expected = answers.and_then(|answers| answers.get(&(solution.day, part)));
+        let text = texts[i].as_deref().unwrap_or_default();
+        run_part(solution, part, text, expected)
+    });
+
+    for (&(i, part), part_run) in tasks.iter().zip(part_runs) {
+        if let Some(answers) = answers {
+            if !answers.contains_key(&(runs[i].day, part)) {
+                runs[i]
+                    .warnings
+                    .push(format!("No verified answer for part {}", part));
+            }
+        }
+        runs[i].parts.push(part_run);
+    }
+    runs
+}
+
+pub fn run_year(data: &Path, year: u16) -> YearRun {
+    let mut warnings = Vec::new();
+    let answers_path = records::answers_path(data, year);
+    let answers = records::load(&answers_path).unwrap_or_else(|e| {
+        warnings.push(format!("Unable to load {}: {}", answers_path.display(), e));
+        Records::new()
+    });
+    if registry::days(year).is_empty() {
+        warnings.push(format!("No days compiled in for {}", year));
+    }
+
+    let days: Vec<(Solution, PathBuf)> = registry::solutions()
+        .into_iter()
+        .filter(|solution| solution.year == year)
+        .map(|solution| {
+            let input = data
+                .join(year.to_string())
+                .join(format!("day{}", solution.day))
+                .join("input.txt");
+            (solution, input)
+        })
+        .collect();
+    YearRun {
+        year,
+        days: run_days(&days, &[1, 2], Some(&answers), pool::jobs()),
+        warnings,
+    }
+}
+
+// Runs one day against every file in a directory, in file name order
+pub fn run_batch(solution: Solution, parts: &[u8], directory: &Path) -> io::Result<Vec<DayRun>> {
+    let mut inputs = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.is_file() {
+            inputs.push((solution, path));
+        }
+    }
+    inputs.sort_by(|a, b| a.1.cmp(&b.1));
+    Ok(run_days(&inputs, parts, None, pool::jobs()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::Solver;
+
+    #[test]
+    fn test_verdicts() {
+        let solution = Solution {
+            year: 2021,
+            day: 1,
+            part1: Solver::Builtin(|input| Ok(input.trim().to_string())),
+            part2: Solver::Builtin(|_| panic!("boom")),
+        };
+        let expected = String::from("42");
+        assert_eq!(
+            run_part(solution, 1, "42", Some(&expected)).verdict,
+            Verdict::Pass
+        );
+        assert_eq!(
+            run_part(solution, 1, "41", Some(&expected)).verdict,
+            Verdict::Fail(expected.clone())
+        );
+        assert_eq!(
+            run_part(solution, 1, "41", None).verdict,
+            Verdict::Unverified
+        );
+
+        let panicked = run_part(solution, 2, "", None);
+        assert_eq!(
+            panicked.verdict,
+            Verdict::Error(String::from("panicked: boom"))
+        );
+        assert!(panicked.answer.is_none());
+    }
+
+    #[test]
+    fn test_missing_input_is_a_warning() {
+        let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("data");
+        let solution = Solution {
+            year: 2021,
+            day: 25,
+            part1: Solver::Builtin(|_| Ok(String::new())),
+            part2: Solver::Builtin(|_| Ok(String::new())),
+        };
+        let run = run_day(
+            solution,
+            &data.join("2021/day25/input.txt"),
+            &Records::new(),
+        );
+        assert!(run.parts.is_empty());
+        assert_eq!(run.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_parts_a_day_leaves_out_are_not_run() {
+        let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("data");
+        let solution = Solution {
+            year: 2021,
+            day: 1,
+            part1: Solver::Builtin(|_| Ok(String::new())),
+            part2: Solver::Missing,
+        };
+        let runs = run_days(
+            &[(solution, data.join("2021/day1/example.txt"))],
+            &[1, 2],
+            None,
+            1,
+        );
+        let parts: Vec<u8> = runs[0].parts.iter().map(|part| part.part).collect();
+        assert_eq!(parts, [1]);
+    }
+
+    #[test]
+    fn test_parallel_days_match_serial() {
+        let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("data");
+        let solution = |day| Solution {
+            year: 2021,
+            day,
+            part1: Solver::Builtin(|input| Ok(input.lines().count().to_string())),
+            part2: Solver::Builtin(|input| Ok((input.lines().count() * 2).to_string())),
+        };
+        let days: Vec<(Solution, PathBuf)> = [1, 25, 2]
+            .iter()
+            .map(|&day| {
+                (
+                    solution(day),
+                    data.join(format!("2021/day{}/input.txt", day)),
+                )
+            })
+            .collect();
+        let summary = |runs: Vec<DayRun>| -> Vec<(u8, Vec<Option<String>>, usize)> {
+            runs.into_iter()
+                .map(|run| {
+                    let answers = run.parts.into_iter().map(|part| part.answer).collect();
+                    (run.day, answers, run.warnings.len())
+                })
+                .collect()
+        };
+        let serial = summary(run_days(&days, &[1, 2], None, 1));
+        assert_eq!(summary(run_days(&days, &[1, 2], None, 4)), serial);
+        assert_eq!(serial[1], (25, Vec::new(), 1));
+        assert_eq!(
+            serial[0].1,
+            vec![Some(String::from("2000")), Some(String::from("4000"))]
+        );
+    }
+}
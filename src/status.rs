@@ -0,0 +1,270 @@
+// Advent calendar view of a year's progress. Each of the 25 days shows whether its module is
+// compiled in, whether data/<year>/dayN has an input and an example, a star for each part the CLI
+// has recorded a run of with the answer in answers.txt (see --record) and that run's runtime.
+
+use std::path::Path;
+use std::time::Duration;
+
+use crate::records::{self, RecordError};
+use crate::registry;
+
+pub const DAYS: u8 = 25;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayStatus {
+    pub day: u8,
+    pub implemented: bool,
+    pub input: bool,
+    pub example: bool,
+    pub stars: [bool; 2],
+    // Slowest of the recorded parts
+    pub runtime: Option<Duration>,
+}
+
+pub fn collect(data: &Path, year: u16) -> Result<Vec<DayStatus>, RecordError> {
+    let implemented = registry::days(year);
+    let answers = records::load(&records::answers_path(data, year))?;
+    let runtimes = records::load_runtimes(&records::runtimes_path(data, year))?;
+    let directory = data.join(year.to_string());
+
+    Ok((1..=DAYS)
+        .map(|day| {
+            let day_directory = directory.join(format!("day{}", day));
+            DayStatus {
+                day,
+                implemented: implemented.contains(&day),
+                input: day_directory.join("input.txt").is_file(),
+                example: day_directory.join("example.txt").is_file(),
+                // Runtimes are only recorded for runs that matched the answer
+                stars: [1, 2].map(|part| {
+                    answers.contains_key(&(day, part)) && runtimes.contains_key(&(day, part))
+                }),
+                runtime: [1, 2]
+                    .iter()
+                    .filter_map(|&part| runtimes.get(&(day, part)).copied())
+                    .max(),
+            }
+        })
+        .collect())
+}
+
+const COLUMNS: usize = 5;
+const CELL_WIDTH: usize = 13;
+
+fn flag(present: bool, letter: char) -> char {
+    if present {
+        letter
+    } else {
+        '-'
+    }
+}
+
+// The three lines of text inside a day's cell
+fn cell(status: &DayStatus) -> [String; 3] {
+    let stars: String = status
+        .stars
+        .iter()
+        .map(|&star| if star { '*' } else { '.' })
+        .collect();
+    let files = format!(
+        "{} {} {}",
+        flag(status.implemented, 'M'),
+        flag(status.input, 'I'),
+        flag(status.example, 'E')
+    );
+    let runtime = status
+        .runtime
+        .map_or_else(|| String::from("-"), records::format_runtime);
+    [format!("Day {:>2}  {}", status.day, stars), files, runtime]
+}
+
+pub fn render(year: u16, days: &[DayStatus]) -> String {
+    let border = format!(
+        "+{}\n",
+        format!("{}+", "-".repeat(CELL_WIDTH)).repeat(COLUMNS)
+    );
+    let mut output = format!("Advent of Code {}\n", year);
+    output.push_str(&border);
+    for row in days.chunks(COLUMNS) {
+        let cells: Vec<[String; 3]> = row.iter().map(cell).collect();
+        for line in 0..3 {
+            output.push('|');
+            for column in 0..COLUMNS {
+                let text = cells.get(column).map_or("", |cell| cell[line].as_str());
+                output.push_str(&format!(" {:<width$}|", text, width = CELL_WIDTH - 1));
+            }
+            output.push('\n');
+        }
+        output.push_str(&border);
+    }
+
+    let stars: usize = days
+        .iter()
+        .map(|day| day.stars.iter().filter(|&&star| star).count())
+        .sum();
+    output.push_str(&format!(
+        "{} stars, {} of {} days implemented\n",
+        stars,
+        days.iter().filter(|day| day.implemented).count(),
+        days.len()
+    ));
+    output.push_str(
+        "M module compiled in, I input, E example, * answer verified by a recorded run\n",
+    );
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_from_data_directory() {
+        let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("data");
+        let days = collect(&data, 2021).unwrap();
+        assert_eq!(days.len(), 25);
+        assert!(days[0].input && days[0].example);
+        assert_eq!(days[0].implemented, cfg!(feature = "day1"));
+        assert!(!days[24].input && !days[24].implemented);
+        assert_eq!(days[24].stars, [false, false]);
+    }
+
+    #[test]
+    fn test_stars_need_a_recorded_run_and_an_answer() {
+        let data = std::env::temp_dir().join(format!("aoc_status_{}", std::process::id()));
+        let year = data.join("2021");
+        std::fs::create_dir_all(&year).unwrap();
+        // Day 2 part 2 has an answer but no recorded run, day 3 part 1 a run but no answer
+        std::fs::write(year.join("answers.txt"), "2 1 10\n2 2 20\n").unwrap();
+        std::fs::write(year.join("runtimes.txt"), "2 1 150\n3 1 900\n").unwrap();
+        let days = collect(&data, 2021).unwrap();
+        std::fs::remove_dir_all(&data).unwrap();
+
+        assert_eq!(days[1].stars, [true, false]);
+        assert_eq!(days[2].stars, [false, false]);
+        assert_eq!(days[1].runtime, Some(Duration::from_micros(150)));
+        assert_eq!(days[0].stars, [false, false]);
+    }
+
+    fn data(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("aoc_status_{}_{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_runtime_is_the_slowest_part() {
+        let data = data("slowest");
+        let year = data.join("2021");
+        std::fs::create_dir_all(&year).unwrap();
+        std::fs::write(year.join("runtimes.txt"), "5 1 900\n5 2 300\n").unwrap();
+        let days = collect(&data, 2021).unwrap();
+        std::fs::remove_dir_all(&data).unwrap();
+        assert_eq!(days[4].runtime, Some(Duration::from_micros(900)));
+        assert_eq!(days[5].runtime, None);
+    }
+
+    #[test]
+    fn test_a_failed_run_takes_the_star_away() {
+        let data = data("regressed");
+        let year = data.join("2021");
+        std::fs::create_dir_all(&year).unwrap();
+        std::fs::write(year.join("answers.txt"), "4 1 10\n").unwrap();
+        let runtimes = records::runtimes_path(&data, 2021);
+        records::record_run(&runtimes, 4, 1, Duration::from_micros(200), true).unwrap();
+        let passed = collect(&data, 2021).unwrap();
+        records::record_run(&runtimes, 4, 1, Duration::from_micros(100), false).unwrap();
+        let failed = collect(&data, 2021).unwrap();
+        std::fs::remove_dir_all(&data).unwrap();
+
+        assert_eq!(passed[3].stars, [true, false]);
+        assert_eq!(failed[3].stars, [false, false]);
+        assert_eq!(failed[3].runtime, None);
+    }
+
+    #[test]
+    fn test_collect_without_a_data_directory() {
+        let days = collect(&data("missing"), 2021).unwrap();
+        assert_eq!(days.len(), 25);
+        assert!(days
+            .iter()
+            .all(|day| !day.input && !day.example && day.stars == [false, false]));
+    }
+
+    #[test]
+    fn test_collect_reports_a_bad_runtimes_file() {
+        let data = data("bad");
+        let year = data.join("2021");
+        std::fs::create_dir_all(&year).unwrap();
+        std::fs::write(year.join("runtimes.txt"), "1 1 slow\n").unwrap();
+        let collected = collect(&data, 2021);
+        std::fs::remove_dir_all(&data).unwrap();
+        assert!(matches!(
+            collected,
+            Err(RecordError::InvalidRuntime {
+                day: 1,
+                part: 1,
+                ..
+            })
+        ));
+    }
+
+    fn empty_days(count: u8) -> Vec<DayStatus> {
+        (1..=count)
+            .map(|day| DayStatus {
+                day,
+                implemented: false,
+                input: false,
+                example: false,
+                stars: [false, false],
+                runtime: None,
+            })
+            .collect()
+    }
+
+    fn first_day_started() -> Vec<DayStatus> {
+        let mut days = empty_days(DAYS);
+        days[0] = DayStatus {
+            implemented: true,
+            input: true,
+            stars: [true, false],
+            runtime: Some(Duration::from_micros(1_500)),
+            ..days[0].clone()
+        };
+        days
+    }
+
+    #[test]
+    fn test_render_grid() {
+        let output = render(2021, &empty_days(DAYS));
+        let lines: Vec<&str> = output.lines().collect();
+        // Title, then five rows of three lines with borders, then the totals and legend
+        assert_eq!(lines.len(), 1 + 1 + 5 * 4 + 2);
+        assert_eq!(lines[0], "Advent of Code 2021");
+        assert_eq!(lines[1], format!("+{}", "-------------+".repeat(5)));
+        assert!(lines.iter().skip(1).take(21).all(|line| line.len() == 71));
+    }
+
+    #[test]
+    fn test_render_cell() {
+        let output = render(2021, &first_day_started());
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[2].starts_with("| Day  1  *.  | Day  2  ..  |"));
+        assert!(lines[3].starts_with("| M I -       |"));
+        assert!(lines[4].starts_with("| 1.5ms       | -           |"));
+    }
+
+    #[test]
+    fn test_render_totals() {
+        let output = render(2021, &first_day_started());
+        assert!(output.contains("\n1 stars, 1 of 25 days implemented\n"));
+        assert!(output.ends_with("* answer verified by a recorded run\n"));
+    }
+
+    #[test]
+    fn test_render_part_of_a_row() {
+        let output = render(2021, &empty_days(7));
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 1 + 1 + 2 * 4 + 2);
+        assert!(lines[6].ends_with("| Day  7  ..  |             |             |             |"));
+        assert!(output.contains("0 stars, 0 of 7 days implemented"));
+    }
+}
@@ -0,0 +1,11 @@
+// Test support shared by the unit tests, the differential and fuzz harnesses and their runners.
+// Everything in here favours being obviously correct over being fast.
+
+pub mod differential;
+pub mod fuzz;
+pub mod generate;
+pub mod prop;
+pub mod reference;
+mod rng;
+
+pub use rng::Rng;
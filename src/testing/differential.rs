@@ -0,0 +1,179 @@
+// Differential testing - runs the real solutions and the reference solutions against the same
+// randomly generated inputs and reports every input where they disagree. A panic in the real
+// solution is reported as a mismatch rather than aborting the run. The generators and reference
+// solutions only cover 2021.
+
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+use super::{generate, reference, Rng};
+use crate::registry;
+use crate::utils::panic_message;
+use crate::y2021::YEAR;
+
+#[derive(Debug, Clone)]
+pub struct Mismatch {
+    pub day: u8,
+    pub part: u8,
+    // Seed that regenerates `input` with `generate::for_day`
+    pub seed: u64,
+    pub input: String,
+    pub fast: String,
+    pub reference: String,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Day {} part {} (seed {}): solution gave {}, reference gave {}",
+            self.day, self.part, self.seed, self.fast, self.reference
+        )?;
+        write!(f, "{}", self.input)
+    }
+}
+
+// Answers are compared as Options so that "no answer" (e.g. no bingo board ever wins) and
+// parse errors line up between the two sides
+fn fast_answer(day: u8, part: u8, input: &str) -> String {
+    let answer = registry::find(YEAR, day).and_then(|solution| solution.solve(part, input).ok());
+    format!("{:?}", answer)
+}
+
+fn reference_answer(day: u8, part: u8, input: &str) -> String {
+    use reference::*;
+
+    let answer = match (day, part) {
+        (1, 1) => day1::part1(input).to_string(),
+        (1, 2) => day1::part2(input).to_string(),
+        (2, 1) => day2::part1(input).to_string(),
+        (2, 2) => day2::part2(input).to_string(),
+        (3, 1) => day3::part1(input).to_string(),
+        (3, 2) => day3::part2(input).to_string(),
+        (4, 1) => return format!("{:?}", day4::part1(input).map(|a| a.to_string())),
+        (4, 2) => return format!("{:?}", day4::part2(input).map(|a| a.to_string())),
+        (5, 1) => day5::part1(input).to_string(),
+        (5, 2) => day5::part2(input).to_string(),
+        (6, 1) => day6::part1(input).to_string(),
+        (6, 2) => day6::part2(input).to_string(),
+        (7, 1) => day7::part1(input).to_string(),
+        (7, 2) => day7::part2(input).to_string(),
+        (8, 1) => day8::part1(input).to_string(),
+        (8, 2) => day8::part2(input).to_string(),
+        _ => unreachable!("No reference for day {} part {}", day, part),
+    };
+    format!("{:?}", Some(answer))
+}
+
+// Compares both parts of a day on a single input, returning (part, fast, reference) for each
+// part that disagrees
+pub fn compare(day: u8, input: &str) -> Vec<(u8, String, String)> {
+    let mut differences = Vec::new();
+    for part in 1..=2 {
+        let fast = panic::catch_unwind(AssertUnwindSafe(|| fast_answer(day, part, input)))
+            .unwrap_or_else(|payload| format!("panic: {}", panic_message(payload)));
+        let reference = reference_answer(day, part, input);
+        if fast != reference {
+            differences.push((part, fast, reference));
+        }
+    }
+    differences
+}
+
+// Runs `iterations` random inputs for a day. Case `i` is generated from `seed + i` so any
+// mismatch can be replayed on its own.
+pub fn run(day: u8, iterations: u64, seed: u64) -> Vec<Mismatch> {
+    let mut mismatches = Vec::new();
+    for i in 0..iterations {
+        let case_seed = seed.wrapping_add(i);
+        let input = match generate::for_day(day, &mut Rng::new(case_seed)) {
+            Some(input) => input,
+            None => break,
+        };
+        for (part, fast, reference) in compare(day, &input) {
+            mismatches.push(Mismatch {
+                day,
+                part,
+                seed: case_seed,
+                input: input.clone(),
+                fast,
+                reference,
+            });
+        }
+    }
+    mismatches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(dead_code)]
+    fn assert_no_mismatches(day: u8) {
+        let mismatches = run(day, 200, 2021);
+        assert!(
+            mismatches.is_empty(),
+            "{} mismatches, first:\n{}",
+            mismatches.len(),
+            mismatches[0]
+        );
+    }
+
+    #[cfg(feature = "day1")]
+    #[test]
+    fn test_day1_matches_reference() {
+        assert_no_mismatches(1);
+    }
+
+    #[cfg(feature = "day2")]
+    #[test]
+    fn test_day2_matches_reference() {
+        assert_no_mismatches(2);
+    }
+
+    #[cfg(feature = "day3")]
+    #[test]
+    fn test_day3_matches_reference() {
+        assert_no_mismatches(3);
+    }
+
+    #[cfg(feature = "day4")]
+    #[test]
+    fn test_day4_matches_reference() {
+        assert_no_mismatches(4);
+    }
+
+    #[cfg(feature = "day5")]
+    #[test]
+    fn test_day5_matches_reference() {
+        assert_no_mismatches(5);
+    }
+
+    #[cfg(feature = "day6")]
+    #[test]
+    fn test_day6_matches_reference() {
+        assert_no_mismatches(6);
+    }
+
+    #[cfg(feature = "day7")]
+    #[test]
+    fn test_day7_matches_reference() {
+        assert_no_mismatches(7);
+    }
+
+    #[cfg(feature = "day8")]
+    #[test]
+    fn test_day8_matches_reference() {
+        // Every case tries up to 5040 wirings per line, so keep this one smaller
+        let mismatches = run(8, 20, 2021);
+        assert!(mismatches.is_empty(), "first:\n{}", mismatches[0]);
+    }
+
+    #[cfg(feature = "day3")]
+    #[test]
+    fn test_day3_duplicate_rows() {
+        // An input the real day 3 solution used to panic on - duplicate rows in part 2
+        let differences = compare(3, "101\n101\n");
+        assert!(differences.is_empty(), "{:?}", differences);
+    }
+}
@@ -0,0 +1,171 @@
+// Fuzzing entry points. Each target parses the bytes as a puzzle input and runs both parts
+// through the registry's fallible solvers - a target must never panic, whatever the bytes.
+//
+// The targets are driven by cargo-fuzz (see fuzz/) or, where libFuzzer isn't available, by the
+// plain std driver below which feeds random bytes and mutated puzzle inputs. Like the generators,
+// the targets are 2021 days.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use super::{generate, Rng};
+use crate::registry::{self, Solution};
+use crate::utils::panic_message;
+use crate::y2021::YEAR;
+
+// Fuzz target for any compiled in day - cargo-fuzz's fuzz_targets/dayN.rs call this
+pub fn run(day: u8, data: &[u8]) {
+    if let Some(solution) = registry::find(YEAR, day) {
+        solve(solution, data);
+    }
+}
+
+fn solve(solution: Solution, data: &[u8]) {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = solution.solve(1, input);
+        let _ = solution.solve(2, input);
+    }
+}
+
+// Bytes that are meaningful to at least one of the puzzle formats
+const INTERESTING: &[u8] = b"0123456789abcdefg ,|->\n\r";
+
+fn random_byte(rng: &mut Rng) -> u8 {
+    if rng.chance(3, 4) {
+        INTERESTING[rng.index(INTERESTING.len())]
+    } else {
+        rng.below(256) as u8
+    }
+}
+
+fn mutate(rng: &mut Rng, data: &mut Vec<u8>) {
+    for _ in 0..rng.between(1, 4) {
+        match rng.below(5) {
+            0 if !data.is_empty() => {
+                let i = rng.index(data.len());
+                data[i] = random_byte(rng);
+            }
+            1 => {
+                let i = rng.index(data.len() + 1);
+                data.insert(i, random_byte(rng));
+            }
+            2 if !data.is_empty() => {
+                data.remove(rng.index(data.len()));
+            }
+            3 => {
+                data.truncate(rng.index(data.len() + 1));
+            }
+            4 if !data.is_empty() => {
+                let start = rng.index(data.len());
+                let end = start + rng.index(data.len() - start + 1);
+                let chunk = data[start..end].to_vec();
+                let at = rng.index(data.len() + 1);
+                data.splice(at..at, chunk);
+            }
+            _ => {}
+        }
+    }
+}
+
+// Builds the input for a single fuzz case - either random bytes or a mutated puzzle input
+pub fn case(day: u8, seed: u64) -> Vec<u8> {
+    let mut rng = Rng::new(seed);
+    if rng.chance(1, 4) {
+        let len = rng.index(64);
+        (0..len).map(|_| random_byte(&mut rng)).collect()
+    } else {
+        let mut data = generate::for_day(day, &mut rng)
+            .unwrap_or_default()
+            .into_bytes();
+        mutate(&mut rng, &mut data);
+        data
+    }
+}
+
+// Runs a target on some bytes, returning the panic message if it crashed
+pub fn crashes(solution: Solution, data: &[u8]) -> Option<String> {
+    panic::catch_unwind(AssertUnwindSafe(|| solve(solution, data)))
+        .err()
+        .map(panic_message)
+}
+
+#[derive(Debug, Clone)]
+pub struct Crash {
+    pub day: u8,
+    pub seed: u64,
+    pub input: Vec<u8>,
+    pub message: String,
+}
+
+// Plain std fuzz driver - case `i` is generated from `seed + i`
+pub fn drive(day: u8, iterations: u64, seed: u64) -> Vec<Crash> {
+    let solution = match registry::find(YEAR, day) {
+        Some(solution) => solution,
+        None => return Vec::new(),
+    };
+    let mut found = Vec::new();
+    for i in 0..iterations {
+        let seed = seed.wrapping_add(i);
+        let input = case(day, seed);
+        if let Some(message) = crashes(solution, &input) {
+            found.push(Crash {
+                day,
+                seed,
+                input,
+                message,
+            });
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    // Replays every input in fuzz/regressions/dayN - all of these crashed a parser at some point
+    #[test]
+    fn test_regression_corpus() {
+        let corpus = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fuzz/regressions");
+        let mut replayed = 0;
+        for solution in registry::solutions()
+            .into_iter()
+            .filter(|solution| solution.year == YEAR)
+        {
+            let directory = corpus.join(format!("day{}", solution.day));
+            let entries = match std::fs::read_dir(&directory) {
+                Ok(entries) => entries,
+                Err(_) => continue,
+            };
+            for entry in entries {
+                let path = entry.unwrap().path();
+                let data = std::fs::read(&path).unwrap();
+                if let Some(message) = crashes(solution, &data) {
+                    panic!("{} still crashes: {}", path.display(), message);
+                }
+                replayed += 1;
+            }
+        }
+        // Only a build without any days has nothing to replay
+        assert!(
+            replayed > 0 || registry::days(YEAR).is_empty(),
+            "No regression inputs found in {}",
+            corpus.display()
+        );
+    }
+
+    #[test]
+    fn test_driver_finds_no_crashes() {
+        for day in registry::days(YEAR) {
+            let crashes = drive(day, 300, 2021);
+            assert!(
+                crashes.is_empty(),
+                "Day {} crashed on seed {}: {} ({:?})",
+                day,
+                crashes[0].seed,
+                crashes[0].message,
+                String::from_utf8_lossy(&crashes[0].input)
+            );
+        }
+    }
+}
@@ -0,0 +1,178 @@
+// Random puzzle input generators. Each generator only produces inputs that are valid for the
+// puzzle (e.g. day 5 lines are horizontal, vertical or 45 degree diagonals) but deliberately
+// leans towards small values so that ties, duplicates and edge cases show up often.
+
+use super::Rng;
+
+// Segments lit for each digit on an unscrambled seven segment display (index = digit)
+pub const DIGIT_SEGMENTS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+pub const SEGMENTS: [char; 7] = ['a', 'b', 'c', 'd', 'e', 'f', 'g'];
+
+pub fn day1(rng: &mut Rng) -> String {
+    let readings = rng.between(0, 30);
+    let mut depth = rng.between(100, 200);
+    let mut output = String::new();
+    for _ in 0..readings {
+        // Small steps in both directions so plateaus and drops are common
+        depth = (depth + rng.between(0, 10)).saturating_sub(rng.between(0, 10));
+        output.push_str(&format!("{}\n", depth));
+    }
+    output
+}
+
+pub fn day2(rng: &mut Rng) -> String {
+    let commands = rng.between(0, 20);
+    let mut output = String::new();
+    for _ in 0..commands {
+        let direction = ["forward", "down", "up"][rng.index(3)];
+        output.push_str(&format!("{} {}\n", direction, rng.between(1, 9)));
+    }
+    output
+}
+
+pub fn day3(rng: &mut Rng) -> String {
+    let width = rng.between(1, 12);
+    let rows = rng.between(1, 30);
+    let mut output = String::new();
+    for _ in 0..rows {
+        for _ in 0..width {
+            output.push(if rng.chance(1, 2) { '1' } else { '0' });
+        }
+        output.push('\n');
+    }
+    output
+}
+
+pub fn day4(rng: &mut Rng) -> String {
+    let mut numbers: Vec<u64> = (0..100).collect();
+    rng.shuffle(&mut numbers);
+    let draws = numbers
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<String>>()
+        .join(",");
+
+    let mut output = format!("{}\n", draws);
+    for _ in 0..rng.between(1, 6) {
+        let mut board: Vec<u64> = (0..100).collect();
+        rng.shuffle(&mut board);
+        output.push('\n');
+        for row in board[..25].chunks(5) {
+            let row = row
+                .iter()
+                .map(|n| format!("{:>2}", n))
+                .collect::<Vec<String>>()
+                .join(" ");
+            output.push_str(&format!("{}\n", row));
+        }
+    }
+    output
+}
+
+pub fn day5(rng: &mut Rng) -> String {
+    // (dx, dy) for the eight directions a line can point in
+    const DIRECTIONS: [(i64, i64); 8] = [
+        (1, 0),
+        (-1, 0),
+        (0, 1),
+        (0, -1),
+        (1, 1),
+        (1, -1),
+        (-1, 1),
+        (-1, -1),
+    ];
+    let mut output = String::new();
+    for _ in 0..rng.between(1, 15) {
+        let (dx, dy) = DIRECTIONS[rng.index(DIRECTIONS.len())];
+        let length = rng.between(0, 10) as i64;
+        let x1 = rng.between(10, 20) as i64;
+        let y1 = rng.between(10, 20) as i64;
+        let (x2, y2) = (x1 + dx * length, y1 + dy * length);
+        output.push_str(&format!("{},{} -> {},{}\n", x1, y1, x2, y2));
+    }
+    output
+}
+
+pub fn day6(rng: &mut Rng) -> String {
+    (0..rng.between(1, 10))
+        .map(|_| rng.between(0, 8).to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+pub fn day7(rng: &mut Rng) -> String {
+    let spread = rng.between(0, 50);
+    (0..rng.between(1, 20))
+        .map(|_| rng.between(0, spread).to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+pub fn day8(rng: &mut Rng) -> String {
+    let mut output = String::new();
+    for _ in 0..rng.between(1, 5) {
+        let mut wiring = SEGMENTS;
+        rng.shuffle(&mut wiring);
+        let digits = [0; 4].map(|_| rng.below(10) as u8);
+        output.push_str(&day8_entry(rng, &wiring, digits));
+        output.push('\n');
+    }
+    output
+}
+
+// Scrambles a single display: `wiring[i]` is the wire that drives segment `SEGMENTS[i]`.
+// The ten unique patterns are shuffled and every pattern has its letters shuffled.
+pub fn day8_entry(rng: &mut Rng, wiring: &[char; 7], digits: [u8; 4]) -> String {
+    let mut encode = |digit: usize| {
+        let mut pattern: Vec<char> = DIGIT_SEGMENTS[digit]
+            .chars()
+            .map(|segment| wiring[(segment as u8 - b'a') as usize])
+            .collect();
+        rng.shuffle(&mut pattern);
+        pattern.into_iter().collect::<String>()
+    };
+
+    let mut order: Vec<usize> = (0..10).collect();
+    let mut source = Vec::new();
+    for digit in 0..10 {
+        source.push(encode(digit));
+    }
+    let output: Vec<String> = digits.iter().map(|&d| encode(d as usize)).collect();
+    rng.shuffle(&mut order);
+    let source: Vec<&str> = order.iter().map(|&i| source[i].as_str()).collect();
+
+    format!("{} | {}", source.join(" "), output.join(" "))
+}
+
+// Generates an input for the given day, None if the day has no generator
+pub fn for_day(day: u8, rng: &mut Rng) -> Option<String> {
+    match day {
+        1 => Some(day1(rng)),
+        2 => Some(day2(rng)),
+        3 => Some(day3(rng)),
+        4 => Some(day4(rng)),
+        5 => Some(day5(rng)),
+        6 => Some(day6(rng)),
+        7 => Some(day7(rng)),
+        8 => Some(day8(rng)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day8_entry_unscrambled() {
+        let mut rng = Rng::new(1);
+        let entry = day8_entry(&mut rng, &SEGMENTS, [1, 2, 3, 4]);
+        let (source, output) = entry.split_once(" | ").unwrap();
+        assert_eq!(source.split(' ').count(), 10);
+        let lengths: Vec<usize> = output.split(' ').map(|p| p.len()).collect();
+        assert_eq!(lengths, vec![2, 5, 5, 4]);
+    }
+}
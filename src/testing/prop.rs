@@ -0,0 +1,348 @@
+// Minimal property based testing. A `Strategy` knows how to generate random values and how to
+// shrink a failing value into simpler candidates. `check` runs a property against many
+// generated values and, on failure, shrinks the counterexample before panicking with it.
+//
+// Every case is generated from its own seed which is printed on failure. Runs start from the same
+// seed every time so a failure in CI reproduces locally. Set AOC_PROP_SEED to the printed seed to
+// replay the failing case first, or to any other seed to explore, and AOC_PROP_CASES to change
+// the number of cases.
+
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+use std::panic::{self, AssertUnwindSafe};
+
+use super::Rng;
+
+pub trait Strategy {
+    type Value: Clone + Debug;
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value;
+
+    // Simpler candidates for a failing value, most aggressive first
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value>;
+}
+
+// Integers in an inclusive range, shrinking towards the low end
+#[derive(Debug, Clone)]
+pub struct Ints {
+    low: u64,
+    high: u64,
+}
+
+pub fn ints(range: RangeInclusive<u64>) -> Ints {
+    Ints {
+        low: *range.start(),
+        high: *range.end(),
+    }
+}
+
+impl Strategy for Ints {
+    type Value = u64;
+
+    fn generate(&self, rng: &mut Rng) -> u64 {
+        if self.low == 0 && self.high == u64::MAX {
+            rng.next_u64()
+        } else {
+            rng.between(self.low, self.high)
+        }
+    }
+
+    fn shrink(&self, value: &u64) -> Vec<u64> {
+        let value = *value;
+        let mut candidates = Vec::new();
+        if value > self.low {
+            candidates.push(self.low);
+            let halfway = self.low + (value - self.low) / 2;
+            if halfway != self.low {
+                candidates.push(halfway);
+            }
+            if value - 1 != halfway {
+                candidates.push(value - 1);
+            }
+        }
+        candidates
+    }
+}
+
+// Vectors with a length in the given range, shrinking by dropping elements and then by
+// shrinking the elements themselves
+#[derive(Debug, Clone)]
+pub struct Vecs<S> {
+    element: S,
+    min_len: usize,
+    max_len: usize,
+}
+
+pub fn vecs<S: Strategy>(element: S, len: RangeInclusive<usize>) -> Vecs<S> {
+    Vecs {
+        element,
+        min_len: *len.start(),
+        max_len: *len.end(),
+    }
+}
+
+impl<S: Strategy> Strategy for Vecs<S> {
+    type Value = Vec<S::Value>;
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value {
+        let len = rng.between(self.min_len as u64, self.max_len as u64) as usize;
+        (0..len).map(|_| self.element.generate(rng)).collect()
+    }
+
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+        let mut candidates = Vec::new();
+        let len = value.len();
+
+        // Drop the back half, then the front half, then single elements
+        let half = len / 2;
+        if half > 0 && len - half >= self.min_len {
+            candidates.push(value[..len - half].to_vec());
+            candidates.push(value[half..].to_vec());
+        }
+        if len > self.min_len {
+            for i in 0..len {
+                let mut smaller = value.clone();
+                smaller.remove(i);
+                candidates.push(smaller);
+            }
+        }
+
+        for (i, element) in value.iter().enumerate() {
+            for simpler in self.element.shrink(element) {
+                let mut candidate = value.clone();
+                candidate[i] = simpler;
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
+
+// Permutations of 0..len, shrinking towards the identity permutation
+#[derive(Debug, Clone)]
+pub struct Permutations {
+    len: usize,
+}
+
+pub fn permutations(len: usize) -> Permutations {
+    Permutations { len }
+}
+
+impl Strategy for Permutations {
+    type Value = Vec<usize>;
+
+    fn generate(&self, rng: &mut Rng) -> Vec<usize> {
+        let mut permutation: Vec<usize> = (0..self.len).collect();
+        rng.shuffle(&mut permutation);
+        permutation
+    }
+
+    fn shrink(&self, value: &Vec<usize>) -> Vec<Vec<usize>> {
+        // Put the first misplaced element back where it belongs
+        match (0..value.len()).find(|&i| value[i] != i) {
+            Some(i) => {
+                let j = value.iter().position(|&v| v == i).unwrap();
+                let mut candidate = value.clone();
+                candidate.swap(i, j);
+                vec![candidate]
+            }
+            None => Vec::new(),
+        }
+    }
+}
+
+macro_rules! tuple_strategy {
+    ($($name:ident $index:tt),+) => {
+        impl<$($name: Strategy),+> Strategy for ($($name,)+) {
+            type Value = ($($name::Value,)+);
+
+            fn generate(&self, rng: &mut Rng) -> Self::Value {
+                ($(self.$index.generate(rng),)+)
+            }
+
+            fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+                let mut candidates = Vec::new();
+                $(
+                    for simpler in self.$index.shrink(&value.$index) {
+                        let mut candidate = value.clone();
+                        candidate.$index = simpler;
+                        candidates.push(candidate);
+                    }
+                )+
+                candidates
+            }
+        }
+    };
+}
+
+tuple_strategy!(A 0, B 1);
+tuple_strategy!(A 0, B 1, C 2);
+tuple_strategy!(A 0, B 1, C 2, D 3);
+
+pub const DEFAULT_SEED: u64 = 2021;
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub cases: u64,
+    pub seed: u64,
+    pub max_shrink_steps: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let seed = std::env::var("AOC_PROP_SEED")
+            .ok()
+            .and_then(|seed| seed.parse().ok())
+            .unwrap_or(DEFAULT_SEED);
+        let cases = std::env::var("AOC_PROP_CASES")
+            .ok()
+            .and_then(|cases| cases.parse().ok())
+            .unwrap_or(100);
+        Self {
+            cases,
+            seed,
+            max_shrink_steps: 1000,
+        }
+    }
+}
+
+// A failing property, after shrinking
+#[derive(Debug, Clone)]
+pub struct Failure<T> {
+    pub seed: u64,
+    pub original: T,
+    pub minimal: T,
+    pub shrink_steps: usize,
+}
+
+fn holds<T>(property: &impl Fn(&T) -> bool, value: &T) -> bool {
+    panic::catch_unwind(AssertUnwindSafe(|| property(value))).unwrap_or(false)
+}
+
+// Runs the property and returns the shrunk counterexample instead of panicking
+pub fn find_failure<S: Strategy>(
+    config: &Config,
+    strategy: &S,
+    property: impl Fn(&S::Value) -> bool,
+) -> Option<Failure<S::Value>> {
+    for case in 0..config.cases {
+        let seed = config.seed.wrapping_add(case);
+        let original = strategy.generate(&mut Rng::new(seed));
+        if holds(&property, &original) {
+            continue;
+        }
+
+        // Greedily take the first simpler candidate that still fails until none do
+        let mut minimal = original.clone();
+        let mut shrink_steps = 0;
+        while shrink_steps < config.max_shrink_steps {
+            match strategy
+                .shrink(&minimal)
+                .into_iter()
+                .find(|candidate| !holds(&property, candidate))
+            {
+                Some(simpler) => {
+                    minimal = simpler;
+                    shrink_steps += 1;
+                }
+                None => break,
+            }
+        }
+
+        return Some(Failure {
+            seed,
+            original,
+            minimal,
+            shrink_steps,
+        });
+    }
+    None
+}
+
+pub fn check_with<S: Strategy>(
+    config: &Config,
+    strategy: &S,
+    property: impl Fn(&S::Value) -> bool,
+) {
+    if let Some(failure) = find_failure(config, strategy, property) {
+        panic!(
+            "Property failed (replay with AOC_PROP_SEED={})\n\
+             minimal input after {} shrinks: {:?}\n\
+             original input: {:?}",
+            failure.seed, failure.shrink_steps, failure.minimal, failure.original
+        );
+    }
+}
+
+pub fn check<S: Strategy>(strategy: &S, property: impl Fn(&S::Value) -> bool) {
+    check_with(&Config::default(), strategy, property)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Config {
+        Config {
+            cases: 100,
+            seed: 1,
+            max_shrink_steps: 1000,
+        }
+    }
+
+    #[test]
+    fn test_shrinks_int_to_boundary() {
+        let failure = find_failure(&config(), &ints(0..=1000), |&n| n < 50).unwrap();
+        assert_eq!(failure.minimal, 50);
+    }
+
+    #[test]
+    fn test_shrinks_vec_to_single_element() {
+        let strategy = vecs(ints(0..=100), 0..=20);
+        let failure = find_failure(&config(), &strategy, |v| v.iter().all(|&n| n < 10)).unwrap();
+        assert_eq!(failure.minimal, vec![10]);
+    }
+
+    #[test]
+    fn test_shrinks_permutation_to_identity() {
+        let failure = find_failure(&config(), &permutations(5), |p| p.len() != 5).unwrap();
+        assert_eq!(failure.minimal, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_seed_replays_failure() {
+        let failure = find_failure(&config(), &ints(0..=1000), |&n| n % 7 != 0).unwrap();
+        let replay = Config {
+            cases: 1,
+            seed: failure.seed,
+            ..config()
+        };
+        let replayed = find_failure(&replay, &ints(0..=1000), |&n| n % 7 != 0).unwrap();
+        assert_eq!(replayed.original, failure.original);
+    }
+
+    #[test]
+    fn test_default_seed_is_fixed() {
+        // The environment can override it, as the replay instructions say
+        let expected = std::env::var("AOC_PROP_SEED")
+            .ok()
+            .and_then(|seed| seed.parse().ok())
+            .unwrap_or(DEFAULT_SEED);
+        assert_eq!(Config::default().seed, expected);
+    }
+
+    #[test]
+    fn test_failure_message_names_the_seed() {
+        let failing = panic::catch_unwind(|| {
+            check_with(&config(), &ints(0..=1000), |&n| n < 50);
+        });
+        let message = crate::utils::panic_message(failing.unwrap_err());
+        assert!(message.starts_with("Property failed (replay with AOC_PROP_SEED="));
+        assert!(message.contains("minimal input after "));
+    }
+
+    #[test]
+    fn test_passing_property() {
+        check(&(ints(0..=10), ints(0..=10)), |&(a, b)| a + b <= 20);
+    }
+}
@@ -0,0 +1,451 @@
+// Slow but obviously correct reference solutions for each day. These follow the puzzle text as
+// literally as possible and are only used to cross check the real solutions - they make no
+// attempt at handling bad input and will panic on it.
+
+fn numbers(input: &str, separator: char) -> Vec<i64> {
+    input
+        .split(separator)
+        .map(|n| n.trim())
+        .filter(|n| !n.is_empty())
+        .map(|n| n.parse::<i64>().unwrap())
+        .collect()
+}
+
+pub mod day1 {
+    use super::numbers;
+
+    pub fn part1(input: &str) -> u64 {
+        let depths = numbers(input, '\n');
+        let mut increases = 0;
+        for i in 1..depths.len() {
+            if depths[i] > depths[i - 1] {
+                increases += 1;
+            }
+        }
+        increases
+    }
+
+    pub fn part2(input: &str) -> u64 {
+        let depths = numbers(input, '\n');
+        let mut increases = 0;
+        for i in 3..depths.len() {
+            let previous = depths[i - 3] + depths[i - 2] + depths[i - 1];
+            let current = depths[i - 2] + depths[i - 1] + depths[i];
+            if current > previous {
+                increases += 1;
+            }
+        }
+        increases
+    }
+}
+
+pub mod day2 {
+    fn commands(input: &str) -> Vec<(&str, i64)> {
+        input
+            .lines()
+            .map(|line| {
+                let (direction, distance) = line.split_once(' ').unwrap();
+                (direction, distance.parse().unwrap())
+            })
+            .collect()
+    }
+
+    pub fn part1(input: &str) -> i64 {
+        let (mut horizontal, mut depth) = (0, 0);
+        for (direction, distance) in commands(input) {
+            match direction {
+                "forward" => horizontal += distance,
+                "down" => depth += distance,
+                "up" => depth -= distance,
+                _ => panic!("Unknown direction {}", direction),
+            }
+        }
+        horizontal * depth
+    }
+
+    pub fn part2(input: &str) -> i64 {
+        let (mut horizontal, mut depth, mut aim) = (0, 0, 0);
+        for (direction, distance) in commands(input) {
+            match direction {
+                "forward" => {
+                    horizontal += distance;
+                    depth += aim * distance;
+                }
+                "down" => aim += distance,
+                "up" => aim -= distance,
+                _ => panic!("Unknown direction {}", direction),
+            }
+        }
+        horizontal * depth
+    }
+}
+
+pub mod day3 {
+    fn rows(input: &str) -> Vec<Vec<char>> {
+        input.lines().map(|line| line.chars().collect()).collect()
+    }
+
+    fn ones_in_column(rows: &[Vec<char>], column: usize) -> usize {
+        rows.iter().filter(|row| row[column] == '1').count()
+    }
+
+    fn to_number(bits: &[char]) -> u64 {
+        bits.iter()
+            .fold(0, |acc, &bit| acc * 2 + if bit == '1' { 1 } else { 0 })
+    }
+
+    // A tie counts towards 0 for gamma (the puzzle never says what to do with ties)
+    pub fn part1(input: &str) -> u64 {
+        let rows = rows(input);
+        let width = rows[0].len();
+        let mut gamma = Vec::new();
+        let mut epsilon = Vec::new();
+        for column in 0..width {
+            let ones = ones_in_column(&rows, column);
+            let zeros = rows.len() - ones;
+            if ones > zeros {
+                gamma.push('1');
+                epsilon.push('0');
+            } else {
+                gamma.push('0');
+                epsilon.push('1');
+            }
+        }
+        to_number(&gamma) * to_number(&epsilon)
+    }
+
+    // Keeps filtering until one row is left. If every remaining row has the same bit there is
+    // nothing to filter on so all of them are kept, and if rows are still left once the bits run
+    // out they are identical so the first one is used.
+    fn rating(rows: &[Vec<char>], oxygen: bool) -> u64 {
+        let mut remaining: Vec<Vec<char>> = rows.to_vec();
+        let mut column = 0;
+        while remaining.len() > 1 && column < remaining[0].len() {
+            let ones = ones_in_column(&remaining, column);
+            let zeros = remaining.len() - ones;
+            if ones > 0 && zeros > 0 {
+                let keep = match (oxygen, ones >= zeros) {
+                    (true, true) | (false, false) => '1',
+                    (true, false) | (false, true) => '0',
+                };
+                remaining.retain(|row| row[column] == keep);
+            }
+            column += 1;
+        }
+        to_number(&remaining[0])
+    }
+
+    pub fn part2(input: &str) -> u64 {
+        let rows = rows(input);
+        rating(&rows, true) * rating(&rows, false)
+    }
+}
+
+pub mod day4 {
+    use super::numbers;
+
+    fn parse(input: &str) -> (Vec<i64>, Vec<Vec<i64>>) {
+        let mut lines = input.lines();
+        let draws = numbers(lines.next().unwrap(), ',');
+        let mut boards = Vec::new();
+        let mut board = Vec::new();
+        for line in lines {
+            board.extend(line.split_whitespace().map(|n| n.parse::<i64>().unwrap()));
+            if board.len() == 25 {
+                boards.push(board);
+                board = Vec::new();
+            }
+        }
+        (draws, boards)
+    }
+
+    fn has_won(marked: &[bool]) -> bool {
+        (0..5).any(|row| (0..5).all(|column| marked[row * 5 + column]))
+            || (0..5).any(|column| (0..5).all(|row| marked[row * 5 + column]))
+    }
+
+    // The number of draws a board needs to win and its score at that point
+    fn play(draws: &[i64], board: &[i64]) -> Option<(usize, i64)> {
+        let mut marked = vec![false; 25];
+        for (turn, &draw) in draws.iter().enumerate() {
+            for (i, &value) in board.iter().enumerate() {
+                if value == draw {
+                    marked[i] = true;
+                }
+            }
+            if has_won(&marked) {
+                let unmarked: i64 = (0..25).filter(|&i| !marked[i]).map(|i| board[i]).sum();
+                return Some((turn, unmarked * draw));
+            }
+        }
+        None
+    }
+
+    // First board to win - if several win on the same draw the first board in the input wins
+    pub fn part1(input: &str) -> Option<i64> {
+        let (draws, boards) = parse(input);
+        let mut best: Option<(usize, i64)> = None;
+        for board in boards {
+            if let Some((turn, score)) = play(&draws, &board) {
+                if best.is_none_or(|(best_turn, _)| turn < best_turn) {
+                    best = Some((turn, score));
+                }
+            }
+        }
+        best.map(|(_, score)| score)
+    }
+
+    // Last board to win - if several win on the same draw the last board in the input is used
+    pub fn part2(input: &str) -> Option<i64> {
+        let (draws, boards) = parse(input);
+        let mut worst: Option<(usize, i64)> = None;
+        for board in boards {
+            if let Some((turn, score)) = play(&draws, &board) {
+                if worst.is_none_or(|(worst_turn, _)| turn >= worst_turn) {
+                    worst = Some((turn, score));
+                }
+            }
+        }
+        worst.map(|(_, score)| score)
+    }
+}
+
+pub mod day5 {
+    use std::collections::HashMap;
+
+    fn overlaps(input: &str, diagonal: bool) -> u64 {
+        let mut covered: HashMap<(i64, i64), u64> = HashMap::new();
+        for line in input.lines() {
+            let (start, end) = line.split_once(" -> ").unwrap();
+            let (x1, y1) = start.split_once(',').unwrap();
+            let (x2, y2) = end.split_once(',').unwrap();
+            let (x1, y1, x2, y2): (i64, i64, i64, i64) = (
+                x1.parse().unwrap(),
+                y1.parse().unwrap(),
+                x2.parse().unwrap(),
+                y2.parse().unwrap(),
+            );
+            if x1 != x2 && y1 != y2 && !diagonal {
+                continue;
+            }
+            let (step_x, step_y) = ((x2 - x1).signum(), (y2 - y1).signum());
+            let steps = (x2 - x1).abs().max((y2 - y1).abs());
+            for i in 0..=steps {
+                *covered
+                    .entry((x1 + i * step_x, y1 + i * step_y))
+                    .or_insert(0) += 1;
+            }
+        }
+        covered.values().filter(|&&count| count >= 2).count() as u64
+    }
+
+    pub fn part1(input: &str) -> u64 {
+        overlaps(input, false)
+    }
+
+    pub fn part2(input: &str) -> u64 {
+        overlaps(input, true)
+    }
+}
+
+pub mod day6 {
+    use super::numbers;
+    use std::collections::HashMap;
+
+    // Simulates every single fish - only usable for small inputs and day counts
+    pub fn simulate_each_fish(input: &str, days: u32) -> u64 {
+        let mut fish = numbers(input, ',');
+        for _ in 0..days {
+            let mut born = 0;
+            for timer in fish.iter_mut() {
+                if *timer == 0 {
+                    *timer = 6;
+                    born += 1;
+                } else {
+                    *timer -= 1;
+                }
+            }
+            fish.extend(std::iter::repeat_n(8, born));
+        }
+        fish.len() as u64
+    }
+
+    // How many fish a single fish with the given timer turns into after `days` days
+    fn descendants(timer: i64, days: i64, cache: &mut HashMap<(i64, i64), u64>) -> u64 {
+        if days <= timer {
+            return 1;
+        }
+        if let Some(&count) = cache.get(&(timer, days)) {
+            return count;
+        }
+        // The fish spawns on day timer + 1, then behaves like a fresh 6 with a new 8 alongside
+        let remaining = days - timer - 1;
+        let count = descendants(6, remaining, cache) + descendants(8, remaining, cache);
+        cache.insert((timer, days), count);
+        count
+    }
+
+    pub fn part1(input: &str) -> u64 {
+        simulate_each_fish(input, 80)
+    }
+
+    pub fn part2(input: &str) -> u64 {
+        let mut cache = HashMap::new();
+        numbers(input, ',')
+            .iter()
+            .map(|&timer| descendants(timer, 256, &mut cache))
+            .sum()
+    }
+}
+
+pub mod day7 {
+    use super::numbers;
+
+    // Tries every position between the leftmost and rightmost crab
+    fn cheapest(input: &str, cost: fn(i64) -> i64) -> i64 {
+        let crabs = numbers(input, ',');
+        let (min, max) = (*crabs.iter().min().unwrap(), *crabs.iter().max().unwrap());
+        (min..=max)
+            .map(|position| {
+                crabs
+                    .iter()
+                    .map(|&crab| cost((crab - position).abs()))
+                    .sum()
+            })
+            .min()
+            .unwrap()
+    }
+
+    pub fn part1(input: &str) -> i64 {
+        cheapest(input, |distance| distance)
+    }
+
+    pub fn part2(input: &str) -> i64 {
+        cheapest(input, |distance| (1..=distance).sum())
+    }
+}
+
+pub mod day8 {
+    use crate::testing::generate::DIGIT_SEGMENTS;
+
+    fn permutations(items: &[char]) -> Vec<Vec<char>> {
+        if items.len() <= 1 {
+            return vec![items.to_vec()];
+        }
+        let mut all = Vec::new();
+        for i in 0..items.len() {
+            let mut rest = items.to_vec();
+            let first = rest.remove(i);
+            for mut permutation in permutations(&rest) {
+                permutation.insert(0, first);
+                all.push(permutation);
+            }
+        }
+        all
+    }
+
+    // Maps a scrambled pattern back through the wiring to the digit it displays
+    fn digit(pattern: &str, wiring: &[char]) -> Option<usize> {
+        let mut segments: Vec<char> = pattern
+            .chars()
+            .map(|wire| (b'a' + wiring.iter().position(|&w| w == wire).unwrap() as u8) as char)
+            .collect();
+        segments.sort_unstable();
+        let segments: String = segments.into_iter().collect();
+        DIGIT_SEGMENTS.iter().position(|&digit| digit == segments)
+    }
+
+    // Tries all 5040 wirings until every one of the ten patterns shows a valid digit
+    fn decode(line: &str) -> u64 {
+        let (source, output) = line.split_once(" | ").unwrap();
+        for wiring in permutations(&['a', 'b', 'c', 'd', 'e', 'f', 'g']) {
+            if source
+                .split_whitespace()
+                .all(|pattern| digit(pattern, &wiring).is_some())
+            {
+                return output.split_whitespace().fold(0, |acc, pattern| {
+                    acc * 10 + digit(pattern, &wiring).unwrap() as u64
+                });
+            }
+        }
+        panic!("No wiring decodes {}", line);
+    }
+
+    pub fn part1(input: &str) -> u64 {
+        input
+            .lines()
+            .flat_map(|line| line.split_once(" | ").unwrap().1.split_whitespace())
+            .filter(|pattern| [2, 3, 4, 7].contains(&pattern.len()))
+            .count() as u64
+    }
+
+    pub fn part2(input: &str) -> u64 {
+        input.lines().map(decode).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // The references are checked against the worked examples from the puzzle text
+    #[test]
+    fn test_day1_example() {
+        let input = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+        assert_eq!(super::day1::part1(input), 7);
+        assert_eq!(super::day1::part2(input), 5);
+    }
+
+    #[test]
+    fn test_day2_example() {
+        let input = "forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2\n";
+        assert_eq!(super::day2::part1(input), 150);
+        assert_eq!(super::day2::part2(input), 900);
+    }
+
+    #[test]
+    fn test_day3_example() {
+        let input =
+            "00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010\n";
+        assert_eq!(super::day3::part1(input), 198);
+        assert_eq!(super::day3::part2(input), 230);
+    }
+
+    #[test]
+    fn test_day4_example() {
+        let input = "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1\n\n\
+                     22 13 17 11  0\n 8  2 23  4 24\n21  9 14 16  7\n 6 10  3 18  5\n 1 12 20 15 19\n\n\
+                      3 15  0  2 22\n 9 18 13 17  5\n19  8  7 25 23\n20 11 10 24  4\n14 21 16 12  6\n\n\
+                     14 21 17 24  4\n10 16 15  9 19\n18  8 23 26 20\n22 11 13  6  5\n 2  0 12  3  7\n";
+        assert_eq!(super::day4::part1(input), Some(4512));
+        assert_eq!(super::day4::part2(input), Some(1924));
+    }
+
+    #[test]
+    fn test_day5_example() {
+        let input = "0,9 -> 5,9\n8,0 -> 0,8\n9,4 -> 3,4\n2,2 -> 2,1\n7,0 -> 7,4\n\
+                     6,4 -> 2,0\n0,9 -> 2,9\n3,4 -> 1,4\n0,0 -> 8,8\n5,5 -> 8,2\n";
+        assert_eq!(super::day5::part1(input), 5);
+        assert_eq!(super::day5::part2(input), 12);
+    }
+
+    #[test]
+    fn test_day6_example() {
+        let input = "3,4,3,1,2";
+        assert_eq!(super::day6::simulate_each_fish(input, 18), 26);
+        assert_eq!(super::day6::part1(input), 5934);
+        assert_eq!(super::day6::part2(input), 26984457539);
+    }
+
+    #[test]
+    fn test_day7_example() {
+        let input = "16,1,2,0,4,2,7,1,2,14";
+        assert_eq!(super::day7::part1(input), 37);
+        assert_eq!(super::day7::part2(input), 168);
+    }
+
+    #[test]
+    fn test_day8_example() {
+        let input = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf\n";
+        assert_eq!(super::day8::part1(input), 0);
+        assert_eq!(super::day8::part2(input), 5353);
+    }
+}
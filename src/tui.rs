@@ -0,0 +1,638 @@
+// Interactive dashboard for a year, drawn with plain ANSI escape codes so it needs no extra
+// dependencies. The left pane lists the year's days, the right pane shows the selected day's
+// answers, runtimes and verdicts with a preview of its input, and keys re-run parts, switch between
+// the example and real input and open the day's visualisation if it has one.
+//
+// The terminal is put into raw mode with stty, so this needs a Unix-like terminal. Frames are
+// built as plain lines of styled segments, which keeps the layout testable without a terminal.
+
+use std::collections::BTreeMap;
+use std::io::{self, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::embedded;
+use crate::records::{self, Records};
+use crate::registry::{self, Solution};
+use crate::run::{self, PartRun, Verdict};
+
+const LIST_WIDTH: usize = 14;
+const PREVIEW_LINES: usize = 12;
+const HELP: &str = "up/down day  1/2 run part  r run both  e example/input  v visualise  q quit";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Key {
+    Up,
+    Down,
+    Run(u8),
+    RunBoth,
+    ToggleInput,
+    Visualise,
+    Quit,
+    Other,
+}
+
+pub fn parse_key(bytes: &[u8]) -> Key {
+    match bytes {
+        b"\x1b[A" | b"\x1bOA" | b"k" => Key::Up,
+        b"\x1b[B" | b"\x1bOB" | b"j" => Key::Down,
+        b"1" => Key::Run(1),
+        b"2" => Key::Run(2),
+        b"r" | b"\r" | b"\n" => Key::RunBoth,
+        b"e" => Key::ToggleInput,
+        b"v" => Key::Visualise,
+        // Escape, q and Ctrl-C (signals are off in raw mode)
+        b"\x1b" | b"q" | b"\x03" => Key::Quit,
+        _ => Key::Other,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Style {
+    Plain,
+    Bold,
+    Selected,
+    Pass,
+    Fail,
+    Dim,
+}
+
+impl Style {
+    fn escape(self) -> &'static str {
+        match self {
+            Style::Plain => "",
+            Style::Bold => "\x1b[1m",
+            Style::Selected => "\x1b[7m",
+            Style::Pass => "\x1b[32m",
+            Style::Fail => "\x1b[31m",
+            Style::Dim => "\x1b[2m",
+        }
+    }
+}
+
+// One screen line as styled segments
+pub type Line = Vec<(String, Style)>;
+
+fn verdict_style(verdict: &Verdict) -> Style {
+    match verdict {
+        Verdict::Pass => Style::Pass,
+        Verdict::Fail(_) | Verdict::Error(_) => Style::Fail,
+        Verdict::Unverified => Style::Plain,
+    }
+}
+
+// Truncates or pads to exactly `width` characters
+fn fit(text: &str, width: usize) -> String {
+    let mut fitted: String = text.chars().take(width).collect();
+    let length = fitted.chars().count();
+    fitted.extend(std::iter::repeat_n(' ', width - length));
+    fitted
+}
+
+pub struct App {
+    year: u16,
+    data: PathBuf,
+    solutions: Vec<Solution>,
+    answers: Records,
+    selected: usize,
+    example: bool,
+    // Latest run of each part, keyed on (index into solutions, example input)
+    runs: BTreeMap<(usize, bool), [Option<PartRun>; 2]>,
+    // Shown in place of the day until the next key
+    visual: Option<String>,
+    message: Option<String>,
+}
+
+impl App {
+    pub fn new(data: &Path, year: u16) -> Self {
+        let answers = records::load(&records::answers_path(data, year)).unwrap_or_default();
+        App {
+            year,
+            data: data.to_path_buf(),
+            solutions: registry::solutions()
+                .into_iter()
+                .filter(|solution| solution.year == year)
+                .collect(),
+            answers,
+            selected: 0,
+            example: false,
+            runs: BTreeMap::new(),
+            visual: None,
+            message: None,
+        }
+    }
+
+    fn input_path(&self, solution: &Solution) -> PathBuf {
+        let name = if self.example {
+            "example.txt"
+        } else {
+            "input.txt"
+        };
+        self.data
+            .join(self.year.to_string())
+            .join(format!("day{}", solution.day))
+            .join(name)
+    }
+
+    // Returns false once the user quits
+    pub fn handle(&mut self, key: Key) -> bool {
+        // Any key closes a visualisation
+        if self.visual.take().is_some() {
+            return true;
+        }
+        self.message = None;
+        match key {
+            Key::Up => self.selected = self.selected.saturating_sub(1),
+            Key::Down => {
+                if self.selected + 1 < self.solutions.len() {
+                    self.selected += 1;
+                }
+            }
+            Key::Run(part) => self.run(&[part]),
+            Key::RunBoth => self.run(&[1, 2]),
+            Key::ToggleInput => self.example = !self.example,
+            Key::Visualise => self.visualise(),
+            Key::Quit => return false,
+            Key::Other => {}
+        }
+        true
+    }
+
+    fn read_input(&mut self) -> Option<(Solution, String)> {
+        let solution = *self.solutions.get(self.selected)?;
+        let path = self.input_path(&solution);
+        match embedded::read(&path) {
+            Ok(text) => Some((solution, text)),
+            Err(e) => {
+                self.message = Some(format!("Unable to read {}: {}", path.display(), e));
+                None
+            }
+        }
+    }
+
+    fn run(&mut self, parts: &[u8]) {
+        let (solution, text) = match self.read_input() {
+            Some(input) => input,
+            None => return,
+        };
+        for &part in parts {
+            // Only the real input has verified answers
+            let expected = if self.example {
+                None
+            } else {
+                self.answers.get(&(solution.day, part))
+            };
+            let part_run = run::run_part(solution, part, &text, expected);
+            self.runs
+                .entry((self.selected, self.example))
+                .or_insert([None, None])[part as usize - 1] = Some(part_run);
+        }
+    }
+
+    fn visualise(&mut self) {
+        let day = match self.solutions.get(self.selected) {
+            Some(solution) => solution.day,
+            None => return,
+        };
+        let visualiser = match registry::visualisation(self.year, day) {
+            Some(visualiser) => visualiser,
+            None => {
+                self.message = Some(format!("Day {} has no visualisation", day));
+                return;
+            }
+        };
+        if let Some((_, text)) = self.read_input() {
+            match visualiser(&text) {
+                Ok(visual) => self.visual = Some(visual),
+                Err(e) => self.message = Some(format!("Unable to visualise day {}: {}", day, e)),
+            }
+        }
+    }
+
+    // Pass or fail marks for the list, from the latest runs on the real input
+    fn marks(&self, index: usize) -> String {
+        let runs = self.runs.get(&(index, false));
+        (0..2)
+            .map(|part| match runs.and_then(|runs| runs[part].as_ref()) {
+                None => ' ',
+                Some(run) => match run.verdict {
+                    Verdict::Pass => '*',
+                    Verdict::Unverified => '?',
+                    _ => 'x',
+                },
+            })
+            .collect()
+    }
+
+    fn list(&self) -> Vec<Line> {
+        self.solutions
+            .iter()
+            .enumerate()
+            .map(|(i, solution)| {
+                let plugin = if solution.plugin().is_some() {
+                    "p"
+                } else {
+                    " "
+                };
+                let text = format!(" Day {:>2}{} {}", solution.day, plugin, self.marks(i));
+                let style = if i == self.selected {
+                    Style::Selected
+                } else {
+                    Style::Plain
+                };
+                vec![(fit(&text, LIST_WIDTH), style)]
+            })
+            .collect()
+    }
+
+    fn details(&self) -> Vec<Line> {
+        let text = |text: String, style| vec![(text, style)];
+        if let Some(visual) = &self.visual {
+            let mut lines = vec![text(
+                String::from("Visualisation, any key to close"),
+                Style::Bold,
+            )];
+            lines.extend(
+                visual
+                    .lines()
+                    .map(|line| text(line.to_string(), Style::Plain)),
+            );
+            return lines;
+        }
+        let solution = match self.solutions.get(self.selected) {
+            Some(solution) => solution,
+            None => {
+                return vec![text(
+                    format!("No days compiled in for {}", self.year),
+                    Style::Bold,
+                )]
+            }
+        };
+
+        let mut title = format!("Day {} of {}", solution.day, self.year);
+        if let Some(plugin) = solution.plugin() {
+            title.push_str(&format!(" ({} plugin)", plugin));
+        }
+        let input_path = self.input_path(solution);
+        let source = if self.example { "Example" } else { "Input" };
+        let mut lines = vec![
+            text(title, Style::Bold),
+            text(format!("{}: {}", source, input_path.display()), Style::Dim),
+            Vec::new(),
+        ];
+
+        let runs = self.runs.get(&(self.selected, self.example));
+        for part in 1..=2u8 {
+            let run = runs.and_then(|runs| runs[part as usize - 1].as_ref());
+            lines.push(match run {
+                None => text(format!("Part {}: not run", part), Style::Dim),
+                Some(run) => {
+                    let verdict = match &run.verdict {
+                        Verdict::Pass => String::from("pass"),
+                        Verdict::Fail(expected) => format!("FAIL, expected {}", expected),
+                        Verdict::Unverified => String::from("unverified"),
+                        Verdict::Error(e) => format!("error: {}", e),
+                    };
+                    text(
+                        format!(
+                            "Part {}: {}  {}  {}",
+                            part,
+                            run.answer.as_deref().unwrap_or("-"),
+                            records::format_runtime(run.runtime),
+                            verdict
+                        ),
+                        verdict_style(&run.verdict),
+                    )
+                }
+            });
+        }
+
+        lines.push(Vec::new());
+        match embedded::read(&input_path) {
+            Ok(input) => {
+                lines.push(text(
+                    format!("{} lines", input.lines().count()),
+                    Style::Bold,
+                ));
+                lines.extend(
+                    input
+                        .lines()
+                        .take(PREVIEW_LINES)
+                        .map(|line| text(line.to_string(), Style::Plain)),
+                );
+            }
+            Err(e) => lines.push(text(
+                format!("No {}: {}", source.to_lowercase(), e),
+                Style::Fail,
+            )),
+        }
+        lines
+    }
+
+    // Every line of the screen, the list and details side by side above a status line
+    pub fn lines(&self, width: usize, height: usize) -> Vec<Line> {
+        let rows = height.saturating_sub(1);
+        let list = self.list();
+        // Scroll the list to keep the selected day on screen
+        let offset = (self.selected + 1).saturating_sub(rows);
+        let details = self.details();
+        let detail_width = width.saturating_sub(LIST_WIDTH + 3);
+
+        let mut lines: Vec<Line> = (0..rows)
+            .map(|row| {
+                let mut line = list
+                    .get(row + offset)
+                    .cloned()
+                    .unwrap_or_else(|| vec![(fit("", LIST_WIDTH), Style::Plain)]);
+                line.push((String::from(" | "), Style::Dim));
+                if let Some(detail) = details.get(row) {
+                    let mut remaining = detail_width;
+                    for (text, style) in detail {
+                        let text: String = text.chars().take(remaining).collect();
+                        remaining -= text.chars().count();
+                        line.push((text, *style));
+                    }
+                }
+                line
+            })
+            .collect();
+        let status = self.message.as_deref().unwrap_or(HELP);
+        lines.push(vec![(fit(status, width), Style::Dim)]);
+        lines
+    }
+
+    // A full frame of escape codes, redrawn from the top left corner
+    pub fn frame(&self, width: usize, height: usize) -> String {
+        let lines: Vec<String> = self
+            .lines(width, height)
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|(text, style)| match style {
+                        Style::Plain => text.clone(),
+                        _ => format!("{}{}\x1b[0m", style.escape(), text),
+                    })
+                    .collect::<String>()
+                    + "\x1b[K"
+            })
+            .collect();
+        format!("\x1b[H{}\x1b[J", lines.join("\r\n"))
+    }
+}
+
+// Runs stty against the controlling terminal
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// (columns, rows), falling back to 80x24
+fn terminal_size() -> (usize, usize) {
+    let size = stty(&["size"]).unwrap_or_default();
+    let mut numbers = size.split_whitespace().filter_map(|n| n.parse().ok());
+    match (numbers.next(), numbers.next()) {
+        (Some(rows), Some(columns)) if rows > 0 && columns > 0 => (columns, rows),
+        _ => (80, 24),
+    }
+}
+
+// Raw mode on the alternate screen, restored when dropped - even when unwinding from a panic
+struct Terminal {
+    saved: String,
+}
+
+impl Terminal {
+    fn enter() -> io::Result<Terminal> {
+        let saved = stty(&["-g"])?;
+        stty(&["-icanon", "-echo", "-isig", "min", "1"])?;
+        print!("\x1b[?1049h\x1b[?25l");
+        io::stdout().flush()?;
+        Ok(Terminal { saved })
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        let _ = io::stdout().flush();
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+pub fn run(data: &Path, year: u16) -> io::Result<()> {
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        return Err(io::Error::other("The tui needs an interactive terminal"));
+    }
+    let mut app = App::new(data, year);
+    let _terminal = Terminal::enter()?;
+    let mut stdin = io::stdin();
+    let mut buffer = [0u8; 16];
+    loop {
+        let (width, height) = terminal_size();
+        print!("{}", app.frame(width, height));
+        io::stdout().flush()?;
+        let read = stdin.read(&mut buffer)?;
+        if read == 0 || !app.handle(parse_key(&buffer[..read])) {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(lines: &[Line]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| line.iter().map(|(text, _)| text.as_str()).collect())
+            .collect()
+    }
+
+    // Only the day 1 tests run against real input
+    #[cfg(feature = "day1")]
+    fn data() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("data")
+    }
+
+    #[test]
+    fn test_parse_arrow_and_vi_keys() {
+        assert_eq!(parse_key(b"\x1b[A"), Key::Up);
+        assert_eq!(parse_key(b"\x1bOB"), Key::Down);
+        assert_eq!(parse_key(b"k"), Key::Up);
+        assert_eq!(parse_key(b"j"), Key::Down);
+    }
+
+    #[test]
+    fn test_parse_run_keys() {
+        assert_eq!(parse_key(b"2"), Key::Run(2));
+        assert_eq!(parse_key(b"\r"), Key::RunBoth);
+        assert_eq!(parse_key(b"e"), Key::ToggleInput);
+        assert_eq!(parse_key(b"v"), Key::Visualise);
+    }
+
+    #[test]
+    fn test_parse_quit_keys() {
+        for key in [&b"\x1b"[..], b"q", b"\x03"] {
+            assert_eq!(parse_key(key), Key::Quit);
+        }
+    }
+
+    #[test]
+    fn test_parse_other_keys() {
+        assert_eq!(parse_key(b"\x1b[C"), Key::Other);
+        assert_eq!(parse_key(b"3"), Key::Other);
+        assert_eq!(parse_key(b""), Key::Other);
+    }
+
+    #[test]
+    fn test_fit() {
+        assert_eq!(fit("abc", 5), "abc  ");
+        assert_eq!(fit("abcdef", 3), "abc");
+        assert_eq!(fit("déjà vu", 4), "déjà");
+        assert_eq!(fit("abc", 0), "");
+    }
+
+    #[test]
+    fn test_empty_year() {
+        let app = App::new(Path::new("data"), 1999);
+        let screen = plain(&app.lines(60, 5));
+        assert!(screen[0].ends_with("| No days compiled in for 1999"));
+        assert_eq!(screen[4].chars().count(), 60);
+    }
+
+    #[test]
+    fn test_keys_on_an_empty_year() {
+        let mut app = App::new(Path::new("data"), 1999);
+        for key in [Key::Down, Key::RunBoth, Key::Visualise, Key::Up] {
+            assert!(app.handle(key));
+        }
+        assert!(app.message.is_none());
+        assert!(!app.handle(Key::Quit));
+    }
+
+    #[test]
+    fn test_tiny_terminal() {
+        let app = App::new(Path::new("data"), 1999);
+        assert_eq!(plain(&app.lines(0, 0)), [""]);
+        let screen = plain(&app.lines(5, 2));
+        assert_eq!(screen.len(), 2);
+        assert_eq!(screen[1], "up/do");
+    }
+
+    #[test]
+    fn test_frame() {
+        let frame = App::new(Path::new("data"), 1999).frame(60, 3);
+        assert!(frame.starts_with("\x1b[H") && frame.ends_with("\x1b[K\x1b[J"));
+        assert_eq!(frame.matches("\r\n").count(), 2);
+        assert!(frame.contains("\x1b[1mNo days compiled in for 1999\x1b[0m"));
+    }
+
+    #[cfg(feature = "day1")]
+    #[test]
+    fn test_run_both_parts() {
+        let mut app = App::new(&data(), 2021);
+        assert!(app.handle(Key::RunBoth));
+        let screen = plain(&app.lines(100, 30));
+        assert_eq!(screen.len(), 30);
+        assert!(screen[0].starts_with(" Day  1  **    | Day 1 of 2021"));
+        assert!(screen[3].contains("Part 1: 1752"));
+        assert!(screen[3].ends_with("pass"));
+        assert!(screen.iter().all(|line| line.chars().count() <= 100));
+    }
+
+    #[cfg(feature = "day1")]
+    #[test]
+    fn test_run_one_part_of_the_example() {
+        let mut app = App::new(&data(), 2021);
+        app.handle(Key::ToggleInput);
+        app.handle(Key::Run(1));
+        let screen = plain(&app.lines(100, 30));
+        assert!(screen[1].contains("Example: "));
+        assert!(screen[3].contains("Part 1: 7 ") && screen[3].ends_with("unverified"));
+        assert!(screen[4].contains("Part 2: not run"));
+        assert!(screen[7].ends_with("199"));
+        // Only runs on the real input get marks in the list
+        assert_eq!(screen[0][..LIST_WIDTH].trim_end(), " Day  1");
+    }
+
+    #[cfg(feature = "day1")]
+    #[test]
+    fn test_runs_are_kept_per_input() {
+        let mut app = App::new(&data(), 2021);
+        app.handle(Key::Run(1));
+        app.handle(Key::ToggleInput);
+        assert!(plain(&app.lines(100, 30))[3].contains("Part 1: not run"));
+        app.handle(Key::ToggleInput);
+        assert!(plain(&app.lines(100, 30))[3].contains("Part 1: 1752"));
+    }
+
+    #[cfg(feature = "day1")]
+    #[test]
+    fn test_visualise_until_any_key() {
+        let mut app = App::new(&data(), 2021);
+        app.handle(Key::ToggleInput);
+        app.handle(Key::Visualise);
+        let screen = plain(&app.lines(100, 30));
+        assert!(screen[0].ends_with("| Visualisation, any key to close"));
+        assert!(screen[1].ends_with("| Depths, longest increase readings 1-4"));
+        // The key that closes it does nothing else, even quit
+        assert!(app.handle(Key::Quit));
+        assert!(plain(&app.lines(100, 30))[0].ends_with("| Day 1 of 2021"));
+    }
+
+    #[cfg(all(feature = "day1", feature = "day2"))]
+    #[test]
+    fn test_day_without_a_visualisation() {
+        let mut app = App::new(&data(), 2021);
+        app.handle(Key::Down);
+        app.handle(Key::Visualise);
+        let screen = plain(&app.lines(100, 30));
+        assert_eq!(screen[29].trim_end(), "Day 2 has no visualisation");
+        // The message clears on the next key
+        app.handle(Key::Other);
+        assert!(plain(&app.lines(100, 30))[29].starts_with("up/down day"));
+    }
+
+    #[cfg(feature = "day1")]
+    #[test]
+    fn test_missing_input() {
+        let missing = std::env::temp_dir().join(format!("aoc_tui_missing_{}", std::process::id()));
+        let mut app = App::new(&missing, 2021);
+        app.handle(Key::RunBoth);
+        let screen = plain(&app.lines(100, 30));
+        assert!(screen[29].starts_with("Unable to read "));
+        assert!(screen[3].contains("Part 1: not run"));
+        assert!(screen[6].contains("| No input: "));
+    }
+
+    #[cfg(feature = "day1")]
+    #[test]
+    fn test_selection_stays_on_the_list() {
+        let mut app = App::new(&data(), 2021);
+        app.handle(Key::Up);
+        assert_eq!(app.selected, 0);
+        for _ in 0..30 {
+            app.handle(Key::Down);
+        }
+        assert_eq!(app.selected, app.solutions.len() - 1);
+    }
+
+    #[cfg(all(feature = "day1", feature = "day2"))]
+    #[test]
+    fn test_list_scrolls_to_the_selection() {
+        let mut app = App::new(&data(), 2021);
+        app.handle(Key::Down);
+        // One row for the list above the status line
+        let screen = plain(&app.lines(100, 2));
+        assert!(screen[0].starts_with(" Day  2 "));
+    }
+}
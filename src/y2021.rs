@@ -0,0 +1,137 @@
+// Advent of Code 2021. Each day is behind a cargo feature of the same name.
+
+use crate::registry::Solution;
+
+pub const YEAR: u16 = 2021;
+
+#[cfg(feature = "day1")]
+pub mod day1;
+#[cfg(feature = "day2")]
+pub mod day2;
+#[cfg(feature = "day3")]
+pub mod day3;
+#[cfg(feature = "day4")]
+pub mod day4;
+#[cfg(feature = "day5")]
+pub mod day5;
+#[cfg(feature = "day6")]
+pub mod day6;
+#[cfg(feature = "day7")]
+pub mod day7;
+#[cfg(feature = "day8")]
+pub mod day8;
+
+// Day 4 answers are None when no board ever wins
+#[cfg(feature = "day4")]
+fn winning_score(result: Result<Option<i32>, day4::InputParseError>) -> Result<String, String> {
+    match result {
+        Ok(Some(score)) => Ok(score.to_string()),
+        Ok(None) => Err(String::from("No board wins")),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+// Days with a text visualisation of their input
+pub fn visualisation(day: u8) -> Option<crate::registry::Visualiser> {
+    match day {
+        #[cfg(feature = "day1")]
+        1 => Some(|input| crate::registry::answer(day1::plot::visualise(input))),
+        #[cfg(feature = "day5")]
+        5 => Some(|input| crate::registry::answer(day5::render_map(input, true))),
+        _ => None,
+    }
+}
+
+// All compiled in 2021 days, in day order
+#[allow(clippy::vec_init_then_push)]
+pub fn solutions() -> Vec<Solution> {
+    #[allow(unused_mut)]
+    let mut solutions = Vec::new();
+
+    #[cfg(feature = "day1")]
+    solutions.push(Solution {
+        year: YEAR,
+        day: 1,
+        part1: crate::registry::Solver::Builtin(|input| {
+            crate::registry::answer(day1::try_part1(input))
+        }),
+        part2: crate::registry::Solver::Builtin(|input| {
+            crate::registry::answer(day1::try_part2(input))
+        }),
+    });
+    #[cfg(feature = "day2")]
+    solutions.push(Solution {
+        year: YEAR,
+        day: 2,
+        part1: crate::registry::Solver::Builtin(|input| {
+            crate::registry::answer(day2::try_part1(input))
+        }),
+        part2: crate::registry::Solver::Builtin(|input| {
+            crate::registry::answer(day2::try_part2(input))
+        }),
+    });
+    #[cfg(feature = "day3")]
+    solutions.push(Solution {
+        year: YEAR,
+        day: 3,
+        part1: crate::registry::Solver::Builtin(|input| {
+            crate::registry::answer(day3::try_part1(input))
+        }),
+        part2: crate::registry::Solver::Builtin(|input| {
+            crate::registry::answer(day3::try_part2(input))
+        }),
+    });
+    #[cfg(feature = "day4")]
+    solutions.push(Solution {
+        year: YEAR,
+        day: 4,
+        part1: crate::registry::Solver::Builtin(|input| winning_score(day4::try_part1(input))),
+        part2: crate::registry::Solver::Builtin(|input| winning_score(day4::try_part2(input))),
+    });
+    #[cfg(feature = "day5")]
+    solutions.push(Solution {
+        year: YEAR,
+        day: 5,
+        part1: crate::registry::Solver::Builtin(|input| {
+            crate::registry::answer(day5::part1(input))
+        }),
+        part2: crate::registry::Solver::Builtin(|input| {
+            crate::registry::answer(day5::part2(input))
+        }),
+    });
+    #[cfg(feature = "day6")]
+    solutions.push(Solution {
+        year: YEAR,
+        day: 6,
+        part1: crate::registry::Solver::Builtin(|input| {
+            crate::registry::answer(day6::try_part1(input))
+        }),
+        part2: crate::registry::Solver::Builtin(|input| {
+            crate::registry::answer(day6::try_part2(input))
+        }),
+    });
+    #[cfg(feature = "day7")]
+    solutions.push(Solution {
+        year: YEAR,
+        day: 7,
+        part1: crate::registry::Solver::Builtin(|input| {
+            crate::registry::answer(day7::try_part1(input))
+        }),
+        part2: crate::registry::Solver::Builtin(|input| {
+            crate::registry::answer(day7::try_part2(input))
+        }),
+    });
+    #[cfg(feature = "day8")]
+    solutions.push(Solution {
+        year: YEAR,
+        day: 8,
+        part1: crate::registry::Solver::Builtin(|input| {
+            crate::registry::answer(day8::try_part1(input))
+        }),
+        part2: crate::registry::Solver::Builtin(|input| {
+            crate::registry::answer(day8::try_part2(input))
+        }),
+    });
+
+    solutions
+}
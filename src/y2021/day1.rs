@@ -0,0 +1,301 @@
+// Day 1 Challenges from Advent of Code 2021
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Sub};
+use thiserror::Error;
+
+pub mod analysis;
+pub mod anomaly;
+pub mod explain;
+pub mod plot;
+pub mod profile;
+pub mod stream;
+
+use analysis::{Aggregate, Sonar};
+
+#[derive(Debug, Error)]
+pub enum InputParseError {
+    #[error("Invalid depth {text:?} on line {line}: {reason}")]
+    InvalidDepth {
+        line: usize,
+        text: String,
+        reason: String,
+    },
+}
+
+// Part 1 - Count number of times value increases from previous value - returns the count
+pub fn part1(input: &str) -> u32 {
+    try_part1(input).unwrap()
+}
+
+// Part 2 - Implement sum of sliding window of 3 values across the input
+pub fn part2(input: &str) -> u32 {
+    try_part2(input).unwrap()
+}
+
+pub fn try_part1(input: &str) -> Result<u32, InputParseError> {
+    Ok(Sonar::PART1.analyse_input::<i64>(input)?.increased)
+}
+
+pub fn try_part2(input: &str) -> Result<u32, InputParseError> {
+    Ok(Sonar::PART2.analyse_input::<i64>(input)?.increased)
+}
+
+// Sum a sliding window of `window` values and count how often the sum increases.
+// A window of 0 is treated as 1.
+pub fn windowed_increases(input: &str, window: usize) -> u32 {
+    try_windowed_increases(input, window).unwrap()
+}
+
+pub fn try_windowed_increases(input: &str, window: usize) -> Result<u32, InputParseError> {
+    Ok(Sonar::new(window, Aggregate::Sum, 1)
+        .analyse_input::<i64>(input)?
+        .increased)
+}
+
+// A type readings can be read as. The puzzle's depths fit a u32, i64 takes negative and larger
+// readings, and f64 decimal ones, which compare as equal within a tolerance the other types
+// ignore. Sums and differences of readings are kept in the wider `Wide` so they can't overflow.
+pub trait Depth: Copy + PartialOrd + fmt::Display + fmt::Debug {
+    type Wide: Copy
+        + PartialOrd
+        + fmt::Display
+        + fmt::Debug
+        + Add<Output = Self::Wide>
+        + Sub<Output = Self::Wide>;
+
+    const ZERO: Self::Wide;
+    // Whether the deltas between readings are whole numbers, which the histogram buckets follow
+    const INTEGER: bool;
+
+    fn parse(text: &str) -> Result<Self, String>;
+    fn widen(self) -> Self::Wide;
+    fn to_f64(wide: Self::Wide) -> f64;
+
+    fn compare(a: Self::Wide, b: Self::Wide, _tolerance: f64) -> Ordering {
+        a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+    }
+
+    fn format(wide: Self::Wide) -> String {
+        wide.to_string()
+    }
+}
+
+impl Depth for u32 {
+    // Signed so the difference of two readings fits
+    type Wide = i64;
+    const ZERO: i64 = 0;
+    const INTEGER: bool = true;
+
+    fn parse(text: &str) -> Result<u32, String> {
+        text.parse()
+            .map_err(|e: std::num::ParseIntError| e.to_string())
+    }
+
+    fn widen(self) -> i64 {
+        self as i64
+    }
+
+    fn to_f64(wide: i64) -> f64 {
+        wide as f64
+    }
+}
+
+impl Depth for i64 {
+    type Wide = i128;
+    const ZERO: i128 = 0;
+    const INTEGER: bool = true;
+
+    fn parse(text: &str) -> Result<i64, String> {
+        text.parse()
+            .map_err(|e: std::num::ParseIntError| e.to_string())
+    }
+
+    fn widen(self) -> i128 {
+        self as i128
+    }
+
+    fn to_f64(wide: i128) -> f64 {
+        wide as f64
+    }
+}
+
+impl Depth for f64 {
+    type Wide = f64;
+    const ZERO: f64 = 0.0;
+    const INTEGER: bool = false;
+
+    fn parse(text: &str) -> Result<f64, String> {
+        match text.parse::<f64>() {
+            Ok(depth) if depth.is_finite() => Ok(depth),
+            Ok(_) => Err(String::from("not a finite number")),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    fn widen(self) -> f64 {
+        self
+    }
+
+    fn to_f64(wide: f64) -> f64 {
+        wide
+    }
+
+    fn compare(a: f64, b: f64, tolerance: f64) -> Ordering {
+        if (a - b).abs() <= tolerance {
+            Ordering::Equal
+        } else {
+            a.total_cmp(&b)
+        }
+    }
+
+    // Sums and deltas to six places, so 3.35 doesn't print as 3.3499999999999996
+    fn format(wide: f64) -> String {
+        let text = format!("{:.6}", wide);
+        let text = text.trim_end_matches('0').trim_end_matches('.');
+        match text {
+            "-0" => String::from("0"),
+            text => text.to_string(),
+        }
+    }
+}
+
+fn compare_depths<T: Depth>(a: T, b: T, tolerance: f64) -> Ordering {
+    T::compare(a.widen(), b.widen(), tolerance)
+}
+
+fn sum<T: Depth>(depths: &[T]) -> T::Wide {
+    depths
+        .iter()
+        .fold(T::ZERO, |total, &depth| total + depth.widen())
+}
+
+// One line of input, `line` counting from 1 for the error. Anything after a # is a comment, and
+// lines left blank (including a Windows \r) have no reading.
+pub fn parse_depth<T: Depth>(line: usize, text: &str) -> Result<Option<T>, InputParseError> {
+    let text = text.split('#').next().unwrap_or_default().trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    T::parse(text)
+        .map(Some)
+        .map_err(|reason| InputParseError::InvalidDepth {
+            line,
+            text: text.to_string(),
+            reason,
+        })
+}
+
+// (line number, reading) for each line with a reading on it
+pub fn parse_readings<T: Depth>(input: &str) -> Result<Vec<(usize, T)>, InputParseError> {
+    let mut readings = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        if let Some(depth) = parse_depth(i + 1, line)? {
+            readings.push((i + 1, depth));
+        }
+    }
+    Ok(readings)
+}
+
+pub fn parse_input<T: Depth>(input: &str) -> Result<Vec<T>, InputParseError> {
+    Ok(parse_readings(input)?
+        .into_iter()
+        .map(|(_, depth)| depth)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn test_part1() {
+        use super::*;
+        let input = String::from(
+            "\
+            199\n\
+            200\n\
+            208\n\
+            210\n\
+            200\n\
+            207\n\
+            240\n\
+            269\n\
+            260\n\
+            263\n",
+        );
+
+        let expected_result: u32 = 7;
+        let result = part1(&input);
+        assert_eq!(expected_result, result);
+    }
+
+    #[test]
+    fn test_part2() {
+        use super::*;
+        let input = String::from(
+            "\
+            199\n\
+            200\n\
+            208\n\
+            210\n\
+            200\n\
+            207\n\
+            240\n\
+            269\n\
+            260\n\
+            263\n",
+        );
+
+        let expected_result: u32 = 5;
+        let result = part2(&input);
+        assert_eq!(expected_result, result);
+    }
+
+    #[test]
+    fn test_comments_and_blank_lines_have_no_reading() {
+        use super::*;
+        let input = "# depths from the log\r\n-5\r\n\r\n4000000000  # above a u32\n  -7\n\n";
+        assert_eq!(
+            parse_readings::<i64>(input).unwrap(),
+            vec![(2, -5), (4, 4_000_000_000), (5, -7)]
+        );
+        assert_eq!(part1(input), 1);
+    }
+
+    #[test]
+    fn test_windows_line_endings() {
+        use super::*;
+        assert_eq!(part1("199\r\n200\r\n\r\n"), 1);
+    }
+
+    #[test]
+    fn test_invalid_depth_names_the_line() {
+        use super::*;
+        let error = parse_input::<i64>("1\n2\n\n3.5\n").unwrap_err().to_string();
+        assert_eq!(
+            error,
+            "Invalid depth \"3.5\" on line 4: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn test_nan_is_not_a_depth() {
+        use super::*;
+        assert!(parse_input::<f64>("1.5\nNaN\n")
+            .unwrap_err()
+            .to_string()
+            .contains("line 2"));
+    }
+
+    #[test]
+    fn prop_window_of_one_matches_part1() {
+        use super::*;
+        use crate::testing::prop::{self, ints, vecs};
+
+        prop::check(&vecs(ints(0..=300), 0..=40), |depths| {
+            let input: String = depths.iter().map(|d| format!("{}\n", d)).collect();
+            windowed_increases(&input, 1) == part1(&input)
+        });
+    }
+}
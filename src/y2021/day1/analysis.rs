@@ -0,0 +1,291 @@
+// Sliding window comparisons of the readings. Both parts are a Sonar, and the CLI's --window and
+// --aggregate pick other window sizes and ways of reducing a window to one value.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use super::{parse_input, sum, Depth, InputParseError};
+
+// How a window of readings is reduced to the single value that's compared
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Sum,
+    Mean,
+    Min,
+    Max,
+    // The middle reading, or the mean of the middle two for an even window
+    Median,
+}
+
+#[derive(Debug, Error)]
+#[error("Unknown aggregate {0}, expected one of sum, mean, min, max or median")]
+pub struct UnknownAggregate(String);
+
+impl FromStr for Aggregate {
+    type Err = UnknownAggregate;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sum" => Ok(Aggregate::Sum),
+            "mean" => Ok(Aggregate::Mean),
+            "min" => Ok(Aggregate::Min),
+            "max" => Ok(Aggregate::Max),
+            "median" => Ok(Aggregate::Median),
+            _ => Err(UnknownAggregate(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Aggregate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Aggregate::Sum => "sum",
+            Aggregate::Mean => "mean",
+            Aggregate::Min => "min",
+            Aggregate::Max => "max",
+            Aggregate::Median => "median",
+        };
+        f.write_str(name)
+    }
+}
+
+impl Aggregate {
+    pub fn apply<T: Depth>(&self, window: &[T]) -> f64 {
+        let (key, scale) = self.key(window);
+        T::to_f64(key) / scale
+    }
+
+    // An exact value that orders windows of the same size the way the aggregate does, and what it
+    // is multiplied up by: means compare as sums and medians as twice the median, so integer
+    // readings never go through a float.
+    fn key<T: Depth>(&self, window: &[T]) -> (T::Wide, f64) {
+        let Some(&first) = window.first() else {
+            return (T::ZERO, 1.0);
+        };
+        let pick = |better: Ordering| {
+            window.iter().fold(first, |best, &depth| {
+                match depth.partial_cmp(&best) == Some(better) {
+                    true => depth,
+                    false => best,
+                }
+            })
+        };
+        match self {
+            Aggregate::Sum => (sum(window), 1.0),
+            Aggregate::Mean => (sum(window), window.len() as f64),
+            Aggregate::Min => (pick(Ordering::Less).widen(), 1.0),
+            Aggregate::Max => (pick(Ordering::Greater).widen(), 1.0),
+            // Only the median needs the window in order
+            Aggregate::Median => {
+                let mut sorted = window.to_vec();
+                sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+                let len = sorted.len();
+                let upper = sorted[len / 2].widen();
+                let lower = sorted[(len - 1) / 2].widen();
+                (lower + upper, 2.0)
+            }
+        }
+    }
+}
+
+// Compares the aggregate of each sliding window of `window` readings with the window `stride`
+// readings later. Part 1 is a window of 1 and part 2 a window of 3, both summed with a stride of 1.
+// Aggregates within `tolerance` of each other count as unchanged, for f64 readings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sonar {
+    pub window: usize,
+    pub aggregate: Aggregate,
+    pub stride: usize,
+    pub tolerance: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Changes {
+    pub increased: u32,
+    pub decreased: u32,
+    pub unchanged: u32,
+}
+
+impl Sonar {
+    pub const PART1: Sonar = Sonar {
+        window: 1,
+        aggregate: Aggregate::Sum,
+        stride: 1,
+        tolerance: 0.0,
+    };
+    pub const PART2: Sonar = Sonar {
+        window: 3,
+        aggregate: Aggregate::Sum,
+        stride: 1,
+        tolerance: 0.0,
+    };
+
+    // A window or stride of 0 is treated as 1
+    pub fn new(window: usize, aggregate: Aggregate, stride: usize) -> Sonar {
+        Sonar {
+            window: window.max(1),
+            aggregate,
+            stride: stride.max(1),
+            tolerance: 0.0,
+        }
+    }
+
+    pub fn analyse<T: Depth>(&self, depths: &[T]) -> Changes {
+        let keys: Vec<(T::Wide, f64)> = depths
+            .windows(self.window.max(1))
+            .map(|window| self.aggregate.key(window))
+            .collect();
+        let stride = self.stride.max(1);
+        let mut changes = Changes::default();
+        for (&(earlier, scale), &(later, _)) in keys.iter().zip(keys.iter().skip(stride)) {
+            match T::compare(later, earlier, self.tolerance * scale) {
+                Ordering::Greater => changes.increased += 1,
+                Ordering::Less => changes.decreased += 1,
+                Ordering::Equal => changes.unchanged += 1,
+            }
+        }
+        changes
+    }
+
+    pub fn analyse_input<T: Depth>(&self, input: &str) -> Result<Changes, InputParseError> {
+        Ok(self.analyse(&parse_input::<T>(input)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEPTHS: [u32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    fn changes(window: usize, aggregate: Aggregate, stride: usize) -> Changes {
+        Sonar::new(window, aggregate, stride).analyse(&DEPTHS)
+    }
+
+    #[test]
+    fn test_window_of_one_is_part1() {
+        let expected = Changes {
+            increased: 7,
+            decreased: 2,
+            unchanged: 0,
+        };
+        assert_eq!(changes(1, Aggregate::Sum, 1), expected);
+        assert_eq!(Sonar::PART1.analyse(&DEPTHS), expected);
+    }
+
+    #[test]
+    fn test_window_of_three_is_part2() {
+        let expected = Changes {
+            increased: 5,
+            decreased: 1,
+            unchanged: 1,
+        };
+        assert_eq!(changes(3, Aggregate::Sum, 1), expected);
+        assert_eq!(Sonar::PART2.analyse(&DEPTHS), expected);
+    }
+
+    #[test]
+    fn test_stride_compares_the_same_readings_as_a_wider_sum() {
+        // Summed windows of 3 a reading apart compare the same readings as a stride of 3
+        assert_eq!(changes(1, Aggregate::Sum, 3), changes(3, Aggregate::Sum, 1));
+    }
+
+    #[test]
+    fn test_mean_orders_like_sum() {
+        assert_eq!(
+            changes(3, Aggregate::Mean, 1),
+            changes(3, Aggregate::Sum, 1)
+        );
+    }
+
+    #[test]
+    fn test_min_with_stride() {
+        assert_eq!(
+            changes(3, Aggregate::Min, 2),
+            Changes {
+                increased: 4,
+                decreased: 0,
+                unchanged: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_zero_window_and_stride_are_one() {
+        assert_eq!(changes(0, Aggregate::Sum, 0), changes(1, Aggregate::Sum, 1));
+    }
+
+    #[test]
+    fn test_too_few_readings_compare_nothing() {
+        let sonar = Sonar::new(3, Aggregate::Sum, 1);
+        assert_eq!(sonar.analyse(&DEPTHS[..3]), Changes::default());
+        assert_eq!(sonar.analyse::<u32>(&[]), Changes::default());
+    }
+
+    #[test]
+    fn test_median_of_even_window_is_mean_of_middle_two() {
+        assert_eq!(Aggregate::Median.apply(&[4u32, 1, 3, 2]), 2.5);
+        assert_eq!(changes(2, Aggregate::Median, 1).unchanged, 0);
+    }
+
+    #[test]
+    fn test_median_of_unsorted_decimals() {
+        assert_eq!(Aggregate::Median.apply(&[4.5, -1.0, 3.0]), 3.0);
+    }
+
+    #[test]
+    fn test_min_and_max_of_unsorted_window() {
+        assert_eq!(Aggregate::Min.apply(&[3i64, -1, 2]), -1.0);
+        assert_eq!(Aggregate::Max.apply(&[3i64, -1, 5, 2]), 5.0);
+    }
+
+    #[test]
+    fn test_empty_window_is_zero() {
+        assert_eq!(Aggregate::Max.apply::<u32>(&[]), 0.0);
+        assert_eq!(Aggregate::Median.apply::<u32>(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_mean_sums_without_overflow() {
+        assert_eq!(
+            Aggregate::Mean.apply(&[i64::MAX, i64::MAX]),
+            i64::MAX as f64
+        );
+    }
+
+    #[test]
+    fn test_parse_aggregate() {
+        assert_eq!("max".parse::<Aggregate>().unwrap(), Aggregate::Max);
+        assert!("mode".parse::<Aggregate>().is_err());
+    }
+
+    #[test]
+    fn test_decimal_changes_within_tolerance_are_unchanged() {
+        let readings = "10.0\n10.05\n10.2\n10.1\n";
+        let mut sonar = Sonar::PART1;
+        assert_eq!(sonar.analyse_input::<f64>(readings).unwrap().increased, 2);
+        sonar.tolerance = 0.1;
+        let changes = sonar.analyse_input::<f64>(readings).unwrap();
+        assert_eq!((changes.increased, changes.unchanged), (1, 2));
+    }
+
+    #[test]
+    fn prop_changes_cover_every_comparison() {
+        use crate::testing::prop::{self, ints, vecs};
+
+        prop::check(&vecs(ints(0..=300), 0..=40), |depths| {
+            let depths: Vec<u32> = depths.iter().map(|&d| d as u32).collect();
+            [Aggregate::Sum, Aggregate::Median, Aggregate::Max]
+                .iter()
+                .all(|&aggregate| {
+                    let sonar = Sonar::new(3, aggregate, 2);
+                    let changes = sonar.analyse(&depths);
+                    let compared = depths.len().saturating_sub(sonar.window + sonar.stride - 1);
+                    (changes.increased + changes.decreased + changes.unchanged) as usize == compared
+                })
+        });
+    }
+}
@@ -0,0 +1,485 @@
+// Flags readings that look like sensor spikes rather than the sea floor, and recomputes the part
+// answers without them to show how much the noise moves them. The z-score and MAD methods judge
+// each reading against the readings around it rather than the whole trace, since the depths drift
+// a long way over a full input.
+
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use super::analysis::Sonar;
+use super::{parse_readings, Depth, InputParseError};
+
+// Readings either side of the one being judged by the rolling methods, unless the Detector says
+pub const NEIGHBOURS: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Method {
+    // How many standard deviations a reading is from the mean of its neighbours
+    ZScore { threshold: f64 },
+    // The modified z-score, from the median and median absolute deviation of its neighbours, which
+    // a spike among them doesn't drag along
+    Mad { threshold: f64 },
+    // How far a reading jumped from the one before
+    Delta { limit: f64 },
+}
+
+#[derive(Debug, Error)]
+pub enum UnknownMethod {
+    #[error(
+        "Unknown anomaly method {0}, expected zscore, mad or delta with an optional :THRESHOLD"
+    )]
+    Name(String),
+    #[error("Invalid threshold {0:?}, expected a positive number")]
+    Threshold(String),
+    #[error("The delta method needs the largest allowed jump, for example delta:50")]
+    MissingLimit,
+}
+
+// METHOD or METHOD:THRESHOLD, as --anomalies takes it
+impl FromStr for Method {
+    type Err = UnknownMethod;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, threshold) = match s.split_once(':') {
+            Some((name, threshold)) => match threshold.parse::<f64>() {
+                Ok(threshold) if threshold > 0.0 && threshold.is_finite() => {
+                    (name, Some(threshold))
+                }
+                _ => return Err(UnknownMethod::Threshold(threshold.to_string())),
+            },
+            None => (s, None),
+        };
+        match name {
+            "zscore" => Ok(Method::ZScore {
+                threshold: threshold.unwrap_or(3.0),
+            }),
+            "mad" => Ok(Method::Mad {
+                threshold: threshold.unwrap_or(3.5),
+            }),
+            "delta" => threshold
+                .map(|limit| Method::Delta { limit })
+                .ok_or(UnknownMethod::MissingLimit),
+            _ => Err(UnknownMethod::Name(name.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Method::ZScore { threshold } => write!(f, "z-score above {}", threshold),
+            Method::Mad { threshold } => write!(f, "modified z-score above {}", threshold),
+            Method::Delta { limit } => write!(f, "jumps of more than {}", limit),
+        }
+    }
+}
+
+// A flagged reading, at its 1-based line, with the score that went over the threshold
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Anomaly<T> {
+    pub line: usize,
+    // Position among the readings, counting from 0
+    pub index: usize,
+    pub depth: T,
+    pub score: f64,
+}
+
+// How readings are judged, and how the answers without the anomalies are counted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Detector {
+    pub method: Method,
+    // Readings either side of the one being judged, for the rolling methods
+    pub neighbours: usize,
+    // Changes of this much or less count as no change in the answers, for f64 readings
+    pub tolerance: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repair {
+    Remove,
+    // Replaced on the straight line between the nearest readings either side that weren't flagged
+    Interpolate,
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(f64::total_cmp);
+    let len = values.len();
+    (values[(len - 1) / 2] + values[len / 2]) / 2.0
+}
+
+// A reading that differs from neighbours that don't vary at all scores as infinitely far out
+fn ratio(distance: f64, spread: f64) -> f64 {
+    match (distance == 0.0, spread == 0.0) {
+        (true, _) => 0.0,
+        (false, true) => f64::INFINITY,
+        (false, false) => distance / spread,
+    }
+}
+
+// Scores for every reading, against the up to `neighbours` readings either side of it
+fn scores(values: &[f64], method: Method, neighbours: usize) -> Vec<f64> {
+    let neighbours = |i: usize| -> Vec<f64> {
+        let start = i.saturating_sub(neighbours);
+        let end = (i + neighbours + 1).min(values.len());
+        (start..end)
+            .filter(|&j| j != i)
+            .map(|j| values[j])
+            .collect()
+    };
+    (0..values.len())
+        .map(|i| match method {
+            Method::Delta { .. } => match i {
+                0 => 0.0,
+                i => (values[i] - values[i - 1]).abs(),
+            },
+            _ if values.len() < 3 => 0.0,
+            Method::ZScore { .. } => {
+                let around = neighbours(i);
+                let mean = mean(&around);
+                let variance =
+                    around.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / around.len() as f64;
+                ratio((values[i] - mean).abs(), variance.sqrt())
+            }
+            Method::Mad { .. } => {
+                let mut around = neighbours(i);
+                let median = median(&mut around);
+                let mut deviations: Vec<f64> = around.iter().map(|v| (v - median).abs()).collect();
+                // 0.6745 scales the MAD to a standard deviation for normally distributed noise
+                0.6745 * ratio((values[i] - median).abs(), self::median(&mut deviations))
+            }
+        })
+        .collect()
+}
+
+impl Detector {
+    pub fn new(method: Method) -> Detector {
+        Detector {
+            method,
+            neighbours: NEIGHBOURS,
+            tolerance: 0.0,
+        }
+    }
+
+    // Anomalies among `depths`, with lines counted as positions from 1. Fewer than 1 neighbour
+    // is treated as 1.
+    pub fn detect<T: Depth>(&self, depths: &[T]) -> Vec<Anomaly<T>> {
+        let values: Vec<f64> = depths
+            .iter()
+            .map(|&depth| T::to_f64(depth.widen()))
+            .collect();
+        let threshold = match self.method {
+            Method::ZScore { threshold } | Method::Mad { threshold } => threshold,
+            Method::Delta { limit } => limit,
+        };
+        scores(&values, self.method, self.neighbours.max(1))
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, score)| score > threshold)
+            .map(|(index, score)| Anomaly {
+                line: index + 1,
+                index,
+                depth: depths[index],
+                score,
+            })
+            .collect()
+    }
+
+    // The readings and their anomalies, at the input's line numbers
+    pub fn find<T: Depth>(
+        &self,
+        input: &str,
+    ) -> Result<(Vec<T>, Vec<Anomaly<T>>), InputParseError> {
+        let readings = parse_readings::<T>(input)?;
+        let depths: Vec<T> = readings.iter().map(|&(_, depth)| depth).collect();
+        let mut anomalies = self.detect(&depths);
+        for anomaly in &mut anomalies {
+            anomaly.line = readings[anomaly.index].0;
+        }
+        Ok((depths, anomalies))
+    }
+
+    // Part 1 and part 2 over `values`, within the tolerance
+    fn answers<T: Depth>(&self, values: &[T]) -> [u32; 2] {
+        [Sonar::PART1, Sonar::PART2].map(|mut sonar| {
+            sonar.tolerance = self.tolerance;
+            sonar.analyse(values).increased
+        })
+    }
+
+    // Part 1 and part 2 over the repaired depths
+    pub fn repaired_answers<T: Depth>(
+        &self,
+        depths: &[T],
+        anomalies: &[Anomaly<T>],
+        how: Repair,
+    ) -> [u32; 2] {
+        self.answers(&repair(depths, anomalies, how))
+    }
+
+    // The flagged readings and how the answers move without them, for the CLI's --anomalies
+    pub fn render<T: Depth>(&self, depths: &[T], anomalies: &[Anomaly<T>]) -> String {
+        let mut output = String::new();
+        match self.method {
+            Method::Delta { .. } => writeln!(output, "Anomalies by {}:", self.method),
+            _ => writeln!(
+                output,
+                "Anomalies by {} against {} readings either side:",
+                self.method,
+                self.neighbours.max(1)
+            ),
+        }
+        .unwrap();
+        for anomaly in anomalies {
+            writeln!(
+                output,
+                "  Line {}: {} (score {:.2})",
+                anomaly.line, anomaly.depth, anomaly.score
+            )
+            .unwrap();
+        }
+        writeln!(
+            output,
+            "{} of {} readings flagged",
+            anomalies.len(),
+            depths.len()
+        )
+        .unwrap();
+        writeln!(output, "{:<14}{:>8}{:>8}", "", "Part 1", "Part 2").unwrap();
+        let rows = [
+            ("As read", self.answers(depths)),
+            (
+                "Removed",
+                self.repaired_answers(depths, anomalies, Repair::Remove),
+            ),
+            (
+                "Interpolated",
+                self.repaired_answers(depths, anomalies, Repair::Interpolate),
+            ),
+        ];
+        for (label, [part1, part2]) in rows {
+            writeln!(output, "{:<14}{:>8}{:>8}", label, part1, part2).unwrap();
+        }
+        output
+    }
+}
+
+// The depths with the anomalies dealt with, as floats since interpolating makes fractions
+pub fn repair<T: Depth>(depths: &[T], anomalies: &[Anomaly<T>], repair: Repair) -> Vec<f64> {
+    let mut flagged = vec![false; depths.len()];
+    for anomaly in anomalies {
+        flagged[anomaly.index] = true;
+    }
+    let values: Vec<f64> = depths
+        .iter()
+        .map(|&depth| T::to_f64(depth.widen()))
+        .collect();
+    let kept: Vec<usize> = (0..values.len()).filter(|&i| !flagged[i]).collect();
+    match repair {
+        Repair::Remove => kept.iter().map(|&i| values[i]).collect(),
+        Repair::Interpolate if kept.is_empty() => values,
+        Repair::Interpolate => (0..values.len())
+            .map(|i| {
+                if !flagged[i] {
+                    return values[i];
+                }
+                let after = kept.partition_point(|&k| k < i);
+                match (after.checked_sub(1).map(|b| kept[b]), kept.get(after)) {
+                    (Some(before), Some(&after)) => {
+                        let t = (i - before) as f64 / (after - before) as f64;
+                        values[before] + (values[after] - values[before]) * t
+                    }
+                    (Some(before), None) => values[before],
+                    (None, Some(&after)) => values[after],
+                    (None, None) => values[i],
+                }
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPIKY: &str =
+        "100\n101\n103\n102\n104\n# spike\n900\n105\n107\n106\n108\n110\n109\n111\n";
+    const ZSCORE: Method = Method::ZScore { threshold: 3.0 };
+
+    fn lines(method: &str) -> Vec<usize> {
+        let detector = Detector::new(method.parse().unwrap());
+        let (_, anomalies) = detector.find::<i64>(SPIKY).unwrap();
+        anomalies.iter().map(|anomaly| anomaly.line).collect()
+    }
+
+    fn spiky() -> (Vec<i64>, Vec<Anomaly<i64>>) {
+        Detector::new(ZSCORE).find(SPIKY).unwrap()
+    }
+
+    #[test]
+    fn test_zscore_finds_the_spike() {
+        assert_eq!(lines("zscore"), vec![7]);
+        let (depths, anomalies) = spiky();
+        assert_eq!(depths.len(), 13);
+        assert_eq!(anomalies[0].depth, 900);
+        assert!(anomalies[0].score > 3.0);
+    }
+
+    #[test]
+    fn test_mad_finds_the_spike() {
+        assert_eq!(lines("mad"), vec![7]);
+    }
+
+    #[test]
+    fn test_delta_also_flags_the_drop_back() {
+        assert_eq!(lines("delta:50"), vec![7, 8]);
+    }
+
+    #[test]
+    fn test_zscore_at_the_first_and_last_readings() {
+        let detector = Detector::new(ZSCORE);
+        let first = [500, 100, 102, 101, 103, 102, 104];
+        let last = [100, 102, 101, 103, 102, 104, 500];
+        let indices = |depths: &[u32]| -> Vec<usize> {
+            detector
+                .detect(depths)
+                .iter()
+                .map(|anomaly| anomaly.index)
+                .collect()
+        };
+        assert_eq!(indices(&first), vec![0]);
+        assert_eq!(indices(&last), vec![6]);
+    }
+
+    #[test]
+    fn test_neighbours_are_configurable() {
+        // A pair of spikes hide each other from one reading either side, but not from five
+        let depths: [u32; 20] = [
+            100, 102, 101, 103, 102, 104, 103, 105, 104, 106, 900, 900, 105, 107, 106, 108, 107,
+            109, 108, 110,
+        ];
+        let mut detector = Detector::new(Method::ZScore { threshold: 2.5 });
+        let indices = |detector: &Detector| -> Vec<usize> {
+            detector
+                .detect(&depths)
+                .iter()
+                .map(|anomaly| anomaly.index)
+                .collect()
+        };
+        assert_eq!(indices(&detector), vec![10, 11]);
+        detector.neighbours = 1;
+        let narrow = indices(&detector);
+        assert!(!narrow.contains(&10) && !narrow.contains(&11));
+        detector.neighbours = 0;
+        assert_eq!(indices(&detector), narrow);
+    }
+
+    #[test]
+    fn test_too_few_readings_to_judge() {
+        assert!(Detector::new(ZSCORE).detect::<u32>(&[]).is_empty());
+        assert!(Detector::new(ZSCORE).detect(&[1u32, 900]).is_empty());
+    }
+
+    #[test]
+    fn test_parse_method_threshold() {
+        assert_eq!(
+            "mad:2".parse::<Method>().unwrap(),
+            Method::Mad { threshold: 2.0 }
+        );
+        assert!("zscore:-1".parse::<Method>().is_err());
+    }
+
+    #[test]
+    fn test_delta_needs_a_limit() {
+        assert!(matches!(
+            "delta".parse::<Method>(),
+            Err(UnknownMethod::MissingLimit)
+        ));
+    }
+
+    #[test]
+    fn test_unknown_method() {
+        assert!("iqr".parse::<Method>().is_err());
+    }
+
+    #[test]
+    fn test_interpolate_between_kept_readings() {
+        let (depths, anomalies) = spiky();
+        assert_eq!(
+            repair(&depths, &anomalies, Repair::Interpolate)[4..7],
+            [104.0, 104.5, 105.0]
+        );
+    }
+
+    #[test]
+    fn test_remove() {
+        let (depths, anomalies) = spiky();
+        assert_eq!(repair(&depths, &anomalies, Repair::Remove).len(), 12);
+    }
+
+    #[test]
+    fn test_interpolate_at_the_ends_takes_the_nearest() {
+        let edges = [90, 101, 95];
+        let flagged: Vec<Anomaly<i64>> = [0, 2]
+            .map(|index| Anomaly {
+                line: index + 1,
+                index,
+                depth: edges[index],
+                score: 10.0,
+            })
+            .to_vec();
+        assert_eq!(
+            repair(&edges, &flagged, Repair::Interpolate),
+            vec![101.0, 101.0, 101.0]
+        );
+    }
+
+    #[test]
+    fn test_repaired_answers() {
+        // Removing the spike loses its rise and the drop after it, interpolating leaves a rise
+        let (depths, anomalies) = spiky();
+        let detector = Detector::new(ZSCORE);
+        assert_eq!(
+            detector.repaired_answers(&depths, &anomalies, Repair::Remove),
+            [8, 9]
+        );
+        assert_eq!(
+            detector.repaired_answers(&depths, &anomalies, Repair::Interpolate),
+            [9, 10]
+        );
+    }
+
+    #[test]
+    fn test_repaired_answers_use_the_tolerance() {
+        // The delta flags the spike and the drop back after it
+        let depths = [10.0, 10.05, 10.1, 50.0, 10.15, 10.2];
+        let mut detector = Detector::new(Method::Delta { limit: 5.0 });
+        let anomalies = detector.detect(&depths);
+        assert_eq!(
+            detector.repaired_answers(&depths, &anomalies, Repair::Remove)[0],
+            3
+        );
+        detector.tolerance = 0.1;
+        assert_eq!(
+            detector.repaired_answers(&depths, &anomalies, Repair::Remove)[0],
+            0
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let (depths, anomalies) = spiky();
+        let rendered = Detector::new(ZSCORE).render(&depths, &anomalies);
+        assert!(
+            rendered.starts_with("Anomalies by z-score above 3 against 5 readings either side:")
+        );
+        assert!(rendered.contains("  Line 7: 900 (score "));
+        assert!(rendered.contains("As read              8       9\n"));
+        assert!(rendered.contains("Interpolated         9      10\n"));
+    }
+}
@@ -0,0 +1,175 @@
+// The puzzle's annotated listings of readings and window sums, for the CLI's --explain.
+
+use std::cmp::Ordering;
+use std::fmt::{self, Write};
+
+use super::{parse_input, sum, Depth, InputParseError};
+
+// How a reading or window sum compares with the one before it, in the puzzle's words
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    // The first, with nothing before it
+    NotApplicable,
+    Increased,
+    Decreased,
+    NoChange,
+}
+
+// `values` are readings or sums of them, widened
+pub fn classify<T: Depth>(values: &[T::Wide], tolerance: f64) -> Vec<Change> {
+    let changes = values
+        .windows(2)
+        .map(|pair| match T::compare(pair[1], pair[0], tolerance) {
+            Ordering::Greater => Change::Increased,
+            Ordering::Less => Change::Decreased,
+            Ordering::Equal => Change::NoChange,
+        });
+    values
+        .first()
+        .map(|_| Change::NotApplicable)
+        .into_iter()
+        .chain(changes)
+        .collect()
+}
+
+// A line of the listing: the window's letter (empty for readings), its value and the change
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotated<T: Depth> {
+    pub label: String,
+    pub value: T::Wide,
+    pub change: Change,
+}
+
+impl<T: Depth> fmt::Display for Annotated<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.label.is_empty() {
+            write!(f, "{}: ", self.label)?;
+        }
+        let change = match (self.change, self.label.is_empty()) {
+            (Change::NotApplicable, true) => "N/A - no previous measurement",
+            (Change::NotApplicable, false) => "N/A - no previous sum",
+            (Change::Increased, _) => "increased",
+            (Change::Decreased, _) => "decreased",
+            (Change::NoChange, _) => "no change",
+        };
+        write!(f, "{} ({})", T::format(self.value), change)
+    }
+}
+
+// Windows are lettered A to Z, then AA, AB and on like spreadsheet columns
+fn window_label(mut index: usize) -> String {
+    let mut label = Vec::new();
+    loop {
+        label.push(b'A' + (index % 26) as u8);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    label.iter().rev().map(|&letter| letter as char).collect()
+}
+
+pub fn annotate_readings<T: Depth>(depths: &[T], tolerance: f64) -> Vec<Annotated<T>> {
+    let values: Vec<T::Wide> = depths.iter().map(|&depth| depth.widen()).collect();
+    values
+        .iter()
+        .zip(classify::<T>(&values, tolerance))
+        .map(|(&value, change)| Annotated {
+            label: String::new(),
+            value,
+            change,
+        })
+        .collect()
+}
+
+// A window of 0 is treated as 1
+pub fn annotate_windows<T: Depth>(
+    depths: &[T],
+    window: usize,
+    tolerance: f64,
+) -> Vec<Annotated<T>> {
+    let sums: Vec<T::Wide> = depths.windows(window.max(1)).map(sum).collect();
+    sums.iter()
+        .zip(classify::<T>(&sums, tolerance))
+        .enumerate()
+        .map(|(i, (&sum, change))| Annotated {
+            label: window_label(i),
+            value: sum,
+            change,
+        })
+        .collect()
+}
+
+// Both parts' listings as the puzzle walks through the example, for the CLI's --explain
+pub fn explain<T: Depth>(input: &str, tolerance: f64) -> Result<String, InputParseError> {
+    let depths = parse_input::<T>(input)?;
+    let mut output = String::from("Measurements:\n");
+    for line in annotate_readings(&depths, tolerance) {
+        writeln!(output, "{}", line).unwrap();
+    }
+    output.push_str("\nSums of three-measurement windows:\n");
+    for line in annotate_windows(&depths, 3, tolerance) {
+        writeln!(output, "{}", line).unwrap();
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+    const DEPTHS: [u32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    fn increases(lines: &[Annotated<u32>]) -> usize {
+        lines
+            .iter()
+            .filter(|line| line.change == Change::Increased)
+            .count()
+    }
+
+    #[test]
+    fn test_readings_listing_matches_puzzle() {
+        let listing = explain::<u32>(EXAMPLE, 0.0).unwrap();
+        let lines: Vec<&str> = listing.lines().collect();
+        assert_eq!(lines[1], "199 (N/A - no previous measurement)");
+        assert_eq!(lines[2], "200 (increased)");
+        assert_eq!(lines[5], "200 (decreased)");
+    }
+
+    #[test]
+    fn test_windows_listing_matches_puzzle() {
+        let listing = explain::<u32>(EXAMPLE, 0.0).unwrap();
+        let lines: Vec<&str> = listing.lines().collect();
+        assert_eq!(lines[13], "A: 607 (N/A - no previous sum)");
+        assert_eq!(lines[15], "C: 618 (no change)");
+        assert_eq!(lines[16], "D: 617 (decreased)");
+        assert_eq!(lines.last(), Some(&"H: 792 (increased)"));
+    }
+
+    #[test]
+    fn test_annotated_increases_are_the_answers() {
+        assert_eq!(increases(&annotate_readings(&DEPTHS, 0.0)), 7);
+        assert_eq!(increases(&annotate_windows(&DEPTHS, 3, 0.0)), 5);
+    }
+
+    #[test]
+    fn test_nothing_to_classify() {
+        assert!(classify::<u32>(&[], 0.0).is_empty());
+    }
+
+    #[test]
+    fn test_window_labels_run_on_like_columns() {
+        assert_eq!(window_label(0), "A");
+        assert_eq!(window_label(25), "Z");
+        assert_eq!(window_label(26), "AA");
+        assert_eq!(window_label(27 * 26), "AAA");
+    }
+
+    #[test]
+    fn test_decimal_changes_within_tolerance() {
+        assert!(explain::<f64>("10.0\n10.05\n10.2\n", 0.1)
+            .unwrap()
+            .contains("10.05 (no change)"));
+    }
+}
@@ -0,0 +1,531 @@
+// Depth traces of the readings and their window sums, as an SVG line chart or as a braille or
+// ASCII chart for the terminal. Depth grows downwards like the sonar sweep, each step is coloured
+// by whether the depth increased or decreased, and the longest run of increases is highlighted.
+// Long inputs are drawn to fit, so the full 2000 readings make a chart as wide as 10 do.
+
+use std::fmt::Write;
+
+use super::explain::{classify, Change};
+use super::{parse_input, sum, Depth, InputParseError};
+
+// A line chart's values with how each compares to the one before it
+struct Series {
+    title: String,
+    values: Vec<f64>,
+    changes: Vec<Change>,
+    // First and last index of the longest run of increases
+    longest_increase: Option<(usize, usize)>,
+}
+
+impl Series {
+    // Values are compared exactly, or within the tolerance for f64, and only drawn as floats
+    fn new<T: Depth>(title: String, values: &[T::Wide], tolerance: f64) -> Series {
+        let changes = classify::<T>(values, tolerance);
+        let mut longest_increase: Option<(usize, usize)> = None;
+        let mut start = 0;
+        for (i, change) in changes.iter().enumerate() {
+            if *change != Change::Increased {
+                start = i;
+            } else if longest_increase.is_none_or(|(first, last)| i - start > last - first) {
+                longest_increase = Some((start, i));
+            }
+        }
+        Series {
+            title,
+            values: values.iter().map(|&value| T::to_f64(value)).collect(),
+            changes,
+            longest_increase,
+        }
+    }
+
+    fn range(&self) -> (f64, f64) {
+        let min = self.values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = self
+            .values
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+        (min, max)
+    }
+
+    // How the step into value `i` is drawn, the highlight winning over the change. Only a strict
+    // decrease gets the decrease colour, a flat step is neutral like the first value
+    fn class(&self, i: usize) -> Class {
+        match (self.longest_increase, self.changes[i]) {
+            (Some((first, last)), _) if i > first && i <= last => Class::Highlight,
+            (_, Change::Increased) => Class::Increased,
+            (_, Change::Decreased) => Class::Decreased,
+            (_, Change::NoChange | Change::NotApplicable) => Class::Unchanged,
+        }
+    }
+
+    fn legend(&self) -> String {
+        match self.longest_increase {
+            Some((first, last)) => format!(
+                "{}, longest increase readings {}-{}",
+                self.title,
+                first + 1,
+                last + 1
+            ),
+            None => self.title.clone(),
+        }
+    }
+}
+
+fn series<T: Depth>(depths: &[T], window: usize, tolerance: f64) -> [Series; 2] {
+    let window = window.max(1);
+    let readings: Vec<T::Wide> = depths.iter().map(|&depth| depth.widen()).collect();
+    let sums: Vec<T::Wide> = depths.windows(window).map(sum).collect();
+    [
+        Series::new::<T>(String::from("Depths"), &readings, tolerance),
+        Series::new::<T>(format!("Sums of {} readings", window), &sums, tolerance),
+    ]
+}
+
+// Axis labels, rounded like decimal readings
+fn label(value: f64) -> String {
+    <f64 as Depth>::format(value)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Class {
+    Unchanged,
+    Decreased,
+    Increased,
+    Highlight,
+}
+
+impl Class {
+    fn colour(self) -> &'static str {
+        match self {
+            Class::Unchanged => "#9e9e9e",
+            Class::Decreased => "#c62828",
+            Class::Increased => "#2e7d32",
+            Class::Highlight => "#f9a825",
+        }
+    }
+
+    fn escape(self) -> &'static str {
+        match self {
+            Class::Unchanged => "\x1b[2m",
+            Class::Decreased => "\x1b[31m",
+            Class::Increased => "\x1b[32m",
+            Class::Highlight => "\x1b[1;33m",
+        }
+    }
+
+    fn ascii(self) -> char {
+        match self {
+            Class::Unchanged => '-',
+            Class::Decreased => '^',
+            Class::Increased => 'v',
+            Class::Highlight => '#',
+        }
+    }
+}
+
+const SVG_WIDTH: f64 = 900.0;
+const SVG_CHART_HEIGHT: f64 = 260.0;
+const SVG_MARGIN: f64 = 50.0;
+
+// Both series as stacked line charts, one polyline per stretch of steps of the same class
+pub fn render_svg<T: Depth>(depths: &[T], window: usize, tolerance: f64) -> String {
+    let charts = series(depths, window, tolerance);
+    let panel = SVG_CHART_HEIGHT + SVG_MARGIN;
+    let height = panel * charts.len() as f64 + SVG_MARGIN / 2.0;
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+         viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\" font-size=\"12\">",
+        w = SVG_WIDTH,
+        h = height
+    )
+    .unwrap();
+    for (i, chart) in charts.iter().enumerate() {
+        svg_chart(&mut svg, chart, SVG_MARGIN + i as f64 * panel);
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn svg_chart(svg: &mut String, chart: &Series, top: f64) {
+    let (left, width) = (SVG_MARGIN, SVG_WIDTH - 2.0 * SVG_MARGIN);
+    let (min, max) = chart.range();
+    let span = if max > min { max - min } else { 1.0 };
+    let steps = chart.values.len().saturating_sub(1).max(1) as f64;
+    let x = |i: usize| left + i as f64 / steps * width;
+    let y = |value: f64| top + (value - min) / span * SVG_CHART_HEIGHT;
+
+    writeln!(
+        svg,
+        "<text x=\"{}\" y=\"{:.1}\" font-weight=\"bold\">{}</text>",
+        left,
+        top - 10.0,
+        chart.legend()
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"#e0e0e0\"/>",
+        left, top, width, SVG_CHART_HEIGHT
+    )
+    .unwrap();
+    if chart.values.is_empty() {
+        return;
+    }
+    // Shallowest at the top, deepest at the bottom
+    for (value, label_y) in [(min, top + 4.0), (max, top + SVG_CHART_HEIGHT)] {
+        writeln!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\">{}</text>",
+            left - 4.0,
+            label_y,
+            label(value)
+        )
+        .unwrap();
+    }
+    if let Some((first, last)) = chart.longest_increase {
+        writeln!(
+            svg,
+            "<rect x=\"{:.1}\" y=\"{}\" width=\"{:.1}\" height=\"{}\" fill=\"{}\" \
+             fill-opacity=\"0.15\"/>",
+            x(first),
+            top,
+            (x(last) - x(first)).max(1.0),
+            SVG_CHART_HEIGHT,
+            Class::Highlight.colour()
+        )
+        .unwrap();
+    }
+
+    // Steps start..i are one stretch, drawn through points start - 1 to i - 1
+    let mut start = 1;
+    for i in 2..=chart.values.len() {
+        let class = chart.class(start);
+        if i < chart.values.len() && chart.class(i) == class {
+            continue;
+        }
+        let points: Vec<String> = (start - 1..i)
+            .map(|j| format!("{:.1},{:.1}", x(j), y(chart.values[j])))
+            .collect();
+        let stroke = if class == Class::Highlight { 2.5 } else { 1.2 };
+        writeln!(
+            svg,
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"/>",
+            points.join(" "),
+            class.colour(),
+            stroke
+        )
+        .unwrap();
+        start = i;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Glyphs {
+    // Eight dots to a character, so four times the detail
+    Braille,
+    // A character per point, marked v for increased, ^ decreased, - unchanged and # for the
+    // longest increase
+    Ascii,
+}
+
+// Size of each of the two charts in characters, and whether to colour them with ANSI escapes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextChart {
+    pub width: usize,
+    pub height: usize,
+    pub glyphs: Glyphs,
+    pub colour: bool,
+}
+
+impl TextChart {
+    pub fn render<T: Depth>(&self, depths: &[T], window: usize, tolerance: f64) -> String {
+        let mut output = String::new();
+        for chart in series(depths, window, tolerance) {
+            let (min, max) = chart.range();
+            writeln!(output, "{}", chart.legend()).unwrap();
+            if chart.values.is_empty() {
+                continue;
+            }
+            let (min, max) = (label(min), label(max));
+            let width = min.len().max(max.len()).max(8);
+            for (i, row) in self.grid(&chart).iter().enumerate() {
+                let label = match i {
+                    0 => min.as_str(),
+                    i if i + 1 == self.height.max(1) => max.as_str(),
+                    _ => "",
+                };
+                writeln!(output, "{:>width$} |{}", label, row, width = width).unwrap();
+            }
+        }
+        if self.colour {
+            writeln!(
+                output,
+                "{}increased\x1b[0m {}decreased\x1b[0m {}no change\x1b[0m {}longest increase\x1b[0m",
+                Class::Increased.escape(),
+                Class::Decreased.escape(),
+                Class::Unchanged.escape(),
+                Class::Highlight.escape()
+            )
+            .unwrap();
+        }
+        output
+    }
+
+    // The chart's rows, drawn as dots on a grid of cells each `across` by `down` dots
+    fn grid(&self, chart: &Series) -> Vec<String> {
+        let (across, down) = match self.glyphs {
+            Glyphs::Braille => (2, 4),
+            Glyphs::Ascii => (1, 1),
+        };
+        let (width, height) = (self.width.max(1), self.height.max(1));
+        let (dots_x, dots_y) = (width * across, height * down);
+        let (min, max) = chart.range();
+        let span = if max > min { max - min } else { 1.0 };
+        let steps = chart.values.len().saturating_sub(1).max(1) as f64;
+        let dot = |i: usize| {
+            let x = (i as f64 / steps * (dots_x - 1) as f64).round() as usize;
+            let y = ((chart.values[i] - min) / span * (dots_y - 1) as f64).round() as usize;
+            (x, y)
+        };
+
+        // Dots set in each cell and the strongest class drawn through it
+        let mut cells = vec![vec![(0u8, None::<Class>); width]; height];
+        let mut mark = |(x, y): (usize, usize), class: Class| {
+            let cell = &mut cells[y / down][x / across];
+            cell.0 |= braille_bit(x % across, y % down);
+            cell.1 = cell.1.max(Some(class));
+        };
+        mark(dot(0), chart.class(0));
+        for i in 1..chart.values.len() {
+            // Every dot along the step, so the line has no gaps
+            let ((x0, y0), (x1, y1)) = (dot(i - 1), dot(i));
+            let length = x0.abs_diff(x1).max(y0.abs_diff(y1)).max(1);
+            for step in 1..=length {
+                let t = step as f64 / length as f64;
+                let x = x0 as f64 + (x1 as f64 - x0 as f64) * t;
+                let y = y0 as f64 + (y1 as f64 - y0 as f64) * t;
+                mark((x.round() as usize, y.round() as usize), chart.class(i));
+            }
+        }
+
+        cells
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&(bits, class)| match class {
+                        None => String::from(" "),
+                        Some(class) => {
+                            let glyph = match self.glyphs {
+                                Glyphs::Braille => char::from_u32(0x2800 + bits as u32).unwrap(),
+                                Glyphs::Ascii => class.ascii(),
+                            };
+                            match self.colour {
+                                true => format!("{}{}\x1b[0m", class.escape(), glyph),
+                                false => glyph.to_string(),
+                            }
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+// Braille dots are numbered down the left column then the right, with the bottom row last
+fn braille_bit(x: usize, y: usize) -> u8 {
+    match (x, y) {
+        (0, 3) => 0x40,
+        (1, 3) => 0x80,
+        (0, y) => 1 << y,
+        (_, y) => 1 << (y + 3),
+    }
+}
+
+// The tui's day 1 visualisation, a braille chart of the depths and three reading sums
+pub fn visualise(input: &str) -> Result<String, InputParseError> {
+    let chart = TextChart {
+        width: 60,
+        height: 10,
+        glyphs: Glyphs::Braille,
+        colour: false,
+    };
+    Ok(chart.render(&parse_input::<i64>(input)?, 3, 0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [u32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    const ASCII: TextChart = TextChart {
+        width: 10,
+        height: 5,
+        glyphs: Glyphs::Ascii,
+        colour: false,
+    };
+
+    const BRAILLE: TextChart = TextChart {
+        width: 60,
+        height: 10,
+        glyphs: Glyphs::Braille,
+        colour: false,
+    };
+
+    // A full sized input that wanders up and down on its way deeper
+    fn full_input() -> Vec<u32> {
+        (0..2000).map(|i| 100 + (i * 37 % 101) + i / 2).collect()
+    }
+
+    fn polyline_points(svg: &str) -> usize {
+        svg.split("<polyline points=\"")
+            .skip(1)
+            .map(|polyline| polyline.split('"').next().unwrap().split(' ').count() - 1)
+            .sum()
+    }
+
+    #[test]
+    fn test_ascii_chart() {
+        let output = ASCII.render(&EXAMPLE, 3, 0.0);
+        let lines: Vec<&str> = output.lines().collect();
+        // Deeper readings lower down, one column per reading at this width
+        assert_eq!(lines[1], "     199 |-#  ^v    ");
+        assert_eq!(lines[2], "         |  ##  v   ");
+        assert_eq!(lines[5], "     269 |       v v");
+        assert_eq!(lines.len(), 12);
+    }
+
+    #[test]
+    fn test_legend_gives_the_longest_increase() {
+        let output = ASCII.render(&EXAMPLE, 3, 0.0);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "Depths, longest increase readings 1-4");
+        assert_eq!(
+            lines[6],
+            "Sums of 3 readings, longest increase readings 4-8"
+        );
+    }
+
+    #[test]
+    fn test_legend_without_an_increase() {
+        let output = ASCII.render(&[5u32, 5, 4], 1, 0.0);
+        assert!(output.starts_with("Depths\n"));
+    }
+
+    #[test]
+    fn test_braille_chart() {
+        let output = TextChart {
+            width: 10,
+            height: 5,
+            ..BRAILLE
+        }
+        .render(&EXAMPLE, 3, 0.0);
+        assert!(output
+            .chars()
+            .any(|c| ('\u{2801}'..='\u{28ff}').contains(&c)));
+        assert!(!output.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_colour() {
+        let output = TextChart {
+            colour: true,
+            ..ASCII
+        }
+        .render(&EXAMPLE, 3, 0.0);
+        assert!(output.contains("\x1b[1;33m#\x1b[0m"));
+        assert!(output.lines().last().unwrap().contains("longest increase"));
+    }
+
+    #[test]
+    fn test_braille_bits() {
+        assert_eq!(braille_bit(0, 0) | braille_bit(1, 3), 0x81);
+        assert_eq!(braille_bit(0, 2) | braille_bit(1, 0), 0x0c);
+    }
+
+    #[test]
+    fn test_full_input_fits_the_text_chart() {
+        let visual = BRAILLE.render(&full_input(), 3, 0.0);
+        assert_eq!(visual.lines().count(), 22);
+        assert!(visual.lines().all(|line| line.chars().count() <= 70));
+    }
+
+    #[test]
+    fn test_one_reading() {
+        // One point and no window sums, with no step to scale by
+        let output = ASCII.render(&[7u32], 3, 0.0);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "Depths");
+        assert_eq!(lines[1], "       7 |-         ");
+        assert_eq!(lines.last(), Some(&"Sums of 3 readings"));
+        assert!(render_svg(&[7u32], 3, 0.0).ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn test_no_readings() {
+        assert_eq!(
+            ASCII.render::<u32>(&[], 3, 0.0),
+            "Depths\nSums of 3 readings\n"
+        );
+        assert!(render_svg::<u32>(&[], 3, 0.0).ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn test_svg() {
+        let svg = render_svg(&EXAMPLE, 3, 0.0);
+        assert!(svg.starts_with("<svg ") && svg.ends_with("</svg>\n"));
+        assert!(svg.contains(Class::Highlight.colour()));
+        assert!(svg.contains("Depths, longest increase readings 1-4"));
+        assert!(!svg.contains("NaN"));
+    }
+
+    #[test]
+    fn test_svg_of_the_full_input() {
+        let svg = render_svg(&full_input(), 3, 0.0);
+        // Every step of both series drawn once
+        assert_eq!(polyline_points(&svg), 1999 + 1997);
+        assert!(svg.len() < 2_000_000);
+    }
+
+    #[test]
+    fn test_tolerance_flattens_small_changes() {
+        let depths = [1.0, 1.05, 1.1, 2.0];
+        assert_eq!(
+            ASCII.render(&depths, 1, 0.0).lines().next(),
+            Some("Depths, longest increase readings 1-4")
+        );
+        assert_eq!(
+            ASCII.render(&depths, 1, 0.1).lines().next(),
+            Some("Depths, longest increase readings 3-4")
+        );
+    }
+
+    #[test]
+    fn test_equal_depths_are_neutral() {
+        let depths = [3u32, 5, 5, 4];
+        let chart = &series(&depths, 1, 0.0)[0];
+        assert_eq!(
+            (1..4).map(|i| chart.class(i)).collect::<Vec<Class>>(),
+            [Class::Highlight, Class::Unchanged, Class::Decreased]
+        );
+
+        // The flat step is its own grey stretch between the increase and the decrease
+        let svg = render_svg(&depths, 1, 0.0);
+        assert!(svg.contains(&format!(
+            "<polyline points=\"316.7,310.0 583.3,310.0\" fill=\"none\" stroke=\"{}\"",
+            Class::Unchanged.colour()
+        )));
+        let ascii = ASCII.render(&depths, 1, 0.0);
+        assert_eq!(ascii.lines().nth(5), Some("       5 |   #---   "));
+    }
+
+    #[test]
+    fn test_visualise() {
+        let visual = visualise("199\n200\n208\n").unwrap();
+        assert!(visual.starts_with("Depths, longest increase readings 1-3\n"));
+        assert!(visualise("199\ndeep\n").is_err());
+    }
+}
@@ -0,0 +1,397 @@
+// A report on the shape of the depths, for the CLI's --profile: the longest runs up and down, the
+// largest jumps, the plateaus and a histogram of the changes between neighbouring readings.
+
+use std::cmp::Ordering;
+use std::fmt::Write;
+
+use super::{compare_depths, parse_readings, sum, Depth, InputParseError};
+
+// Buckets in a report's histogram of deltas between neighbouring readings
+pub const HISTOGRAM_BUCKETS: usize = 10;
+
+// Readings `start` to `end`, as 1-based line numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Run<T> {
+    pub start: usize,
+    pub end: usize,
+    // Readings in the run, which is more than end - start + 1 with blank lines or comments in it
+    pub readings: usize,
+    pub from: T,
+    pub to: T,
+}
+
+// The change into the reading on `line` from the one before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Jump<T> {
+    pub line: usize,
+    pub from: T,
+    pub to: T,
+}
+
+impl<T: Depth> Jump<T> {
+    pub fn delta(&self) -> T::Wide {
+        self.to.widen() - self.from.widen()
+    }
+}
+
+// Deltas from `low` to `high`, inclusive for whole number readings and up to but not including
+// `high` for decimal ones, apart from the last bucket
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bucket {
+    pub low: f64,
+    pub high: f64,
+    pub count: usize,
+}
+
+// Everything about the shape of the depths, where count_increases only keeps how often they rose.
+// Runs and jumps are None when there aren't enough readings, and ties go to the earliest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepthReport<T> {
+    pub readings: usize,
+    pub min: Option<T>,
+    pub max: Option<T>,
+    pub mean: Option<f64>,
+    // Strictly increasing or decreasing, at least two readings
+    pub longest_increase: Option<Run<T>>,
+    pub longest_decrease: Option<Run<T>>,
+    pub largest_jump: Option<Jump<T>>,
+    pub largest_drop: Option<Jump<T>>,
+    // Every run of two or more equal readings, in order
+    pub plateaus: Vec<Run<T>>,
+    pub histogram: Vec<Bucket>,
+}
+
+// The report with runs and jumps at the input's line numbers
+pub fn report<T: Depth>(input: &str, tolerance: f64) -> Result<DepthReport<T>, InputParseError> {
+    let readings = parse_readings::<T>(input)?;
+    let depths: Vec<T> = readings.iter().map(|&(_, depth)| depth).collect();
+    let line = |reading: usize| readings[reading - 1].0;
+    let mut report = profile(&depths, tolerance);
+    let runs = report
+        .longest_increase
+        .iter_mut()
+        .chain(report.longest_decrease.iter_mut())
+        .chain(report.plateaus.iter_mut());
+    for run in runs {
+        run.start = line(run.start);
+        run.end = line(run.end);
+    }
+    for jump in report
+        .largest_jump
+        .iter_mut()
+        .chain(report.largest_drop.iter_mut())
+    {
+        jump.line = line(jump.line);
+    }
+    Ok(report)
+}
+
+// The report with runs and jumps at 1-based positions in `depths`
+pub fn profile<T: Depth>(depths: &[T], tolerance: f64) -> DepthReport<T> {
+    let run = |start: usize, end: usize| Run {
+        start: start + 1,
+        end: end + 1,
+        readings: end - start + 1,
+        from: depths[start],
+        to: depths[end],
+    };
+    let jump = |i: usize| Jump {
+        line: i + 1,
+        from: depths[i - 1],
+        to: depths[i],
+    };
+
+    // Runs of neighbours that all compare the same way, as (first index, last index)
+    let runs = |ordering: Ordering| {
+        let mut runs = Vec::new();
+        let mut start = 0;
+        for i in 1..=depths.len() {
+            if i == depths.len() || compare_depths(depths[i], depths[i - 1], tolerance) != ordering
+            {
+                if i - 1 > start {
+                    runs.push((start, i - 1));
+                }
+                start = i;
+            }
+        }
+        runs
+    };
+    let longest = |ordering: Ordering| {
+        runs(ordering)
+            .into_iter()
+            .rev()
+            .max_by_key(|&(start, end)| end - start)
+            .map(|(start, end)| run(start, end))
+    };
+
+    let deltas: Vec<T::Wide> = depths
+        .windows(2)
+        .map(|pair| pair[1].widen() - pair[0].widen())
+        .collect();
+    let by_delta = |&a: &usize, &b: &usize| T::compare(deltas[a - 1], deltas[b - 1], 0.0);
+    let largest_jump = (1..depths.len())
+        .rev()
+        .max_by(by_delta)
+        .filter(|&i| T::compare(deltas[i - 1], T::ZERO, tolerance) == Ordering::Greater)
+        .map(jump);
+    let largest_drop = (1..depths.len())
+        .rev()
+        .min_by(by_delta)
+        .filter(|&i| T::compare(deltas[i - 1], T::ZERO, tolerance) == Ordering::Less)
+        .map(jump);
+
+    let extreme = |ordering: Ordering| {
+        depths
+            .iter()
+            .copied()
+            .reduce(|a, b| match compare_depths(b, a, 0.0) == ordering {
+                true => b,
+                false => a,
+            })
+    };
+    DepthReport {
+        readings: depths.len(),
+        min: extreme(Ordering::Less),
+        max: extreme(Ordering::Greater),
+        mean: (!depths.is_empty()).then(|| T::to_f64(sum(depths)) / depths.len() as f64),
+        longest_increase: longest(Ordering::Greater),
+        longest_decrease: longest(Ordering::Less),
+        largest_jump,
+        largest_drop,
+        plateaus: runs(Ordering::Equal)
+            .into_iter()
+            .map(|(start, end)| run(start, end))
+            .collect(),
+        histogram: histogram(
+            &deltas
+                .iter()
+                .map(|&delta| T::to_f64(delta))
+                .collect::<Vec<f64>>(),
+            T::INTEGER,
+        ),
+    }
+}
+
+// Equal width buckets from the smallest delta to the largest, whole numbers wide for integers
+fn histogram(deltas: &[f64], integer: bool) -> Vec<Bucket> {
+    let Some(low) = deltas.iter().copied().reduce(f64::min) else {
+        return Vec::new();
+    };
+    let high = deltas.iter().copied().fold(low, f64::max);
+    let buckets = HISTOGRAM_BUCKETS as f64;
+    let (width, count) = if integer {
+        let width = ((high - low + 1.0) / buckets).ceil();
+        (width, ((high - low + 1.0) / width).ceil() as usize)
+    } else if high > low {
+        ((high - low) / buckets, HISTOGRAM_BUCKETS)
+    } else {
+        (0.0, 1)
+    };
+    let end = if integer { width - 1.0 } else { width };
+    let mut buckets: Vec<Bucket> = (0..count)
+        .map(|i| Bucket {
+            low: low + i as f64 * width,
+            high: low + i as f64 * width + end,
+            count: 0,
+        })
+        .collect();
+    for delta in deltas {
+        let bucket = match width > 0.0 {
+            true => ((delta - low) / width) as usize,
+            false => 0,
+        };
+        buckets[bucket.min(count - 1)].count += 1;
+    }
+    buckets
+}
+
+// Whole numbers print as they are and decimals to three places
+fn format_delta(delta: f64, integer: bool) -> String {
+    match integer {
+        true => delta.to_string(),
+        false => format!("{:.3}", delta),
+    }
+}
+
+// The report as the CLI's --profile shows it
+pub fn render_report<T: Depth>(report: &DepthReport<T>) -> String {
+    let mut output = String::new();
+    writeln!(output, "Readings: {}", report.readings).unwrap();
+    if let (Some(min), Some(max), Some(mean)) = (report.min, report.max, report.mean) {
+        writeln!(output, "Depth: min {}, max {}, mean {:.1}", min, max, mean).unwrap();
+    }
+    let runs = [
+        ("Longest increase", report.longest_increase),
+        ("Longest decrease", report.longest_decrease),
+    ];
+    for (label, run) in runs {
+        match run {
+            Some(run) => writeln!(
+                output,
+                "{}: {} readings, lines {}-{} ({} to {})",
+                label, run.readings, run.start, run.end, run.from, run.to
+            ),
+            None => writeln!(output, "{}: none", label),
+        }
+        .unwrap();
+    }
+    for (label, jump) in [
+        ("Largest jump", report.largest_jump),
+        ("Largest drop", report.largest_drop),
+    ] {
+        match jump {
+            Some(jump) => writeln!(
+                output,
+                "{}: {}{} at line {} ({} to {})",
+                label,
+                if jump.delta() > T::ZERO { "+" } else { "" },
+                T::format(jump.delta()),
+                jump.line,
+                jump.from,
+                jump.to
+            ),
+            None => writeln!(output, "{}: none", label),
+        }
+        .unwrap();
+    }
+    match report.plateaus.iter().rev().max_by_key(|run| run.readings) {
+        Some(longest) => writeln!(
+            output,
+            "Plateaus: {}, longest {} readings at lines {}-{} (depth {})",
+            report.plateaus.len(),
+            longest.readings,
+            longest.start,
+            longest.end,
+            longest.from
+        ),
+        None => writeln!(output, "Plateaus: none"),
+    }
+    .unwrap();
+
+    if !report.histogram.is_empty() {
+        output.push_str("Deltas:\n");
+        let most = report.histogram.iter().map(|b| b.count).max().unwrap_or(0);
+        for bucket in &report.histogram {
+            let range = if bucket.low == bucket.high {
+                format_delta(bucket.low, T::INTEGER)
+            } else {
+                format!(
+                    "{}..{}",
+                    format_delta(bucket.low, T::INTEGER),
+                    format_delta(bucket.high, T::INTEGER)
+                )
+            };
+            // Bars of up to 40 characters, any bucket with a delta in it gets at least one
+            let bar = (bucket.count * 40).div_ceil(most.max(1));
+            writeln!(
+                output,
+                "  {:>16} |{:<40} {}",
+                range,
+                "#".repeat(bar),
+                bucket.count
+            )
+            .unwrap();
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The puzzle's example with a plateau at the end
+    fn example() -> DepthReport<u32> {
+        report::<u32>(
+            "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n263\n",
+            0.0,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_readings_min_and_max() {
+        let report = example();
+        assert_eq!(report.readings, 11);
+        assert_eq!((report.min, report.max), (Some(199), Some(269)));
+    }
+
+    #[test]
+    fn test_longest_runs_go_to_the_earliest() {
+        let report = example();
+        assert_eq!(
+            report.longest_increase,
+            Some(Run {
+                start: 1,
+                end: 4,
+                readings: 4,
+                from: 199,
+                to: 210
+            })
+        );
+        assert_eq!(report.longest_decrease.map(|run| run.start), Some(4));
+    }
+
+    #[test]
+    fn test_largest_jump_and_drop() {
+        let report = example();
+        assert_eq!(
+            report.largest_jump.map(|jump| (jump.line, jump.delta())),
+            Some((7, 33))
+        );
+        assert_eq!(
+            report.largest_drop.map(|jump| (jump.line, jump.delta())),
+            Some((5, -10))
+        );
+    }
+
+    #[test]
+    fn test_plateaus() {
+        let report = example();
+        assert_eq!(report.plateaus.len(), 1);
+        assert_eq!(report.plateaus[0].readings, 2);
+    }
+
+    #[test]
+    fn test_histogram_counts_every_delta() {
+        let report = example();
+        let counted: usize = report.histogram.iter().map(|bucket| bucket.count).sum();
+        assert_eq!(counted, 10);
+        assert_eq!(report.histogram[0].low, -10.0);
+    }
+
+    #[test]
+    fn test_render() {
+        let rendered = render_report(&example());
+        assert!(rendered.contains("Longest increase: 4 readings, lines 1-4 (199 to 210)\n"));
+        assert!(rendered.contains("Largest drop: -10 at line 5 (210 to 200)\n"));
+    }
+
+    #[test]
+    fn test_no_readings() {
+        let empty = profile::<u32>(&[], 0.0);
+        assert_eq!((empty.mean, empty.longest_increase), (None, None));
+        assert!(empty.histogram.is_empty());
+    }
+
+    #[test]
+    fn test_one_reading_has_no_plateaus() {
+        assert!(render_report(&profile(&[5u32], 0.0)).contains("Plateaus: none"));
+    }
+
+    #[test]
+    fn test_decimal_plateaus_within_tolerance() {
+        let decimal = report::<f64>("10.0\n10.05\n10.2\n10.1\n", 0.1).unwrap();
+        assert_eq!(decimal.plateaus.len(), 2);
+        assert_eq!(decimal.largest_drop, None);
+    }
+
+    #[test]
+    fn test_line_numbers_are_the_inputs() {
+        // Around blank lines and comments
+        let report = report::<i64>("# start\n1\n\n2\n3\n", 0.0).unwrap();
+        let increase = report.longest_increase.unwrap();
+        assert_eq!((increase.start, increase.end, increase.readings), (2, 5, 3));
+        assert_eq!(report.largest_jump.map(|jump| jump.line), Some(4));
+    }
+}
@@ -0,0 +1,138 @@
+// Counting increases as readings arrive, for the CLI reading day 1 from stdin.
+
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+use super::{compare_depths, Depth};
+
+// Counts increases as readings arrive, for feeds that never end. After each reading it yields the
+// running totals for part 1 and for sliding windows of `window` readings, keeping only the last
+// `window` readings: consecutive windows share all but one reading, so the later sum is larger
+// exactly when the reading entering it is larger than the one leaving.
+pub struct IncreaseCounter<I, T> {
+    depths: I,
+    window: usize,
+    tolerance: f64,
+    recent: VecDeque<T>,
+    totals: Totals,
+}
+
+// All u64, as a feed can run on past any u32
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Totals {
+    pub readings: u64,
+    pub increases: u64,
+    pub window_increases: u64,
+}
+
+impl<T: Depth, I: Iterator<Item = T>> IncreaseCounter<I, T> {
+    // A window of 0 is treated as 1
+    pub fn new(depths: I, window: usize, tolerance: f64) -> IncreaseCounter<I, T> {
+        let window = window.max(1);
+        IncreaseCounter {
+            depths,
+            window,
+            tolerance,
+            recent: VecDeque::with_capacity(window),
+            totals: Totals::default(),
+        }
+    }
+
+    pub fn totals(&self) -> Totals {
+        self.totals
+    }
+}
+
+impl<T: Depth, I: Iterator<Item = T>> Iterator for IncreaseCounter<I, T> {
+    type Item = Totals;
+
+    fn next(&mut self) -> Option<Totals> {
+        let depth = self.depths.next()?;
+        let increased =
+            |previous: T| compare_depths(depth, previous, self.tolerance) == Ordering::Greater;
+        if self
+            .recent
+            .back()
+            .is_some_and(|&previous| increased(previous))
+        {
+            self.totals.increases += 1;
+        }
+        if self.recent.len() == self.window {
+            let leaving = self.recent.pop_front().unwrap();
+            if increased(leaving) {
+                self.totals.window_increases += 1;
+            }
+        }
+        self.recent.push_back(depth);
+        self.totals.readings += 1;
+        Some(self.totals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEPTHS: [u32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    #[test]
+    fn test_totals_after_each_reading() {
+        let totals: Vec<Totals> = IncreaseCounter::new(DEPTHS.into_iter(), 3, 0.0).collect();
+        assert_eq!(totals.len(), DEPTHS.len());
+        assert_eq!(
+            totals[3],
+            Totals {
+                readings: 4,
+                increases: 3,
+                window_increases: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_final_totals_are_the_answers() {
+        let totals = IncreaseCounter::new(DEPTHS.into_iter(), 3, 0.0)
+            .last()
+            .unwrap();
+        assert_eq!((totals.increases, totals.window_increases), (7, 5));
+    }
+
+    #[test]
+    fn test_feed_that_never_ends() {
+        // Totals are there between readings, and only the window is kept
+        let mut counter = IncreaseCounter::new((1..).step_by(2).map(|d: i64| d), 2, 0.0);
+        counter.nth(999);
+        assert_eq!(counter.totals().increases, 999);
+        assert_eq!(counter.totals().window_increases, 998);
+        assert!(counter.recent.len() <= 2);
+    }
+
+    #[test]
+    fn test_no_readings() {
+        let mut counter = IncreaseCounter::new(std::iter::empty::<u32>(), 3, 0.0);
+        assert_eq!(counter.next(), None);
+        assert_eq!(counter.totals(), Totals::default());
+    }
+
+    #[test]
+    fn test_decimal_tolerance() {
+        let totals = IncreaseCounter::new([10.0, 10.05, 10.2].into_iter(), 1, 0.1).last();
+        assert_eq!(totals.map(|totals| totals.increases), Some(1));
+    }
+
+    #[test]
+    fn prop_streaming_matches_parts() {
+        use super::super::{part1, part2};
+        use crate::testing::prop::{self, ints, vecs};
+
+        prop::check(&vecs(ints(0..=300), 0..=40), |depths| {
+            let input: String = depths.iter().map(|d| format!("{}\n", d)).collect();
+            let depths = depths.iter().map(|&d| d as u32);
+            let totals = IncreaseCounter::new(depths, 3, 0.0)
+                .last()
+                .unwrap_or_default();
+            totals.increases == u64::from(part1(&input))
+                && totals.window_increases == u64::from(part2(&input))
+        });
+    }
+}
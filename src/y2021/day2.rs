@@ -0,0 +1,444 @@
+// Day 2 Challenges from Advent of Code 2021
+
+use std::fmt;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+pub mod script;
+pub mod trajectory;
+
+pub fn part1(input: &str) -> i32 {
+    try_part1(input).unwrap()
+}
+
+pub fn part2(input: &str) -> i32 {
+    try_part2(input).unwrap()
+}
+
+pub fn try_part1(input: &str) -> Result<i32, String> {
+    solve(Simple::default(), input)
+}
+
+pub fn try_part2(input: &str) -> Result<i32, String> {
+    solve(Aimed::default(), input)
+}
+
+// Runs every command then takes the product, naming the line of a command that overflows
+fn solve<M: MovementModel>(model: M, input: &str) -> Result<i32, String> {
+    let mut my_sub = Submarine::new(model);
+    for (line, command) in parse_lines(input)? {
+        my_sub
+            .apply(&command)
+            .map_err(|e| format!("Line {}: {}", line, e))?;
+    }
+    my_sub.product().map_err(|e| e.to_string())
+}
+
+// A position, or the answer, past what an i32 holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("Overflow, the sub went further than an i32 holds")]
+pub struct Overflow;
+
+// Where a sub is. Models without an aim leave it at 0
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Position {
+    pub horizontal: i32,
+    pub depth: i32,
+    pub aim: i32,
+}
+
+// How a sub responds to commands. Implement this to try out a new reading of the manual
+pub trait MovementModel {
+    // Leaves the sub where it was if the command would overflow its position
+    fn apply(&mut self, command: &SubCommand) -> Result<(), Overflow>;
+
+    fn position(&self) -> Position;
+
+    // The puzzle answer, horizontal position times depth
+    fn product(&self) -> Result<i32, Overflow> {
+        let position = self.position();
+        position
+            .depth
+            .checked_mul(position.horizontal)
+            .ok_or(Overflow)
+    }
+}
+
+// Part 1: up and down change depth directly
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Simple {
+    depth: i32,
+    horizontal: i32,
+}
+
+impl MovementModel for Simple {
+    fn apply(&mut self, command: &SubCommand) -> Result<(), Overflow> {
+        let distance = command.distance;
+        match command.direction {
+            SubDirection::Down => {
+                self.depth = self.depth.checked_add(distance).ok_or(Overflow)?;
+            }
+            SubDirection::Up => {
+                self.depth = self.depth.checked_sub(distance).ok_or(Overflow)?;
+            }
+            SubDirection::Forward => {
+                self.horizontal = self.horizontal.checked_add(distance).ok_or(Overflow)?;
+            }
+            SubDirection::Backward => {
+                self.horizontal = self.horizontal.checked_sub(distance).ok_or(Overflow)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn position(&self) -> Position {
+        Position {
+            horizontal: self.horizontal,
+            depth: self.depth,
+            aim: 0,
+        }
+    }
+}
+
+// Part 2: up and down change the aim, and forward dives along it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Aimed {
+    depth: i32,
+    horizontal: i32,
+    aim: i32,
+}
+
+impl MovementModel for Aimed {
+    // It increases your horizontal position by X units.
+    // It increases your depth by your aim multiplied by X.
+    fn apply(&mut self, command: &SubCommand) -> Result<(), Overflow> {
+        let distance = command.distance;
+        match command.direction {
+            SubDirection::Down => {
+                self.aim = self.aim.checked_add(distance).ok_or(Overflow)?;
+            }
+            SubDirection::Up => {
+                self.aim = self.aim.checked_sub(distance).ok_or(Overflow)?;
+            }
+            SubDirection::Forward => {
+                let depth_change = self.aim.checked_mul(distance).ok_or(Overflow)?;
+                let horizontal = self.horizontal.checked_add(distance).ok_or(Overflow)?;
+                self.depth = self.depth.checked_add(depth_change).ok_or(Overflow)?;
+                self.horizontal = horizontal;
+            }
+            // Reverses along the same line, so it rises while aimed down
+            SubDirection::Backward => {
+                let depth_change = self.aim.checked_mul(distance).ok_or(Overflow)?;
+                let horizontal = self.horizontal.checked_sub(distance).ok_or(Overflow)?;
+                self.depth = self.depth.checked_sub(depth_change).ok_or(Overflow)?;
+                self.horizontal = horizontal;
+            }
+        }
+        Ok(())
+    }
+
+    fn position(&self) -> Position {
+        Position {
+            horizontal: self.horizontal,
+            depth: self.depth,
+            aim: self.aim,
+        }
+    }
+}
+
+// A sub driven by any movement model, for code that wants more than the final product
+#[derive(Debug, Clone, Default)]
+pub struct Submarine<M> {
+    pub model: M,
+}
+
+impl<M: MovementModel> Submarine<M> {
+    pub fn new(model: M) -> Self {
+        Self { model }
+    }
+
+    pub fn apply(&mut self, command: &SubCommand) -> Result<(), Overflow> {
+        self.model.apply(command)
+    }
+
+    // Stops at the first command that would overflow
+    pub fn run<I: IntoIterator<Item = SubCommand>>(&mut self, commands: I) -> Result<(), Overflow> {
+        for command in commands {
+            self.apply(&command)?;
+        }
+        Ok(())
+    }
+
+    // Parses the whole input before moving, so a bad line leaves the sub where it was
+    pub fn run_input(&mut self, input: &str) -> Result<(), String> {
+        self.run(parse_input(input)?).map_err(|e| e.to_string())
+    }
+
+    pub fn position(&self) -> Position {
+        self.model.position()
+    }
+
+    pub fn depth(&self) -> i32 {
+        self.position().depth
+    }
+
+    pub fn horizontal(&self) -> i32 {
+        self.position().horizontal
+    }
+
+    pub fn aim(&self) -> i32 {
+        self.position().aim
+    }
+
+    pub fn product(&self) -> Result<i32, Overflow> {
+        self.model.product()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubCommand {
+    pub direction: SubDirection,
+    pub distance: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubDirection {
+    Forward,
+    Backward,
+    Up,
+    Down,
+}
+
+// Convert string to SubDirection Enum
+impl FromStr for SubDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "forward" => Ok(SubDirection::Forward),
+            "backward" => Ok(SubDirection::Backward),
+            "up" => Ok(SubDirection::Up),
+            "down" => Ok(SubDirection::Down),
+            _ => Err(format!("Invalid SubDirection: {}", s)),
+        }
+    }
+}
+
+// Written the way the input spells them, so commands print back as they were read
+impl fmt::Display for SubDirection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            SubDirection::Forward => "forward",
+            SubDirection::Backward => "backward",
+            SubDirection::Up => "up",
+            SubDirection::Down => "down",
+        };
+        f.write_str(name)
+    }
+}
+
+impl fmt::Display for SubCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.direction, self.distance)
+    }
+}
+
+// Convert string to SubCommand Struct
+impl FromStr for SubCommand {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut split = s.split_whitespace();
+        let direction: SubDirection = split
+            .next()
+            .ok_or_else(|| String::from("Missing direction"))?
+            .parse()?;
+        let distance: i32 = split
+            .next()
+            .ok_or_else(|| String::from("Missing distance"))?
+            .parse()
+            .map_err(|e| format!("Invalid distance in {:?}: {}", s, e))?;
+        if split.next().is_some() {
+            return Err(format!("Unexpected trailing input in {:?}", s));
+        }
+        Ok(SubCommand {
+            direction,
+            distance,
+        })
+    }
+}
+
+// Parses Input Into Commands
+pub fn parse_input(input: &str) -> Result<Vec<SubCommand>, String> {
+    Ok(parse_lines(input)?
+        .into_iter()
+        .map(|(_, command)| command)
+        .collect())
+}
+
+// Commands with the 1-based line each came from, expanded from the command language in script
+pub fn parse_lines(input: &str) -> Result<Vec<(usize, SubCommand)>, String> {
+    script::parse(input).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_part1() {
+        // Define Input Data
+        let input = String::from(
+            "forward 5\n\
+             down 5\n\
+             forward 8\n\
+             up 3\n\
+             down 8\n\
+             forward 2\n",
+        );
+        let result = part1(&input);
+        let expected_result: i32 = 150;
+        assert_eq!(result, expected_result)
+    }
+
+    #[test]
+    fn test_part2() {
+        // Define Input Data
+        let input = String::from(
+            "forward 5\n\
+             down 5\n\
+             forward 8\n\
+             up 3\n\
+             down 8\n\
+             forward 2\n",
+        );
+
+        let result = part2(&input);
+        let expected_result: i32 = 900;
+        assert_eq!(result, expected_result)
+    }
+
+    #[test]
+    fn test_submarine_with_a_custom_model() {
+        // Reverses the controls, to show a model from outside the built-in two
+        #[derive(Default)]
+        struct Inverted(Aimed);
+
+        impl MovementModel for Inverted {
+            fn apply(&mut self, command: &SubCommand) -> Result<(), Overflow> {
+                let direction = match command.direction {
+                    SubDirection::Up => SubDirection::Down,
+                    SubDirection::Down => SubDirection::Up,
+                    other => other,
+                };
+                self.0.apply(&SubCommand {
+                    direction,
+                    ..*command
+                })
+            }
+
+            fn position(&self) -> Position {
+                self.0.position()
+            }
+        }
+
+        let input = "forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2\n";
+        let mut aimed = Submarine::new(Aimed::default());
+        aimed.run_input(input).unwrap();
+        assert_eq!(
+            (aimed.horizontal(), aimed.depth(), aimed.aim()),
+            (15, 60, 10)
+        );
+        assert_eq!(aimed.product(), Ok(900));
+        aimed.apply(&"backward 2".parse().unwrap()).unwrap();
+        assert_eq!((aimed.horizontal(), aimed.depth()), (13, 40));
+
+        let mut simple = Submarine::new(Simple::default());
+        simple.run_input(input).unwrap();
+        assert_eq!(simple.position().aim, 0);
+        assert_eq!(simple.product(), Ok(150));
+
+        let mut inverted = Submarine::<Inverted>::default();
+        inverted.run_input(input).unwrap();
+        assert_eq!(inverted.depth(), -60);
+        assert!(inverted.run_input("forward 1\nsideways 2\n").is_err());
+        assert_eq!(inverted.horizontal(), 15);
+    }
+
+    #[test]
+    fn test_position_overflow_is_an_error() {
+        let max = i32::MAX;
+        assert_eq!(
+            try_part1(&format!("forward 1\ndown {}\ndown 1\n", max)),
+            Err(String::from(
+                "Line 3: Overflow, the sub went further than an i32 holds"
+            ))
+        );
+        assert!(try_part1(&format!("up {}\nup 2\n", max)).is_err());
+    }
+
+    #[test]
+    fn test_aimed_dive_overflow_is_an_error() {
+        assert_eq!(
+            try_part2("down 100000\nforward 100000\n"),
+            Err(String::from(
+                "Line 2: Overflow, the sub went further than an i32 holds"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_product_overflow_is_an_error() {
+        assert_eq!(
+            try_part1("forward 100000\ndown 100000\n"),
+            Err(String::from(
+                "Overflow, the sub went further than an i32 holds"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_overflow_leaves_the_sub_where_it_was() {
+        let mut aimed = Submarine::new(Aimed::default());
+        aimed.run_input("forward 3\ndown 100000\n").unwrap();
+        let before = aimed.position();
+        assert_eq!(
+            aimed.apply(&"forward 100000".parse().unwrap()),
+            Err(Overflow)
+        );
+        assert_eq!(aimed.position(), before);
+    }
+
+    #[test]
+    fn prop_command_roundtrip() {
+        use crate::testing::prop::{self, ints};
+
+        prop::check(&(ints(0..=2), ints(0..=1000)), |&(direction, distance)| {
+            let direction = ["forward", "up", "down"][direction as usize];
+            let command: SubCommand = format!("{} {}", direction, distance).parse().unwrap();
+            command.distance == distance as i32
+                && command.direction == direction.parse::<SubDirection>().unwrap()
+        });
+    }
+
+    #[test]
+    fn prop_part1_ignores_command_order() {
+        use crate::testing::prop::{self, ints, vecs};
+
+        prop::check(&vecs((ints(0..=2), ints(1..=9)), 0..=20), |commands| {
+            let lines: Vec<String> = commands
+                .iter()
+                .map(|&(direction, distance)| {
+                    format!(
+                        "{} {}",
+                        ["forward", "up", "down"][direction as usize],
+                        distance
+                    )
+                })
+                .collect();
+            let forwards = lines.join("\n");
+            let backwards = lines.into_iter().rev().collect::<Vec<String>>().join("\n");
+            part1(&forwards) == part1(&backwards)
+        });
+    }
+}
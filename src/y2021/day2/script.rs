@@ -0,0 +1,738 @@
+// The command language mission planners write by hand, which the puzzle input is the simplest
+// case of. On top of `<direction> <distance>` it has `#` comments, `repeat N { ... }` blocks,
+// macros defined with `def NAME { ... }` and called by name, and `include "FILE"` to pull in
+// another file's commands and macros. Words can be split across lines however reads best.
+//
+// Everything is expanded up front into the plain commands the movement models take. Each command
+// keeps the line of the top level source that produced it, so a macro's commands are at the line
+// it was called from and an included file's at the line of the include.
+//
+// Includes only resolve for a script read from a file, so parsing a bare string, as the parts,
+// FFI and fuzzers do, never touches the filesystem.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use super::{SubCommand, SubDirection};
+
+// Stops a few nested repeats or macros from expanding past what any real plan needs. Every
+// command expanded counts, in blocks and macro bodies as well as the result
+pub const MAX_COMMANDS: usize = 1_000_000;
+
+// Blocks and includes, the top level included, so a deep pile of `{` can't overflow the stack
+const MAX_NESTING: usize = 64;
+
+const KEYWORDS: [&str; 5] = ["repeat", "def", "include", "{", "}"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum Problem {
+    #[error("Unknown command or macro {0:?}")]
+    Unknown(String),
+    #[error("Missing distance after {0}")]
+    MissingDistance(String),
+    #[error("Invalid distance {0:?}: {1}")]
+    InvalidDistance(String, String),
+    #[error("Invalid repeat count {0:?}: {1}")]
+    InvalidCount(String, String),
+    #[error("Expected {expected}, found {found}")]
+    Expected {
+        expected: &'static str,
+        found: String,
+    },
+    #[error("Unclosed {{")]
+    Unclosed,
+    #[error("Unexpected }}")]
+    UnexpectedClose,
+    #[error("Can't define {0:?}, it's a direction or keyword")]
+    Reserved(String),
+    #[error("Unable to include {path}: {reason}")]
+    Include { path: String, reason: String },
+    #[error("Can't include {0} without a file to resolve it against")]
+    IncludeWithoutFile(String),
+    #[error("{0} includes itself")]
+    IncludeCycle(String),
+    #[error("Expands to more than {} commands", MAX_COMMANDS)]
+    TooLong,
+    #[error("Nested more than {} deep", MAX_NESTING)]
+    TooDeep,
+}
+
+// Where a problem is, with the file given for problems inside an include or a parsed file
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{}Line {line}, column {column}: {problem}", file_prefix(.file))]
+pub struct ScriptError {
+    pub file: Option<PathBuf>,
+    pub line: usize,
+    pub column: usize,
+    pub problem: Problem,
+}
+
+fn file_prefix(file: &Option<PathBuf>) -> String {
+    match file {
+        Some(file) => format!("{}: ", file.display()),
+        None => String::new(),
+    }
+}
+
+// Commands with the 1-based top level line each came from
+pub type Commands = Vec<(usize, SubCommand)>;
+
+// Expands `source`, which can't include other files
+pub fn parse(source: &str) -> Result<Commands, ScriptError> {
+    Expander::default().expand(source, None, None)
+}
+
+// Expands `source` read from the file at `path`, resolving includes against its directory
+pub fn parse_file(source: &str, path: &Path) -> Result<Commands, ScriptError> {
+    let mut expander = Expander::default();
+    if let Ok(path) = path.canonicalize() {
+        expander.including.push(path);
+    }
+    expander.expand(source, Some(path), Some(directory(path)))
+}
+
+// The plain commands the file at `path` expands to, one a line, for the parts to run
+pub fn expand_file(source: &str, path: &Path) -> Result<String, ScriptError> {
+    Ok(parse_file(source, path)?
+        .iter()
+        .map(|(_, command)| format!("{}\n", command))
+        .collect())
+}
+
+fn directory(path: &Path) -> &Path {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Token<'a> {
+    text: &'a str,
+    line: usize,
+    column: usize,
+}
+
+// Words split on whitespace, with braces always words of their own and a quoted path kept whole.
+// A `#` outside a quoted path starts a comment
+fn tokenize(source: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    for (i, line) in source.lines().enumerate() {
+        let mut chars = line.char_indices().peekable();
+        // Columns count on from the last token, so a long line isn't counted over and over
+        let (mut counted, mut column) = (0, 1);
+        while let Some((start, c)) = chars.next() {
+            let end = match c {
+                c if c.is_whitespace() => continue,
+                '#' => break,
+                '{' | '}' => start + 1,
+                '"' => chars
+                    .find(|&(_, c)| c == '"')
+                    .map_or(line.len(), |(end, _)| end + 1),
+                _ => {
+                    while chars
+                        .next_if(|&(_, c)| !c.is_whitespace() && !"{}\"#".contains(c))
+                        .is_some()
+                    {}
+                    chars.peek().map_or(line.len(), |&(end, _)| end)
+                }
+            };
+            column += line[counted..start].chars().count();
+            counted = start;
+            tokens.push(Token {
+                text: &line[start..end],
+                line: i + 1,
+                column,
+            });
+        }
+    }
+    tokens
+}
+
+// One file's tokens, read front to back
+struct Source<'a> {
+    file: Option<&'a Path>,
+    // Where includes resolve, if they can
+    dir: Option<&'a Path>,
+    tokens: Vec<Token<'a>>,
+    next: usize,
+    // Where the source ends, for problems found there
+    end: (usize, usize),
+}
+
+impl<'a> Source<'a> {
+    fn take(&mut self) -> Option<Token<'a>> {
+        let token = self.tokens.get(self.next).copied();
+        self.next += 1;
+        token
+    }
+
+    fn error(&self, token: Option<Token>, problem: Problem) -> ScriptError {
+        let (line, column) = token.map_or(self.end, |token| (token.line, token.column));
+        ScriptError {
+            file: self.file.map(Path::to_path_buf),
+            line,
+            column,
+            problem,
+        }
+    }
+
+    fn expect(&mut self, expected: &'static str) -> Result<Token<'a>, ScriptError> {
+        match self.take() {
+            Some(token) if token.text == expected => Ok(token),
+            token => Err(self.error(
+                token,
+                Problem::Expected {
+                    expected,
+                    found: describe(token),
+                },
+            )),
+        }
+    }
+}
+
+fn describe(token: Option<Token>) -> String {
+    match token {
+        Some(token) => format!("{:?}", token.text),
+        None => String::from("the end of the input"),
+    }
+}
+
+// Appends `count` copies of `commands` at `line`, or at their own lines if there's none, counting
+// them against the `expanded` so far
+fn append(
+    out: &mut Commands,
+    expanded: &mut usize,
+    commands: &[(usize, SubCommand)],
+    count: usize,
+    line: Option<usize>,
+) -> Result<(), Problem> {
+    let total = commands
+        .len()
+        .checked_mul(count)
+        .and_then(|added| added.checked_add(*expanded));
+    match total {
+        Some(total) if total <= MAX_COMMANDS => *expanded = total,
+        _ => return Err(Problem::TooLong),
+    }
+    let copies = commands.iter().cycle().take(commands.len() * count);
+    out.extend(copies.map(|&(own, command)| (line.unwrap_or(own), command)));
+    Ok(())
+}
+
+// Macros are shared by every file, so an include can be a library of them
+#[derive(Default)]
+struct Expander {
+    macros: HashMap<String, Commands>,
+    // Canonical paths of the files being included, innermost last
+    including: Vec<PathBuf>,
+    depth: usize,
+    // Commands expanded so far, across every block, macro and file
+    expanded: usize,
+}
+
+impl Expander {
+    fn expand(
+        &mut self,
+        source: &str,
+        file: Option<&Path>,
+        dir: Option<&Path>,
+    ) -> Result<Commands, ScriptError> {
+        let lines = source.lines().count();
+        let mut source = Source {
+            file,
+            dir,
+            tokens: tokenize(source),
+            next: 0,
+            end: (
+                lines.max(1),
+                source.lines().last().map_or(0, |line| line.chars().count()) + 1,
+            ),
+        };
+        self.block(&mut source, None)
+    }
+
+    // Commands up to the `}` closing `open`, or to the end for the top level
+    fn block(&mut self, source: &mut Source, open: Option<Token>) -> Result<Commands, ScriptError> {
+        if self.depth >= MAX_NESTING {
+            return Err(source.error(open, Problem::TooDeep));
+        }
+        self.depth += 1;
+        let mut out = Vec::new();
+        loop {
+            let token = match source.take() {
+                Some(token) => token,
+                None if open.is_some() => return Err(source.error(open, Problem::Unclosed)),
+                None => break,
+            };
+            let added = match token.text {
+                "}" if open.is_some() => break,
+                "}" => return Err(source.error(Some(token), Problem::UnexpectedClose)),
+                "{" => {
+                    return Err(source.error(
+                        Some(token),
+                        Problem::Expected {
+                            expected: "a command",
+                            found: describe(Some(token)),
+                        },
+                    ))
+                }
+                "repeat" => {
+                    let count = match source.take() {
+                        Some(count) => match count.text.parse::<usize>() {
+                            Ok(parsed) => parsed,
+                            Err(e) => {
+                                let problem =
+                                    Problem::InvalidCount(count.text.to_string(), e.to_string());
+                                return Err(source.error(Some(count), problem));
+                            }
+                        },
+                        None => {
+                            let problem = Problem::Expected {
+                                expected: "a repeat count",
+                                found: describe(None),
+                            };
+                            return Err(source.error(None, problem));
+                        }
+                    };
+                    let open = source.expect("{")?;
+                    let body = self.block(source, Some(open))?;
+                    append(&mut out, &mut self.expanded, &body, count, None)
+                }
+                "def" => {
+                    let name = source.take();
+                    let name = match name {
+                        Some(name) if !reserved(name.text) => name,
+                        Some(name) => {
+                            let problem = Problem::Reserved(name.text.to_string());
+                            return Err(source.error(Some(name), problem));
+                        }
+                        None => {
+                            let problem = Problem::Expected {
+                                expected: "a macro name",
+                                found: describe(None),
+                            };
+                            return Err(source.error(None, problem));
+                        }
+                    };
+                    let open = source.expect("{")?;
+                    let body = self.block(source, Some(open))?;
+                    self.macros.insert(name.text.to_string(), body);
+                    Ok(())
+                }
+                "include" => {
+                    let included = self.include(source)?;
+                    append(&mut out, &mut self.expanded, &included, 1, Some(token.line))
+                }
+                word => match (word.parse::<SubDirection>(), self.macros.get(word)) {
+                    (Ok(direction), _) => {
+                        let command = command(source, token, direction)?;
+                        append(&mut out, &mut self.expanded, &[command], 1, None)
+                    }
+                    (Err(_), Some(body)) => {
+                        append(&mut out, &mut self.expanded, body, 1, Some(token.line))
+                    }
+                    (Err(_), None) => {
+                        let problem = Problem::Unknown(word.to_string());
+                        return Err(source.error(Some(token), problem));
+                    }
+                },
+            };
+            added.map_err(|problem| source.error(Some(token), problem))?;
+        }
+        self.depth -= 1;
+        Ok(out)
+    }
+
+    fn include(&mut self, source: &mut Source) -> Result<Commands, ScriptError> {
+        let token = source.take();
+        let path = match token.map(|token| token.text) {
+            Some(text) if text.len() >= 2 && text.starts_with('"') && text.ends_with('"') => {
+                let name = &text[1..text.len() - 1];
+                match source.dir {
+                    Some(dir) => dir.join(name),
+                    None => {
+                        let problem = Problem::IncludeWithoutFile(name.to_string());
+                        return Err(source.error(token, problem));
+                    }
+                }
+            }
+            _ => {
+                let problem = Problem::Expected {
+                    expected: "a quoted path",
+                    found: describe(token),
+                };
+                return Err(source.error(token, problem));
+            }
+        };
+        let failed = |reason: String| Problem::Include {
+            path: path.display().to_string(),
+            reason,
+        };
+        let read = path
+            .canonicalize()
+            .and_then(|canonical| Ok((fs::read_to_string(&canonical)?, canonical)));
+        let (text, canonical) = read.map_err(|e| source.error(token, failed(e.to_string())))?;
+        if self.including.contains(&canonical) {
+            let problem = Problem::IncludeCycle(path.display().to_string());
+            return Err(source.error(token, problem));
+        }
+        if self.depth >= MAX_NESTING {
+            return Err(source.error(token, Problem::TooDeep));
+        }
+        self.including.push(canonical);
+        let included = self.expand(&text, Some(&path), Some(directory(&path)));
+        self.including.pop();
+        included
+    }
+}
+
+fn reserved(word: &str) -> bool {
+    KEYWORDS.contains(&word) || word.parse::<SubDirection>().is_ok()
+}
+
+fn command(
+    source: &mut Source,
+    token: Token,
+    direction: SubDirection,
+) -> Result<(usize, SubCommand), ScriptError> {
+    match source.take() {
+        Some(distance) => match distance.text.parse::<i32>() {
+            Ok(parsed) => Ok((
+                token.line,
+                SubCommand {
+                    direction,
+                    distance: parsed,
+                },
+            )),
+            Err(e) => {
+                let problem = Problem::InvalidDistance(distance.text.to_string(), e.to_string());
+                Err(source.error(Some(distance), problem))
+            }
+        },
+        None => Err(source.error(Some(token), Problem::MissingDistance(direction.to_string()))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commands(source: &str) -> Vec<String> {
+        parse(source)
+            .unwrap()
+            .iter()
+            .map(|(line, command)| format!("{}: {}", line, command))
+            .collect()
+    }
+
+    fn error(source: &str) -> String {
+        parse(source).unwrap_err().to_string()
+    }
+
+    // A directory of script files, removed when dropped
+    struct Files(PathBuf);
+
+    impl Files {
+        fn new(name: &str, files: &[(&str, &str)]) -> Files {
+            let dir = std::env::temp_dir().join(format!(
+                "aoc_day2_script_{}_{}",
+                name,
+                std::process::id()
+            ));
+            for (file, text) in files {
+                let path = dir.join(file);
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(path, text).unwrap();
+            }
+            Files(dir)
+        }
+
+        fn load(&self, name: &str) -> Result<Commands, ScriptError> {
+            let path = self.0.join(name);
+            parse_file(&fs::read_to_string(&path).unwrap(), &path)
+        }
+    }
+
+    impl Drop for Files {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_puzzle_input_is_a_script() {
+        assert_eq!(
+            commands("forward 5\ndown 5\n"),
+            ["1: forward 5", "2: down 5"]
+        );
+    }
+
+    #[test]
+    fn test_directions_ignore_case() {
+        assert_eq!(commands("UP 1\nForward 2"), ["1: up 1", "2: forward 2"]);
+    }
+
+    #[test]
+    fn test_comments() {
+        assert_eq!(
+            commands("# A practice dive\nforward 3 # out of the harbour\nup 1#surface"),
+            ["2: forward 3", "3: up 1"]
+        );
+    }
+
+    #[test]
+    fn test_words_split_across_lines() {
+        assert_eq!(commands("forward\n  3"), ["1: forward 3"]);
+    }
+
+    #[test]
+    fn test_repeat() {
+        assert_eq!(
+            commands("repeat 2 {\n  down 1\n  forward 2\n}"),
+            ["2: down 1", "3: forward 2", "2: down 1", "3: forward 2"]
+        );
+        assert_eq!(commands("repeat 2 { repeat 3 { up 1 } }").len(), 6);
+    }
+
+    #[test]
+    fn test_repeat_of_nothing_is_nothing() {
+        assert!(parse("repeat 4000000000 { repeat 4000000000 { } }")
+            .unwrap()
+            .is_empty());
+        assert!(parse("repeat 0 { up 1 }").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_macro_commands_are_at_the_call() {
+        assert_eq!(
+            commands("def dive { down 5 forward 2 }\nup 1\ndive\n"),
+            ["2: up 1", "3: down 5", "3: forward 2"]
+        );
+    }
+
+    #[test]
+    fn test_macro_without_spaces_round_braces() {
+        assert_eq!(commands("def surface{up 10}surface"), ["1: up 10"]);
+    }
+
+    #[test]
+    fn test_macro_redefined() {
+        assert_eq!(
+            commands("def a { up 1 } a def a { down 1 } a"),
+            ["1: up 1", "1: down 1"]
+        );
+    }
+
+    #[test]
+    fn test_unknown_command() {
+        assert_eq!(
+            error("forward 1\n  sideways 2"),
+            "Line 2, column 3: Unknown command or macro \"sideways\""
+        );
+    }
+
+    #[test]
+    fn test_missing_distance() {
+        assert_eq!(
+            error("down\n"),
+            "Line 1, column 1: Missing distance after down"
+        );
+    }
+
+    #[test]
+    fn test_invalid_distance() {
+        assert_eq!(
+            error("forward x"),
+            "Line 1, column 9: Invalid distance \"x\": invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn test_invalid_repeat_count() {
+        assert_eq!(
+            error("repeat -1 { }"),
+            "Line 1, column 8: Invalid repeat count \"-1\": invalid digit found in string"
+        );
+        assert_eq!(
+            error("repeat"),
+            "Line 1, column 7: Expected a repeat count, found the end of the input"
+        );
+    }
+
+    #[test]
+    fn test_unclosed_block() {
+        assert_eq!(
+            error("repeat 2 {\n forward 1"),
+            "Line 1, column 10: Unclosed {"
+        );
+    }
+
+    #[test]
+    fn test_unexpected_close() {
+        assert_eq!(error("up 1 }"), "Line 1, column 6: Unexpected }");
+    }
+
+    #[test]
+    fn test_stray_open() {
+        assert_eq!(
+            error("up 1 {"),
+            "Line 1, column 6: Expected a command, found \"{\""
+        );
+    }
+
+    #[test]
+    fn test_reserved_macro_name() {
+        assert_eq!(
+            error("def up { }"),
+            "Line 1, column 5: Can't define \"up\", it's a direction or keyword"
+        );
+        assert!(error("def repeat { }")
+            .ends_with("Can't define \"repeat\", it's a direction or keyword"));
+    }
+
+    #[test]
+    fn test_macro_needs_a_body() {
+        assert!(error("def dive").ends_with("Expected {, found the end of the input"));
+        assert!(error("def").ends_with("Expected a macro name, found the end of the input"));
+    }
+
+    #[test]
+    fn test_too_long() {
+        assert_eq!(
+            error("repeat 2000 { repeat 1000 { up 1 } }"),
+            "Line 1, column 1: Expands to more than 1000000 commands"
+        );
+        assert!(error(&format!("repeat {} {{ up 1 }}", usize::MAX)).ends_with("commands"));
+    }
+
+    #[test]
+    fn test_macro_bodies_count_towards_the_limit() {
+        // Each macro is 1000 times the last but none is ever called
+        let source = "def a { repeat 1000 { up 1 } }\n\
+                      def b { repeat 1000 { a } }\n\
+                      def c { repeat 1000 { b } }\n";
+        assert_eq!(parse(source).unwrap_err().problem, Problem::TooLong);
+    }
+
+    #[test]
+    fn test_nesting_limit() {
+        let nested =
+            |depth: usize| format!("{}up 1{}", "repeat 1 { ".repeat(depth), " }".repeat(depth));
+        // The top level is one of the MAX_NESTING blocks
+        assert_eq!(parse(&nested(MAX_NESTING - 1)).unwrap().len(), 1);
+        assert!(error(&nested(MAX_NESTING)).ends_with("Nested more than 64 deep"));
+        assert!(error(&"repeat 1 {".repeat(100_000)).ends_with("Nested more than 64 deep"));
+    }
+
+    #[test]
+    fn test_parse_rejects_includes() {
+        assert_eq!(
+            error("up 1\ninclude \"/etc/passwd\""),
+            "Line 2, column 9: Can't include /etc/passwd without a file to resolve it against"
+        );
+    }
+
+    #[test]
+    fn test_include_needs_a_quoted_path() {
+        let files = Files::new("unquoted", &[("plan.sub", "include moves")]);
+        assert!(matches!(
+            files.load("plan.sub").unwrap_err().problem,
+            Problem::Expected { .. }
+        ));
+    }
+
+    #[test]
+    fn test_include_commands_are_at_the_include() {
+        let files = Files::new(
+            "include",
+            &[
+                ("lib/moves.sub", "def dive { down 2 }\nforward 1\n"),
+                ("plan.sub", "up 1\ninclude \"lib/moves.sub\"\ndive\n"),
+            ],
+        );
+        let lines: Vec<(usize, String)> = files
+            .load("plan.sub")
+            .unwrap()
+            .into_iter()
+            .map(|(line, command)| (line, command.to_string()))
+            .collect();
+        // The included file's macros are defined after it
+        assert_eq!(
+            lines,
+            [
+                (1, String::from("up 1")),
+                (2, String::from("forward 1")),
+                (3, String::from("down 2"))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_include_path_keeps_a_hash() {
+        let files = Files::new(
+            "hash",
+            &[
+                ("moves #1.sub", "forward 1\n"),
+                ("plan.sub", "include \"moves #1.sub\" # the first moves\n"),
+            ],
+        );
+        assert_eq!(files.load("plan.sub").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_include_cycle() {
+        let files = Files::new("cycle", &[("lib/loop.sub", "include \"loop.sub\"\n")]);
+        let error = files.load("lib/loop.sub").unwrap_err();
+        assert_eq!(
+            error.problem,
+            Problem::IncludeCycle(files.0.join("lib/loop.sub").display().to_string())
+        );
+        assert_eq!((error.line, error.column), (1, 9));
+    }
+
+    #[test]
+    fn test_problem_in_an_include_is_at_its_own_line() {
+        let files = Files::new(
+            "broken",
+            &[
+                ("lib/broken.sub", "\nforward\n"),
+                ("plan.sub", "include \"lib/broken.sub\""),
+            ],
+        );
+        let error = files.load("plan.sub").unwrap_err();
+        assert_eq!(error.file, Some(files.0.join("lib/broken.sub")));
+        assert!(error
+            .to_string()
+            .ends_with("broken.sub: Line 2, column 1: Missing distance after forward"));
+    }
+
+    #[test]
+    fn test_missing_include() {
+        let files = Files::new("missing", &[("plan.sub", "include \"no/such/file.sub\"")]);
+        assert!(matches!(
+            files.load("plan.sub").unwrap_err().problem,
+            Problem::Include { .. }
+        ));
+    }
+
+    #[test]
+    fn test_expand_file() {
+        let files = Files::new(
+            "expand",
+            &[
+                ("moves.sub", "def dive { down 2 }"),
+                (
+                    "plan.sub",
+                    "include \"moves.sub\"\nrepeat 2 { dive }\nforward 3",
+                ),
+            ],
+        );
+        let path = files.0.join("plan.sub");
+        let expanded = expand_file(&fs::read_to_string(&path).unwrap(), &path).unwrap();
+        assert_eq!(expanded, "down 2\ndown 2\nforward 3\n");
+    }
+}
@@ -0,0 +1,428 @@
+// The sub's path through the water, one point per command, for code and the CLI's --trajectory.
+// It exports as CSV for spreadsheets and as an SVG side view, with distance along the top and
+// depth growing downwards, and sums up as the deepest point and how far the sub travelled.
+
+use std::fmt::Write;
+
+use super::{parse_lines, MovementModel, Position, SubCommand, Submarine};
+
+// Where the sub was after the command on `line`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub line: usize,
+    pub command: SubCommand,
+    pub position: Position,
+}
+
+// The steps in order, starting from wherever the sub was before the first of them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trajectory {
+    pub start: Position,
+    pub steps: Vec<Step>,
+}
+
+impl Trajectory {
+    // Runs the commands through `submarine`, which is left where the last of them took it, or
+    // the line of the command that would overflow its position
+    pub fn record<M: MovementModel>(
+        submarine: &mut Submarine<M>,
+        commands: &[(usize, SubCommand)],
+    ) -> Result<Trajectory, String> {
+        let start = submarine.position();
+        let steps = commands
+            .iter()
+            .map(|&(line, command)| {
+                submarine
+                    .apply(&command)
+                    .map_err(|e| format!("Line {}: {}", line, e))?;
+                Ok(Step {
+                    line,
+                    command,
+                    position: submarine.position(),
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Trajectory { start, steps })
+    }
+
+    // The start then the position after each step
+    pub fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+        std::iter::once(self.start).chain(self.steps.iter().map(|step| step.position))
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("line,command,horizontal,depth,aim\n");
+        for step in &self.steps {
+            writeln!(
+                csv,
+                "{},{},{},{},{}",
+                step.line,
+                step.command,
+                step.position.horizontal,
+                step.position.depth,
+                step.position.aim
+            )
+            .unwrap();
+        }
+        csv
+    }
+
+    pub fn summary(&self) -> Summary {
+        let mut summary = Summary {
+            commands: self.steps.len(),
+            end: self.start,
+            max_depth: self.start.depth,
+            deepest: None,
+            horizontal_travel: 0,
+            vertical_travel: 0,
+            path_length: 0.0,
+        };
+        for step in &self.steps {
+            let (from, to) = (summary.end, step.position);
+            let across = (to.horizontal as i64 - from.horizontal as i64).abs();
+            let down = (to.depth as i64 - from.depth as i64).abs();
+            summary.horizontal_travel += across;
+            summary.vertical_travel += down;
+            summary.path_length += (across as f64).hypot(down as f64);
+            // The first time it got that deep, if it stayed there a while
+            if to.depth > summary.max_depth {
+                summary.max_depth = to.depth;
+                summary.deepest = Some(*step);
+            }
+            summary.end = to;
+        }
+        summary
+    }
+}
+
+// The headline numbers of a trajectory
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub commands: usize,
+    pub end: Position,
+    pub max_depth: i32,
+    // The step that first reached the maximum depth, or None if the sub never got deeper than
+    // where it started
+    pub deepest: Option<Step>,
+    pub horizontal_travel: i64,
+    pub vertical_travel: i64,
+    // Straight line distance along the path, since the aimed sub dives at an angle
+    pub path_length: f64,
+}
+
+impl Summary {
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+        writeln!(
+            output,
+            "{} commands, ending at horizontal {}, depth {}, aim {}",
+            self.commands, self.end.horizontal, self.end.depth, self.end.aim
+        )
+        .unwrap();
+        match self.deepest {
+            Some(step) => writeln!(
+                output,
+                "Deepest: {} at horizontal {}, reached by {:?} on line {}",
+                self.max_depth,
+                step.position.horizontal,
+                step.command.to_string(),
+                step.line
+            ),
+            None => writeln!(output, "Deepest: {} at the start", self.max_depth),
+        }
+        .unwrap();
+        writeln!(
+            output,
+            "Travelled {} horizontally and {} vertically, {:.1} along the path",
+            self.horizontal_travel, self.vertical_travel, self.path_length
+        )
+        .unwrap();
+        output
+    }
+}
+
+// Records `model` running the input's commands from the surface
+pub fn record<M: MovementModel>(model: M, input: &str) -> Result<Trajectory, String> {
+    let commands = parse_lines(input)?;
+    Trajectory::record(&mut Submarine::new(model), &commands)
+}
+
+const SVG_WIDTH: f64 = 900.0;
+const SVG_HEIGHT: f64 = 400.0;
+const SVG_MARGIN: f64 = 50.0;
+const SURFACE: &str = "#90caf9";
+const PATH: &str = "#1565c0";
+const DEEPEST: &str = "#c62828";
+
+// A side view of the path, with the surface drawn in when it's in view and the deepest point marked
+pub fn render_svg(trajectory: &Trajectory) -> String {
+    let summary = trajectory.summary();
+    let (left, top) = (SVG_MARGIN, SVG_MARGIN);
+    let (width, height) = (SVG_WIDTH - 2.0 * SVG_MARGIN, SVG_HEIGHT - 2.0 * SVG_MARGIN);
+    let range = |values: Vec<f64>| {
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        (min, if max > min { max - min } else { 1.0 })
+    };
+    let (min_x, span_x) = range(
+        trajectory
+            .positions()
+            .map(|position| position.horizontal as f64)
+            .collect(),
+    );
+    let (min_y, span_y) = range(
+        trajectory
+            .positions()
+            .map(|position| position.depth as f64)
+            .collect(),
+    );
+    let x = |horizontal: i32| left + (horizontal as f64 - min_x) / span_x * width;
+    let y = |depth: i32| top + (depth as f64 - min_y) / span_y * height;
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+         viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\" font-size=\"12\">",
+        w = SVG_WIDTH,
+        h = SVG_HEIGHT
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        "<text x=\"{}\" y=\"{}\" font-weight=\"bold\">Depth against horizontal position, \
+         {} commands</text>",
+        left,
+        top - 20.0,
+        summary.commands
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"#e0e0e0\"/>",
+        left, top, width, height
+    )
+    .unwrap();
+    let (shallowest, deepest) = (min_y as i32, (min_y + span_y) as i32);
+    if shallowest <= 0 && deepest >= 0 {
+        writeln!(
+            svg,
+            "<line x1=\"{}\" y1=\"{:.1}\" x2=\"{}\" y2=\"{:.1}\" stroke=\"{}\" \
+             stroke-dasharray=\"4 4\"/>",
+            left,
+            y(0),
+            left + width,
+            y(0),
+            SURFACE
+        )
+        .unwrap();
+    }
+    for (depth, label_y) in [(shallowest, top + 4.0), (deepest, top + height)] {
+        writeln!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\">{}</text>",
+            left - 4.0,
+            label_y,
+            depth
+        )
+        .unwrap();
+    }
+    for (horizontal, anchor) in [(min_x as i32, "start"), ((min_x + span_x) as i32, "end")] {
+        writeln!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"{}\">{}</text>",
+            x(horizontal),
+            top + height + 16.0,
+            anchor,
+            horizontal
+        )
+        .unwrap();
+    }
+
+    let points: Vec<String> = trajectory
+        .positions()
+        .map(|position| format!("{:.1},{:.1}", x(position.horizontal), y(position.depth)))
+        .collect();
+    writeln!(
+        svg,
+        "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"1.5\"/>",
+        points.join(" "),
+        PATH
+    )
+    .unwrap();
+    if let Some(step) = summary.deepest {
+        let (cx, cy) = (x(step.position.horizontal), y(step.position.depth));
+        writeln!(
+            svg,
+            "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"4\" fill=\"{}\"/>",
+            cx, cy, DEEPEST
+        )
+        .unwrap();
+        // Labelled on whichever side has room
+        let (dx, anchor) = match cx > left + width / 2.0 {
+            true => (-8.0, "end"),
+            false => (8.0, "start"),
+        };
+        writeln!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"{}\" fill=\"{}\">Deepest {} on line \
+             {}</text>",
+            cx + dx,
+            cy - 8.0,
+            anchor,
+            DEEPEST,
+            summary.max_depth,
+            step.line
+        )
+        .unwrap();
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Aimed, Simple};
+    use super::*;
+
+    const EXAMPLE: &str = "forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2\n";
+
+    fn aimed() -> Trajectory {
+        record(Aimed::default(), EXAMPLE).unwrap()
+    }
+
+    fn points(svg: &str) -> Vec<&str> {
+        let polyline = svg.split("<polyline points=\"").nth(1).unwrap();
+        polyline.split('"').next().unwrap().split(' ').collect()
+    }
+
+    #[test]
+    fn test_record() {
+        let trajectory = aimed();
+        assert_eq!(trajectory.start, Position::default());
+        assert_eq!(trajectory.steps.len(), 6);
+        assert_eq!(
+            trajectory.steps[2].position,
+            Position {
+                horizontal: 13,
+                depth: 40,
+                aim: 5
+            }
+        );
+        assert_eq!(trajectory.positions().count(), 7);
+    }
+
+    #[test]
+    fn test_record_reports_the_bad_line() {
+        assert!(record(Simple::default(), "forward 1\nsideways 2\n")
+            .unwrap_err()
+            .starts_with("Line 2, column 1:"));
+    }
+
+    #[test]
+    fn test_record_reports_the_overflowing_line() {
+        assert_eq!(
+            record(Aimed::default(), "down 100000\nforward 1\nforward 100000\n"),
+            Err(String::from(
+                "Line 3: Overflow, the sub went further than an i32 holds"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_csv() {
+        let csv = aimed().to_csv();
+        assert_eq!(
+            csv.lines().next(),
+            Some("line,command,horizontal,depth,aim")
+        );
+        assert_eq!(csv.lines().nth(3), Some("3,forward 8,13,40,5"));
+        assert_eq!(csv.lines().count(), 7);
+    }
+
+    #[test]
+    fn test_summary() {
+        let summary = aimed().summary();
+        assert_eq!(summary.max_depth, 60);
+        assert_eq!(summary.deepest.map(|step| step.line), Some(6));
+        assert_eq!(summary.end.horizontal * summary.end.depth, 900);
+        assert_eq!(
+            (summary.horizontal_travel, summary.vertical_travel),
+            (15, 60)
+        );
+    }
+
+    #[test]
+    fn test_path_length_follows_the_dive() {
+        // Forward 8 at aim 5 dives along a 8 by 40 slope
+        let slope = 8f64.hypot(40.0) + 2f64.hypot(20.0);
+        assert!((aimed().summary().path_length - (5.0 + slope)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_simple_sub_travels_up_and_down() {
+        // The simple sub is deepest after the last down, and up counts as vertical travel too
+        let summary = record(Simple::default(), EXAMPLE).unwrap().summary();
+        assert_eq!(summary.deepest.map(|step| step.line), Some(5));
+        assert_eq!((summary.max_depth, summary.vertical_travel), (10, 16));
+    }
+
+    #[test]
+    fn test_render_summary() {
+        assert!(aimed()
+            .summary()
+            .render()
+            .contains("Deepest: 60 at horizontal 15, reached by \"forward 2\" on line 6"));
+    }
+
+    #[test]
+    fn test_never_deeper_than_the_start() {
+        let surfaced = record(Simple::default(), "up 3\nforward 4\n").unwrap();
+        assert_eq!(surfaced.summary().deepest, None);
+        assert!(surfaced
+            .summary()
+            .render()
+            .contains("Deepest: 0 at the start"));
+    }
+
+    #[test]
+    fn test_svg() {
+        let svg = render_svg(&aimed());
+        assert!(svg.starts_with("<svg ") && svg.ends_with("</svg>\n"));
+        // The start and a point per command
+        assert_eq!(points(&svg).len(), 7);
+        assert!(svg.contains("Deepest 60 on line 6"));
+        assert!(svg.contains(SURFACE));
+    }
+
+    #[test]
+    fn test_svg_of_one_point() {
+        // No commands leaves just the start, which has no extent to scale by
+        let svg = render_svg(&record(Simple::default(), "").unwrap());
+        assert!(svg.ends_with("</svg>\n") && !svg.contains("<circle"));
+        assert_eq!(points(&svg), ["50.0,50.0"]);
+        assert!(!svg.contains("NaN") && !svg.contains("inf"));
+    }
+
+    #[test]
+    fn test_svg_above_the_surface() {
+        let svg = render_svg(&record(Simple::default(), "up 3\nforward 4\n").unwrap());
+        // Depth -3 is at the top, the surface at the bottom, and nothing is deeper than the start
+        assert_eq!(points(&svg), ["50.0,350.0", "50.0,50.0", "850.0,50.0"]);
+        assert!(svg.contains(">-3</text>") && svg.contains(">0</text>"));
+        assert!(svg.contains(SURFACE) && !svg.contains("<circle"));
+    }
+
+    #[test]
+    fn test_svg_leaves_out_the_surface_out_of_view() {
+        let trajectory = Trajectory {
+            start: Position {
+                horizontal: 0,
+                depth: 10,
+                aim: 0,
+            },
+            steps: Vec::new(),
+        };
+        assert!(!render_svg(&trajectory).contains(SURFACE));
+    }
+}
@@ -2,6 +2,28 @@ use std::num::ParseIntError;
 
 use ndarray::prelude::*;
 use ndarray::OwnedRepr;
+use thiserror::Error;
+
+use crate::pool;
+
+// Both rates have to fit in a u32 once multiplied together
+const MAX_WIDTH: usize = 16;
+
+#[derive(Debug, Error)]
+pub enum InputParseError {
+    #[error("No Input")]
+    NoInput,
+    #[error("Invalid bit {found:?} on line {line}")]
+    InvalidBit { line: usize, found: char },
+    #[error("Line {line} has {found} bits but the first line has {expected}")]
+    InconsistentWidth {
+        line: usize,
+        found: usize,
+        expected: usize,
+    },
+    #[error("Numbers wider than {MAX_WIDTH} bits are not supported (found {0})")]
+    TooWide(usize),
+}
 
 #[derive(Debug)]
 enum BitCriteria {
@@ -17,7 +39,11 @@ enum SignificantBit {
 }
 
 pub fn part2(input: &str) -> u32 {
-    let array_input = parse_input2(input);
+    try_part2(input).unwrap()
+}
+
+pub fn try_part2(input: &str) -> Result<u32, InputParseError> {
+    let array_input = parse_input2(input)?;
 
     // Getting Oxygen Generator
     // Start with the first column, and find the most significant bit
@@ -44,16 +70,11 @@ pub fn part2(input: &str) -> u32 {
     let co2_generator_rating =
         convert_bit_string_to_u32(co2_generator_rating_str.as_str()).unwrap();
 
-    let life_support_rating = oxygen_generator_rating * co2_generator_rating;
-
-    return life_support_rating;
+    Ok(oxygen_generator_rating * co2_generator_rating)
 }
 
 fn convert_bit_string_to_u32(array: &str) -> Result<u32, ParseIntError> {
-    match u32::from_str_radix(array, 2) {
-        Ok(num) => Ok(num),
-        Err(e) => Err(e),
-    }
+    u32::from_str_radix(array, 2)
 }
 
 fn convert_bit_vector_to_string(array: Vec<u8>) -> String {
@@ -68,15 +89,15 @@ fn convert_bit_vector_to_string(array: Vec<u8>) -> String {
         .join("")
 }
 
-fn filter_by_criteria(array: &Vec<Vec<u8>>, criteria: BitCriteria) -> Vec<Vec<u8>> {
-    let mut filtered_array = array.clone();
+fn filter_by_criteria(array: &[Vec<u8>], criteria: BitCriteria) -> Vec<Vec<u8>> {
+    let mut filtered_array = array.to_vec();
 
     // Loop through the array columns, remove those that don't match criteria until no columns remain or only 1 row is left.
     let mut bit_position = 0;
 
     while filtered_array.len() > 1 {
         // Make sure we also haven't already filtered through all the bits
-        if bit_position > filtered_array[0].len() {
+        if bit_position >= filtered_array[0].len() {
             break;
         }
 
@@ -89,7 +110,7 @@ fn filter_by_criteria(array: &Vec<Vec<u8>>, criteria: BitCriteria) -> Vec<Vec<u8
         );
 
         // Iterate through rows in array and if the bit position doesn't match the critera then drop the row.
-        filtered_array = filtered_array
+        let remaining: Vec<Vec<u8>> = filtered_array
             .iter()
             .filter(|column| match criteria {
                 BitCriteria::MostCommon => {
@@ -113,10 +134,14 @@ fn filter_by_criteria(array: &Vec<Vec<u8>>, criteria: BitCriteria) -> Vec<Vec<u8
             })
             .map(|row| row.to_owned())
             .collect();
+        // If every remaining row shares this bit there is no least common value, so keep them all
+        if !remaining.is_empty() {
+            filtered_array = remaining;
+        }
         bit_position += 1
     }
 
-    return filtered_array;
+    filtered_array
 }
 
 fn get_significant_bit(array: Vec<u8>) -> SignificantBit {
@@ -140,97 +165,101 @@ fn get_significant_bit(array: Vec<u8>) -> SignificantBit {
 }
 
 pub fn part1(input: &str) -> u32 {
+    try_part1(input).unwrap()
+}
+
+pub fn try_part1(input: &str) -> Result<u32, InputParseError> {
     // Input is a x position binary string
     // Parse the input into  2 dimensional array (using ndarray)
-    let array_input = parse_input(input);
+    let array_input = parse_input(input)?;
 
     let mut episilon_rate_str = String::new();
     // Count the 1's in each vector tuples in each position .filter.count maybe?
 
     let array_rows = array_input.shape()[0];
 
-    array_input.axis_iter(Axis(1)).for_each(|column| {
-        if column.iter().filter(|&&x| x == 1).count() > (array_rows / 2) as usize {
-            episilon_rate_str.push_str("1")
+    // Each column is counted separately, so they're shared out with --jobs
+    let columns: Vec<usize> = (0..array_input.shape()[1]).collect();
+    let ones = pool::map(pool::jobs(), &columns, |&column| {
+        array_input
+            .index_axis(Axis(1), column)
+            .iter()
+            .filter(|&&x| x == 1)
+            .count()
+    });
+    ones.iter().for_each(|&count| {
+        if count > (array_rows / 2) {
+            episilon_rate_str.push('1')
         } else {
-            episilon_rate_str.push_str("0")
+            episilon_rate_str.push('0')
         }
     });
 
     let episilon_rate = u32::from_str_radix(episilon_rate_str.as_str(), 2).unwrap();
     let gamma_rate =
         u32::from_str_radix(binary_string_flip(&episilon_rate_str).as_str(), 2).unwrap();
-    episilon_rate * gamma_rate
+    Ok(episilon_rate * gamma_rate)
 }
 
 fn binary_string_flip(input: &str) -> String {
     let mut output = String::new();
     for c in input.chars() {
         if c == '0' {
-            output.push_str("1")
+            output.push('1')
         } else {
-            output.push_str("0")
+            output.push('0')
         }
     }
-    return output;
+    output
 }
 
-fn parse_input(input: &str) -> ArrayBase<OwnedRepr<u8>, Dim<[usize; 2]>> {
-    //
-
-    let lines = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '0' => 0b0,
-                    '1' => 0b1,
-                    _ => panic!("Invalid input"),
-                })
-                .collect::<Vec<u8>>()
-        })
-        .collect::<Vec<Vec<u8>>>();
+fn parse_input(input: &str) -> Result<ArrayBase<OwnedRepr<u8>, Dim<[usize; 2]>>, InputParseError> {
+    let lines = parse_input2(input)?;
     let length_rows = lines.len();
     let length_columns = lines[0].len();
-    println!("Rows: {}", length_rows);
-    println!("Columns: {}", length_columns);
-    // println!("{:?}", lines);
     let mut input_array = Array2::zeros((length_rows, length_columns));
     for (i, row) in lines.iter().enumerate() {
         for (j, col) in row.iter().enumerate() {
             input_array[[i, j]] = *col;
         }
     }
-    input_array
+    Ok(input_array)
 }
 
-fn parse_input2(input: &str) -> Vec<Vec<u8>> {
-    //
-
-    let lines = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '0' => 0b0,
-                    '1' => 0b1,
-                    _ => panic!("Invalid input"),
-                })
-                .collect::<Vec<u8>>()
-        })
-        .collect::<Vec<Vec<u8>>>();
-    // let length_rows = lines.len();
-    // let length_columns = lines[0].len();
-    // println!("Rows: {}", length_rows);
-    // println!("Columns: {}", length_columns);
-    // // println!("{:?}", lines);
-    // let mut input_array = Array2::zeros((length_rows, length_columns));
-    // for (i, row) in lines.iter().enumerate() {
-    //     for (j, col) in row.iter().enumerate() {
-    //         input_array[[i, j]] = *col;
-    //     }
-    // }
-    lines
+// Parses the report into rows of bits, checking every row has the same (supported) width
+fn parse_input2(input: &str) -> Result<Vec<Vec<u8>>, InputParseError> {
+    let mut lines: Vec<Vec<u8>> = Vec::new();
+
+    for (i, line) in input.lines().enumerate() {
+        let row = line
+            .chars()
+            .map(|c| match c {
+                '0' => Ok(0b0),
+                '1' => Ok(0b1),
+                _ => Err(InputParseError::InvalidBit {
+                    line: i + 1,
+                    found: c,
+                }),
+            })
+            .collect::<Result<Vec<u8>, InputParseError>>()?;
+
+        if let Some(first) = lines.first() {
+            if first.len() != row.len() {
+                return Err(InputParseError::InconsistentWidth {
+                    line: i + 1,
+                    found: row.len(),
+                    expected: first.len(),
+                });
+            }
+        }
+        lines.push(row);
+    }
+
+    match lines.first().map(|row| row.len()) {
+        None | Some(0) => Err(InputParseError::NoInput),
+        Some(width) if width > MAX_WIDTH => Err(InputParseError::TooWide(width)),
+        Some(_) => Ok(lines),
+    }
 }
 
 #[cfg(test)]
@@ -282,6 +311,31 @@ mod tests {
         let expected_result = 230;
         assert_eq!(result, expected_result);
     }
+
+    #[test]
+    fn prop_part1_unchanged_by_flipping_every_bit() {
+        use crate::testing::prop::{self, ints, vecs};
+
+        // With an odd number of rows there are no ties, so flipping every bit just swaps the
+        // gamma and epsilon rates
+        prop::check(
+            &(ints(1..=12), vecs(ints(0..=4095), 1..=15)),
+            |(width, rows)| {
+                let rows = if rows.len() % 2 == 0 {
+                    &rows[1..]
+                } else {
+                    &rows[..]
+                };
+                let width = *width as usize;
+                let render = |flip: u64| {
+                    rows.iter()
+                        .map(|row| format!("{:0width$b}\n", (row ^ flip) & ((1 << width) - 1)))
+                        .collect::<String>()
+                };
+                part1(&render(0)) == part1(&render(u64::MAX))
+            },
+        );
+    }
 }
 
 // --- Part Two ---
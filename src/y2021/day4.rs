@@ -0,0 +1,405 @@
+use std::num::ParseIntError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum InputParseError {
+    #[error("No Input")]
+    NoInput,
+    #[error("Unable to Parse input as i32")]
+    ParseIntError(#[from] ParseIntError),
+    #[error("Board row on line {0} does not have 5 numbers")]
+    InvalidRow(usize),
+    #[error("Board ending on line {0} is not full")]
+    IncompleteBoard(usize),
+    #[error("Board on line {0} has more than 5 rows")]
+    OversizedBoard(usize),
+    #[error("Overflow, the winning score is too big for an i32")]
+    Overflow,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct BoardEntry {
+    value: i32,
+    marked: bool,
+}
+
+impl BoardEntry {
+    pub fn new(value: i32) -> Self {
+        Self {
+            value,
+            marked: false,
+        }
+    }
+    pub fn mark(&mut self) {
+        self.marked = true;
+    }
+
+    pub fn is_marked(&self) -> bool {
+        self.marked
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct BoardRow {
+    entries: [BoardEntry; 5], // Should only contain 5 entries per row
+}
+
+impl BoardRow {
+    fn new(entries: [BoardEntry; 5]) -> Self {
+        Self { entries }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct GameBoard {
+    rows: [BoardRow; 5],
+    won: bool,
+    winning_number: i32,
+}
+
+impl GameBoard {
+    fn new(rows: [BoardRow; 5]) -> Self {
+        Self {
+            rows,
+            won: false,
+            winning_number: 0,
+        }
+    }
+
+    // Marks number on the board)
+    fn call_number(&mut self, number: i32) {
+        if !self.won {
+            for row in &mut self.rows {
+                for entry in &mut row.entries {
+                    if entry.value == number {
+                        entry.mark();
+                    }
+                }
+            }
+        }
+    }
+
+    fn has_won(&mut self, number: i32) -> bool {
+        if self.won {
+            return true;
+        }
+
+        let mut win_state: bool = false;
+        // Check Columns - returns true if there are 5 marked numbers in the column
+        for i in 0..5 {
+            let mut marked_count = 0;
+            for row in &self.rows {
+                if row.entries[i].is_marked() {
+                    marked_count += 1;
+                }
+            }
+            if marked_count == 5 {
+                win_state = true;
+            }
+        }
+
+        // Check Rows - returns true if there are 5 marked numbers in at least one row or returns false
+        if !win_state {
+            win_state = self
+                .rows
+                .iter()
+                .filter(|row| row.entries.iter().filter(|entry| entry.is_marked()).count() == 5)
+                .count()
+                >= 1;
+        }
+
+        if win_state {
+            self.won = win_state;
+            self.winning_number = number;
+        }
+
+        self.won
+    }
+
+    // Sum unmarked numbers * last drawn number, or an error if huge numbers overflow
+    fn calculate_score(&self) -> Result<i32, InputParseError> {
+        let mut unmarked_tally: i32 = 0;
+        for row in &self.rows {
+            for entry in &row.entries {
+                if !entry.is_marked() {
+                    unmarked_tally = unmarked_tally
+                        .checked_add(entry.value)
+                        .ok_or(InputParseError::Overflow)?;
+                }
+            }
+        }
+        unmarked_tally
+            .checked_mul(self.winning_number)
+            .ok_or(InputParseError::Overflow)
+    }
+}
+
+struct Game {
+    boards: Vec<GameBoard>,
+    numbers_to_call: Vec<i32>,
+}
+
+impl Game {
+    fn new(boards: Vec<GameBoard>, numbers_to_call: Vec<i32>) -> Self {
+        Self {
+            boards,
+            numbers_to_call,
+        }
+    }
+}
+
+pub fn part2(input: &str) -> Option<i32> {
+    try_part2(input).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        None
+    })
+}
+
+pub fn try_part2(input: &str) -> Result<Option<i32>, InputParseError> {
+    // Process Input into Numbers to be drawn and boards
+    let mut game = parse_input(input)?;
+    let mut indexes_to_remove: Vec<usize> = Vec::new();
+    let mut last_board_to_win: Option<GameBoard> = None;
+
+    // Call Numbers
+    for number in game.numbers_to_call {
+        for (i, board) in &mut game.boards.iter_mut().enumerate() {
+            // Mark Boards
+            board.call_number(number);
+            // Check for Victory
+            if board.has_won(number) {
+                // If victory calculate and return score - Sum unmarked numbers * last drawn number
+                last_board_to_win = Some(*board);
+                indexes_to_remove.push(i.to_owned());
+            }
+        }
+        // Remove winning boards
+        for index in indexes_to_remove.iter().rev() {
+            game.boards.remove(*index);
+        }
+        indexes_to_remove.clear();
+    }
+    // If no victory return None
+    last_board_to_win
+        .map(|board| board.calculate_score())
+        .transpose()
+}
+
+pub fn part1(input: &str) -> Option<i32> {
+    try_part1(input).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        None
+    })
+}
+
+pub fn try_part1(input: &str) -> Result<Option<i32>, InputParseError> {
+    // Process Input into Numbers to be drawn and boards
+    let mut game = parse_input(input)?;
+
+    // Call Numbers
+    for number in game.numbers_to_call {
+        for board in &mut game.boards {
+            // Mark Boards
+            board.call_number(number);
+            // Check for Victory
+            if board.has_won(number) {
+                // If victory calculate and return score - Sum unmarked numbers * last drawn number
+                return board.calculate_score().map(Some);
+            }
+        }
+    }
+    // If no victory return None
+    Ok(None)
+}
+
+fn parse_input(input: &str) -> Result<Game, InputParseError> {
+    // line 1 -> numbers to be called.
+    // line 3-8 -> board rows
+    // line 10-15 -> board rows etc
+    let mut lines = input.lines().enumerate();
+    let numbers_to_call: Vec<i32> = match lines.next() {
+        Some((_, line)) => line
+            .split(',')
+            .map(|s| s.trim().parse::<i32>())
+            .collect::<Result<Vec<i32>, ParseIntError>>()?,
+        None => return Err(InputParseError::NoInput),
+    };
+
+    let mut temp_games_holder: Vec<GameBoard> = Vec::new();
+    let mut temp_board_holder: Vec<BoardRow> = Vec::new();
+
+    for (i, line) in lines {
+        if line.trim().is_empty() {
+            // Board Seperator
+            match temp_board_holder.len() {
+                0 => {}
+                5 => {
+                    temp_games_holder.push(GameBoard::new(
+                        temp_board_holder.clone().try_into().unwrap(),
+                    ));
+                    temp_board_holder.clear();
+                }
+                _ => return Err(InputParseError::IncompleteBoard(i + 1)),
+            }
+        } else if temp_board_holder.len() == 5 {
+            return Err(InputParseError::OversizedBoard(i + 1));
+        } else {
+            // Board row
+            let row = line
+                .split_whitespace()
+                .map(|s| s.parse::<i32>().map(BoardEntry::new))
+                .collect::<Result<Vec<BoardEntry>, ParseIntError>>()?;
+            let row: [BoardEntry; 5] = row
+                .try_into()
+                .map_err(|_| InputParseError::InvalidRow(i + 1))?;
+            temp_board_holder.push(BoardRow::new(row));
+        }
+    }
+    // Handle last board
+    match temp_board_holder.len() {
+        0 => {}
+        5 => {
+            temp_games_holder.push(GameBoard::new(
+                temp_board_holder.clone().try_into().unwrap(),
+            ));
+            temp_board_holder.clear();
+        }
+        _ => return Err(InputParseError::IncompleteBoard(input.lines().count())),
+    }
+
+    let game = Game::new(temp_games_holder, numbers_to_call);
+    Ok(game)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input: String = String::from(
+            "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1\n\
+            \n\
+            22 13 17 11  0\n\
+             8  2 23  4 24\n\
+            21  9 14 16  7\n\
+             6 10  3 18  5\n\
+             1 12 20 15 19\n\
+            \n\
+             3 15  0  2 22\n\
+             9 18 13 17  5\n\
+            19  8  7 25 23\n\
+            20 11 10 24  4\n\
+            14 21 16 12  6\n\
+            \n\
+            14 21 17 24  4\n\
+            10 16 15  9 19\n\
+            18  8 23 26 20\n\
+            22 11 13  6  5\n\
+             2  0 12  3  7\n\
+        ",
+        );
+
+        let result = part1(&input);
+        let expected_result = Some(4512);
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input: String = String::from(
+            "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1\n\
+            \n\
+            22 13 17 11  0\n\
+             8  2 23  4 24\n\
+            21  9 14 16  7\n\
+             6 10  3 18  5\n\
+             1 12 20 15 19\n\
+            \n\
+             3 15  0  2 22\n\
+             9 18 13 17  5\n\
+            19  8  7 25 23\n\
+            20 11 10 24  4\n\
+            14 21 16 12  6\n\
+            \n\
+            14 21 17 24  4\n\
+            10 16 15  9 19\n\
+            18  8 23 26 20\n\
+            22 11 13  6  5\n\
+             2  0 12  3  7\n\
+        ",
+        );
+
+        let result = part2(&input);
+        let expected_result = Some(1924);
+        assert_eq!(result, expected_result);
+    }
+
+    // Every board that wins, in the order they win, as (index of the winning draw, score)
+    #[test]
+    fn test_score_overflow_is_an_error() {
+        // The first row wins, leaving twenty billion unmarked
+        let huge_board = "1,2,3,4,5\n\n1 2 3 4 5\n".to_string()
+            + &"1000000000 1000000000 1000000000 1000000000 1000000000\n".repeat(4);
+        assert!(matches!(
+            try_part1(&huge_board),
+            Err(InputParseError::Overflow)
+        ));
+        assert!(matches!(
+            try_part2(&huge_board),
+            Err(InputParseError::Overflow)
+        ));
+
+        // A small tally times a huge last number
+        let huge_number =
+            "1,2,3,4,2000000000\n\n1 2 3 4 2000000000\n".to_string() + &"7 7 7 7 7\n".repeat(4);
+        assert!(matches!(
+            try_part1(&huge_number),
+            Err(InputParseError::Overflow)
+        ));
+    }
+
+    fn winners(input: &str) -> Result<Vec<(usize, i32)>, InputParseError> {
+        let mut game = parse_input(input)?;
+        let mut wins = Vec::new();
+
+        for (turn, &number) in game.numbers_to_call.iter().enumerate() {
+            for board in game.boards.iter_mut().filter(|board| !board.won) {
+                board.call_number(number);
+                if board.has_won(number) {
+                    wins.push((turn, board.calculate_score()?));
+                }
+            }
+        }
+        Ok(wins)
+    }
+
+    #[test]
+    fn prop_first_winner_wins_no_later_than_last() {
+        use crate::testing::prop::{self, permutations, vecs};
+
+        // Draw order followed by boards made from the first 25 numbers of each permutation
+        prop::check(
+            &(permutations(100), vecs(permutations(100), 1..=4)),
+            |(draws, boards)| {
+                let mut input = draws
+                    .iter()
+                    .map(|n| n.to_string())
+                    .collect::<Vec<String>>()
+                    .join(",");
+                input.push('\n');
+                for board in boards {
+                    input.push('\n');
+                    for row in board[..25].chunks(5) {
+                        let row: Vec<String> = row.iter().map(|n| format!("{:>2}", n)).collect();
+                        input.push_str(&format!("{}\n", row.join(" ")));
+                    }
+                }
+
+                let wins = winners(&input).unwrap();
+                let (first, last) = (wins[0], wins[wins.len() - 1]);
+                first.0 <= last.0 && part1(&input) == Some(first.1) && part2(&input) == Some(last.1)
+            },
+        );
+    }
+}
@@ -0,0 +1,416 @@
+use std::collections::HashMap;
+use thiserror::Error;
+
+use crate::pool;
+
+// Makes it easier to reference the x,y coordinates of a point
+type MapPoint = (i32, i32);
+// A line is defined by a start point and an end point
+type MapLine = (MapPoint, MapPoint);
+
+// Most points the lines may cover between them. Coordinates can be anything an i32 holds, as
+// they always could, but a stray huge one would otherwise rasterise billions of points. Real
+// inputs cover a few hundred thousand.
+pub const MAX_POINTS: u64 = 10_000_000;
+
+#[derive(Debug, Error)]
+pub enum InputParseError {
+    #[error("No Input")]
+    NoInput,
+    #[error("Invalid input")]
+    InvalidFormat,
+    #[error("Unable to Parse input as i32")]
+    ParseIntError(#[from] std::num::ParseIntError),
+    #[error("The lines cover {0} points, more than the limit of {MAX_POINTS}")]
+    TooManyPoints(u64),
+}
+
+fn parse_point(input: &str) -> Result<MapPoint, InputParseError> {
+    match input.split_once(",") {
+        Some((x, y)) => Ok((x.trim().parse::<i32>()?, y.trim().parse::<i32>()?)),
+        None => Err(InputParseError::InvalidFormat),
+    }
+}
+
+fn process_line(input_line: &str) -> Result<MapLine, InputParseError> {
+    let split_input = input_line.split_once(" -> ");
+
+    match split_input {
+        Some((start_points, end_points)) => {
+            Ok((parse_point(start_points)?, parse_point(end_points)?))
+        }
+        None => Err(InputParseError::InvalidFormat),
+    }
+}
+
+struct VentMap {
+    data: HashMap<MapPoint, i32>,
+}
+
+impl VentMap {
+    fn new() -> Self {
+        Self {
+            data: HashMap::new(),
+        }
+    }
+
+    fn add_point(&mut self, point: MapPoint) {
+        *self.data.entry(point).or_insert(0) += 1;
+    }
+
+    fn merge(&mut self, other: VentMap) {
+        for (point, count) in other.data {
+            *self.data.entry(point).or_insert(0) += count;
+        }
+    }
+
+    fn get_danger_level(&self, level: i32) -> i32 {
+        // iterate through the values and return the number of entries that are >= level.
+        self.data.iter().filter(|(_, &v)| v >= level).count() as i32
+    }
+
+    fn line_between_points(&mut self, start: MapPoint, end: MapPoint, diagonal: bool) {
+        let (x_start, y_start) = start;
+        let (x_end, y_end) = end;
+
+        // Work out the direction of the line or None if x stays the same
+        let x_range = if x_start < x_end {
+            Some((x_start..=x_end).collect::<Vec<i32>>())
+        } else if x_start > x_end {
+            Some((x_end..=x_start).rev().collect::<Vec<i32>>())
+        } else {
+            None
+        };
+
+        // Work out the direction of the line or None if y stays the same
+        let y_range = if y_start < y_end {
+            Some((y_start..=y_end).collect::<Vec<i32>>())
+        } else if y_start > y_end {
+            Some((y_end..=y_start).rev().collect::<Vec<i32>>())
+        } else {
+            None
+        };
+
+        match x_range {
+            Some(x_range) => match y_range {
+                Some(y_range) => {
+                    if diagonal {
+                        x_range.iter().zip(y_range.iter()).for_each(|(&x, &y)| {
+                            self.add_point((x, y));
+                            // println!("Adding Point for diagonal Range{:?}", (x, y));
+                        });
+                    }
+                }
+                None => {
+                    x_range.iter().for_each(|&x| {
+                        self.add_point((x, y_start));
+                        // println!("Adding Point for Horizontal range{:?}", (x, y_start));
+                    });
+                }
+            },
+            None => {
+                match y_range {
+                    Some(y_range) => {
+                        y_range.iter().for_each(|&y| {
+                            self.add_point((x_start, y));
+                            // println!("Adding Point for Vertical range{:?}", (x_start, y));
+                        });
+                    }
+                    None => {
+                        // Add single point
+                        self.add_point(start);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Points on a line, counting both ends
+fn points_covered(((x1, y1), (x2, y2)): MapLine) -> u64 {
+    let dx = (x1 as i64 - x2 as i64).unsigned_abs();
+    let dy = (y1 as i64 - y2 as i64).unsigned_abs();
+    dx.max(dy) + 1
+}
+
+fn parse_input(input: &str) -> Result<Vec<MapLine>, InputParseError> {
+    let mut output_vector = Vec::new();
+    let mut points = 0;
+    for line in input.lines() {
+        match process_line(line) {
+            Ok(line) => {
+                points += points_covered(line);
+                output_vector.push(line)
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    if points > MAX_POINTS {
+        return Err(InputParseError::TooManyPoints(points));
+    }
+    Ok(output_vector)
+}
+
+// Rasterises the lines into one map per chunk, on up to `jobs` threads, then adds the maps up
+fn danger_level(input: &str, diagonal: bool, jobs: usize) -> Result<i32, InputParseError> {
+    let danger_threshold = 2;
+
+    // Parse input
+    let map_lines = parse_input(input)?;
+
+    let chunks = pool::chunks(jobs, &map_lines);
+    let maps = pool::map(jobs, &chunks, |chunk| {
+        let mut map = VentMap::new();
+        //iterates through all points between start points and destination point -
+        for &(start_point, end_point) in chunk.iter() {
+            map.line_between_points(start_point, end_point, diagonal);
+        }
+        map
+    });
+
+    let mut map = VentMap::new();
+    for chunk_map in maps {
+        map.merge(chunk_map);
+    }
+    Ok(map.get_danger_level(danger_threshold))
+}
+
+// Largest map render_map draws before scaling it down
+const MAP_WIDTH: i64 = 80;
+const MAP_HEIGHT: i64 = 40;
+
+// The vent map drawn like the puzzle description, each point showing how many lines cover it or
+// '.' for none ('#' past 9). Bigger maps are scaled down, each character showing the most lines
+// over any point it stands for.
+pub fn render_map(input: &str, diagonal: bool) -> Result<String, InputParseError> {
+    let mut map = VentMap::new();
+    for (start_point, end_point) in parse_input(input)? {
+        map.line_between_points(start_point, end_point, diagonal);
+    }
+    // From 0,0 like the puzzle, or from further up and left if any line goes negative
+    let min_x = map
+        .data
+        .keys()
+        .map(|&(x, _)| x as i64)
+        .min()
+        .unwrap_or(0)
+        .min(0);
+    let min_y = map
+        .data
+        .keys()
+        .map(|&(_, y)| y as i64)
+        .min()
+        .unwrap_or(0)
+        .min(0);
+    let span_x = map
+        .data
+        .keys()
+        .map(|&(x, _)| x as i64 - min_x)
+        .max()
+        .unwrap_or(0);
+    let span_y = map
+        .data
+        .keys()
+        .map(|&(_, y)| y as i64 - min_y)
+        .max()
+        .unwrap_or(0);
+    let scale = ((span_x + MAP_WIDTH) / MAP_WIDTH).max((span_y + MAP_HEIGHT) / MAP_HEIGHT);
+
+    let (width, height) = (span_x / scale + 1, span_y / scale + 1);
+    let mut grid = vec![vec![0; width as usize]; height as usize];
+    for (&(x, y), &count) in &map.data {
+        let column = (x as i64 - min_x) / scale;
+        let cell = &mut grid[((y as i64 - min_y) / scale) as usize][column as usize];
+        *cell = (*cell).max(count);
+    }
+    Ok(grid
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|&count| match count {
+                    0 => '.',
+                    1..=9 => char::from(b'0' + count as u8),
+                    _ => '#',
+                })
+                .collect::<String>()
+                + "\n"
+        })
+        .collect())
+}
+
+pub fn part1(input: &str) -> Result<i32, InputParseError> {
+    // For part 1 we ignore the diagonal lines
+    danger_level(input, false, pool::jobs())
+}
+
+pub fn part2(input: &str) -> Result<i32, InputParseError> {
+    // For part 2 we care about the diagonal lines
+    danger_level(input, true, pool::jobs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_between_points() {
+        let mut map = VentMap::new();
+        map.line_between_points((0, 6), (6, 0), true);
+
+        let result = map.data.len();
+        let expected: usize = 7;
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_part1() {
+        let input: String = String::from(
+            "\
+            0,9 -> 5,9\n\
+            8,0 -> 0,8\n\
+            9,4 -> 3,4\n\
+            2,2 -> 2,1\n\
+            7,0 -> 7,4\n\
+            6,4 -> 2,0\n\
+            0,9 -> 2,9\n\
+            3,4 -> 1,4\n\
+            0,0 -> 8,8\n\
+            5,5 -> 8,2\n\
+            ",
+        );
+
+        let result = match part1(&input) {
+            Ok(result) => result,
+            Err(e) => panic!("Error: {}", e),
+        };
+        let expected_result = 5;
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input: String = String::from(
+            "\
+            0,9 -> 5,9\n\
+            8,0 -> 0,8\n\
+            9,4 -> 3,4\n\
+            2,2 -> 2,1\n\
+            7,0 -> 7,4\n\
+            6,4 -> 2,0\n\
+            0,9 -> 2,9\n\
+            3,4 -> 1,4\n\
+            0,0 -> 8,8\n\
+            5,5 -> 8,2\n\
+            ",
+        );
+
+        let result = match part2(&input) {
+            Ok(result) => result,
+            Err(e) => panic!("Error: {}", e),
+        };
+        let expected_result = 12;
+        assert_eq!(result, expected_result);
+
+        // Splitting the lines between threads gives the same answer
+        assert_eq!(danger_level(&input, true, 3).unwrap(), expected_result);
+    }
+
+    #[test]
+    fn test_render_map() {
+        let input = std::fs::read_to_string(
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("data/2021/day5/example.txt"),
+        )
+        .unwrap();
+        // As drawn in the puzzle description
+        let expected = "\
+            1.1....11.\n\
+            .111...2..\n\
+            ..2.1.111.\n\
+            ...1.2.2..\n\
+            .112313211\n\
+            ...1.2....\n\
+            ..1...1...\n\
+            .1.....1..\n\
+            1.......1.\n\
+            222111....\n";
+        assert_eq!(render_map(&input, true).unwrap(), expected);
+
+        let scaled = render_map("0,0 -> 999,0\n0,0 -> 0,399\n", false).unwrap();
+        assert!(scaled.lines().count() <= 40);
+        assert!(scaled.lines().all(|line| line.len() <= 80));
+        assert!(scaled.starts_with("2111"));
+    }
+
+    #[test]
+    fn test_negative_coordinates() {
+        // Accepted before there was a limit on the points covered, and still are
+        let input = "-2,0 -> 2,0\n0,-2 -> 0,2\n";
+        assert_eq!(part1(input).unwrap(), 1);
+        assert_eq!(
+            render_map(input, false).unwrap(),
+            "..1..\n..1..\n11211\n..1..\n..1..\n"
+        );
+    }
+
+    #[test]
+    fn test_too_many_points() {
+        let error = part1("0,0 -> 2147483647,0\n").unwrap_err();
+        assert!(matches!(
+            error,
+            InputParseError::TooManyPoints(2_147_483_648)
+        ));
+        assert_eq!(
+            error.to_string(),
+            "The lines cover 2147483648 points, more than the limit of 10000000"
+        );
+        // The limit counts every line, not just the longest
+        let input = "0,0 -> 5999999,0\n0,1 -> 5999999,1\n";
+        assert!(matches!(
+            part2(input),
+            Err(InputParseError::TooManyPoints(12_000_000))
+        ));
+        assert_eq!(part1("-2147483648,0 -> -2147483648,9\n").unwrap(), 0);
+    }
+
+    #[test]
+    fn prop_process_line_roundtrip() {
+        use crate::testing::prop::{self, ints};
+
+        prop::check(
+            &(ints(0..=999), ints(0..=999), ints(0..=999), ints(0..=999)),
+            |&(x1, y1, x2, y2)| {
+                let line = format!("{},{} -> {},{}", x1, y1, x2, y2);
+                let expected = ((x1 as i32, y1 as i32), (x2 as i32, y2 as i32));
+                process_line(&line).ok() == Some(expected)
+            },
+        );
+    }
+
+    #[test]
+    fn prop_part2_at_least_part1() {
+        use crate::testing::prop::{self, ints, vecs};
+
+        // Lines as (x, y, direction, length) - direction picks one of the eight compass points
+        let line = (ints(0..=20), ints(0..=20), ints(0..=7), ints(0..=10));
+        prop::check(&vecs(line, 1..=15), |lines| {
+            let input: String = lines
+                .iter()
+                .map(|&(x, y, direction, length)| {
+                    let (dx, dy) = [
+                        (1, 0),
+                        (-1, 0),
+                        (0, 1),
+                        (0, -1),
+                        (1, 1),
+                        (1, -1),
+                        (-1, 1),
+                        (-1, -1),
+                    ][direction as usize];
+                    let (x, y, length) = (x as i32 + 10, y as i32 + 10, length as i32);
+                    format!("{},{} -> {},{}\n", x, y, x + dx * length, y + dy * length)
+                })
+                .collect();
+            part2(&input).unwrap() >= part1(&input).unwrap()
+        });
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum InputParseError {
+    #[error("Unable to Parse input as a timer")]
+    ParseIntError(#[from] std::num::ParseIntError),
+    #[error("Timer {0} is outside 0..=8")]
+    InvalidTimer(i32),
+}
+
+pub fn part2(input: &str) -> u64 {
+    simulate(input, 256)
+}
+
+pub fn part1(input: &str) -> u64 {
+    simulate(input, 80)
+}
+
+pub fn try_part2(input: &str) -> Result<u64, InputParseError> {
+    try_simulate(input, 256)
+}
+
+pub fn try_part1(input: &str) -> Result<u64, InputParseError> {
+    try_simulate(input, 80)
+}
+
+// Returns the number of lanternfish after `day_counter` days
+pub fn simulate(input: &str, day_counter: u32) -> u64 {
+    try_simulate(input, day_counter).unwrap()
+}
+
+pub fn try_simulate(input: &str, day_counter: u32) -> Result<u64, InputParseError> {
+    let mut world: HashMap<i32, u64> = HashMap::new();
+
+    for fish in input.split(",") {
+        let fish = fish.trim().parse::<i32>()?;
+        if !(0..=8).contains(&fish) {
+            return Err(InputParseError::InvalidTimer(fish));
+        }
+        *world.entry(fish).or_insert(0) += 1;
+    }
+
+    for _ in 1..=day_counter {
+        let val = *world.get(&0).unwrap_or(&0);
+        for i in 0..=8 {
+            *world.entry(i).or_insert(0) = *world.entry(i + 1).or_insert(0);
+        }
+        *world.entry(6).or_insert(0) += val;
+        *world.entry(8).or_insert(0) += val;
+    }
+
+    Ok(world.values().sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = String::from("3,4,3,1,2");
+        let result = part1(&input);
+        let expected_result = 5934;
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = String::from("3,4,3,1,2");
+        let result = part2(&input);
+        let expected_result: u64 = 26984457539;
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn prop_population_never_shrinks() {
+        use crate::testing::prop::{self, ints, vecs};
+
+        prop::check(
+            &(vecs(ints(0..=8), 1..=10), ints(0..=80)),
+            |(fish, days)| {
+                let input = fish
+                    .iter()
+                    .map(|f| f.to_string())
+                    .collect::<Vec<String>>()
+                    .join(",");
+                let days = *days as u32;
+                simulate(&input, days + 1) >= simulate(&input, days)
+            },
+        );
+    }
+}
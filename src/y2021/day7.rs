@@ -0,0 +1,151 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum InputParseError {
+    #[error("No Input")]
+    NoInput,
+    // Positions are limited to u32 so a single crab's fuel always fits in a u64
+    #[error("Unable to Parse input as a u32 position")]
+    ParseIntError(#[from] std::num::ParseIntError),
+    // Enough crabs far enough from the destination can still overflow the total
+    #[error("Overflow, the fuel needed is too big for a u64")]
+    Overflow,
+}
+
+fn median(list: &[u64]) -> u64 {
+    let mut list = list.to_vec();
+    list.sort();
+    let len = list.len();
+    if len.is_multiple_of(2) {
+        (list[len / 2 - 1] + list[len / 2]) / 2
+    } else {
+        list[len / 2]
+    }
+}
+
+fn mean(list: &[u64]) -> u64 {
+    let mut sum = 0;
+    for i in list {
+        sum += i;
+    }
+    sum / list.len() as u64
+}
+
+fn calc_individual_fuel_consumption(distance: u64) -> u64 {
+    distance * (distance + 1) / 2
+}
+
+fn calc_total_fuel_consumption_of_crabs(
+    crabs: &[u64],
+    destination: u64,
+) -> Result<u64, InputParseError> {
+    let mut sum: u64 = 0;
+    for crab in crabs {
+        sum = sum
+            .checked_add(calc_individual_fuel_consumption(
+                destination.abs_diff(*crab),
+            ))
+            .ok_or(InputParseError::Overflow)?;
+    }
+    Ok(sum)
+}
+
+fn parse_input(input: &str) -> Result<Vec<u64>, InputParseError> {
+    if input.trim().is_empty() {
+        return Err(InputParseError::NoInput);
+    }
+    input
+        .split(",")
+        .map(|x| Ok(x.trim().parse::<u32>()? as u64))
+        .collect()
+}
+
+pub fn part2(input: &str) -> u64 {
+    try_part2(input).unwrap()
+}
+
+pub fn try_part2(input: &str) -> Result<u64, InputParseError> {
+    // parse input
+    let crabs = parse_input(input)?;
+
+    // fuel cost is the a progression. each step is costs 1 mmore that the previous
+    // e.g. moving 3 steps costs 6 fuel = 1 + 2 + 3
+    //          x * (x+1) / 2
+    //
+
+    // Start with mean of all distances and check above and below then take the lowest.
+    // The optimum is always within half a step of the mean, so the (floored) mean and its
+    // neighbours are enough. Ties are fine as we only care about the fuel, not the position.
+    // A candidate that overflows can't be the cheapest unless they all do.
+    let destination = mean(&crabs);
+
+    (destination.saturating_sub(1)..=destination + 1)
+        .filter_map(|candidate| calc_total_fuel_consumption_of_crabs(&crabs, candidate).ok())
+        .min()
+        .ok_or(InputParseError::Overflow)
+}
+
+pub fn part1(input: &str) -> u64 {
+    try_part1(input).unwrap()
+}
+
+pub fn try_part1(input: &str) -> Result<u64, InputParseError> {
+    // parse input
+    let crabs = parse_input(input)?;
+
+    // Find median of all numbers - which is the destination
+    let destination = median(&crabs);
+
+    // iterate through the numbers and find the distance to the median
+    let mut fuel_consumption = 0;
+
+    for crab in crabs {
+        fuel_consumption += destination.abs_diff(crab);
+    }
+
+    Ok(fuel_consumption)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = String::from("16,1,2,0,4,2,7,1,2,14");
+        let result = part1(&input);
+        let expected_result = 37;
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = String::from("16,1,2,0,4,2,7,1,2,14");
+        let result = part2(&input);
+        let expected_result = 168;
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn test_part2_overflow_is_an_error() {
+        // Each crab is about 2^31 from the mean, costing about 2^61 fuel, and sixteen of them
+        // need more than a u64 holds
+        let input = ["0,4294967295"; 8].join(",");
+        assert!(matches!(try_part2(&input), Err(InputParseError::Overflow)));
+        assert_eq!(try_part1(&input).unwrap(), 8 * 4294967295);
+    }
+
+    #[test]
+    fn prop_part2_costs_at_least_part1() {
+        use crate::testing::prop::{self, ints, vecs};
+
+        prop::check(&vecs(ints(0..=100), 1..=20), |crabs| {
+            let input = crabs
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<String>>()
+                .join(",");
+            part2(&input) >= part1(&input)
+        });
+    }
+}
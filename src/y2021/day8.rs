@@ -1,5 +1,23 @@
 use itertools::Itertools;
 use std::collections::HashSet;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum InputParseError {
+    #[error("Line {0} is missing the | separator")]
+    MissingSeparator(usize),
+    #[error("Line {line} has {found} {kind} patterns, expected {expected}")]
+    WrongPatternCount {
+        line: usize,
+        kind: &'static str,
+        found: usize,
+        expected: usize,
+    },
+    #[error("Invalid pattern {pattern:?} on line {line}")]
+    InvalidPattern { line: usize, pattern: String },
+    #[error("Unable to work out the wiring on line {0}")]
+    Undecodable(usize),
+}
 
 type SourcePatterns = [String; 10];
 type OutputPatterns = [String; 4];
@@ -40,9 +58,17 @@ impl Code {
 }
 
 pub fn part1(input: &str) -> u32 {
+    try_part1(input).unwrap()
+}
+
+pub fn part2(input: &str) -> u32 {
+    try_part2(input).unwrap()
+}
+
+pub fn try_part1(input: &str) -> Result<u32, InputParseError> {
     // For this we only need the easy numbers
 
-    let input_source = parse_input(input);
+    let input_source = parse_input(input)?;
 
     // Parse the data return a count of all the OutputPatterns that are 1,4,7,8
     // 1's are 2 chars, 4s are 4 chars, 7s are 3 chars, 8s are 7 chars
@@ -59,26 +85,24 @@ pub fn part1(input: &str) -> u32 {
         }
     }
 
-    results
+    Ok(results)
 }
 
-pub fn part2(input: &str) -> u32 {
+pub fn try_part2(input: &str) -> Result<u32, InputParseError> {
     // Guessing for this I need to work out all the numbers.
     // See notes in comments for the rules.
 
-    let parsed_input = parse_input(input);
+    let parsed_input = parse_input(input)?;
     let mut decoded_output: Vec<u32> = Vec::new();
 
-    for (source_patterns, output_patterns) in parsed_input {
-        let code = create_decoder(source_patterns);
-
-        match decode_output(&code, output_patterns) {
-            Some(decoded) => decoded_output.push(decoded),
-            None => (),
-        }
+    for (i, (source_patterns, output_patterns)) in parsed_input.into_iter().enumerate() {
+        let decoded = create_decoder(source_patterns)
+            .and_then(|code| decode_output(&code, output_patterns))
+            .ok_or(InputParseError::Undecodable(i + 1))?;
+        decoded_output.push(decoded);
     }
 
-    return decoded_output.iter().sum();
+    Ok(decoded_output.iter().sum())
 }
 
 fn decode_output(code: &Code, output_patterns: OutputPatterns) -> Option<u32> {
@@ -86,47 +110,45 @@ fn decode_output(code: &Code, output_patterns: OutputPatterns) -> Option<u32> {
 
     for output_pattern in output_patterns {
         match output_pattern.chars().collect::<HashSet<char>>() {
-            ref coded_ouput if coded_ouput == code.one.as_ref().unwrap() => {
+            ref coded_ouput if code.one.as_ref() == Some(coded_ouput) => {
                 decoded_patterns.push(1);
             }
-            ref coded_ouput if coded_ouput == code.two.as_ref().unwrap() => {
+            ref coded_ouput if code.two.as_ref() == Some(coded_ouput) => {
                 decoded_patterns.push(2);
             }
-            ref coded_ouput if coded_ouput == code.three.as_ref().unwrap() => {
+            ref coded_ouput if code.three.as_ref() == Some(coded_ouput) => {
                 decoded_patterns.push(3);
             }
-            ref coded_ouput if coded_ouput == code.four.as_ref().unwrap() => {
+            ref coded_ouput if code.four.as_ref() == Some(coded_ouput) => {
                 decoded_patterns.push(4);
             }
-            ref coded_ouput if coded_ouput == code.five.as_ref().unwrap() => {
+            ref coded_ouput if code.five.as_ref() == Some(coded_ouput) => {
                 decoded_patterns.push(5);
             }
-            ref coded_ouput if coded_ouput == code.six.as_ref().unwrap() => {
+            ref coded_ouput if code.six.as_ref() == Some(coded_ouput) => {
                 decoded_patterns.push(6);
             }
-            ref coded_ouput if coded_ouput == code.seven.as_ref().unwrap() => {
+            ref coded_ouput if code.seven.as_ref() == Some(coded_ouput) => {
                 decoded_patterns.push(7);
             }
-            ref coded_ouput if coded_ouput == code.eight.as_ref().unwrap() => {
+            ref coded_ouput if code.eight.as_ref() == Some(coded_ouput) => {
                 decoded_patterns.push(8);
             }
-            ref coded_ouput if coded_ouput == code.nine.as_ref().unwrap() => {
+            ref coded_ouput if code.nine.as_ref() == Some(coded_ouput) => {
                 decoded_patterns.push(9);
             }
-            ref coded_ouput if coded_ouput == code.zero.as_ref().unwrap() => {
+            ref coded_ouput if code.zero.as_ref() == Some(coded_ouput) => {
                 decoded_patterns.push(0);
             }
-            _ => panic!("Unknown output pattern"),
+            _ => return None,
         }
     }
 
-    match decoded_patterns.iter().join("").parse::<u32>() {
-        Ok(decoded_number) => Some(decoded_number),
-        Err(_) => None,
-    }
+    decoded_patterns.iter().join("").parse::<u32>().ok()
 }
 
-fn create_decoder(source: SourcePatterns) -> Code {
+// Works out which pattern is which digit, None if the patterns aren't a valid set of digits
+fn create_decoder(source: SourcePatterns) -> Option<Code> {
     let mut code = Code::new();
 
     // temp placeholder for length 5 codes - (2, 3, 5)
@@ -190,7 +212,7 @@ fn create_decoder(source: SourcePatterns) -> Code {
                     code.eight = Some(coded_number);
                 }
             },
-            _ => unreachable!("This should never happen"),
+            _ => return None,
         }
     }
     // Initial parsing of all numbers now need to work through the length 5 and 6 patterns
@@ -204,12 +226,13 @@ fn create_decoder(source: SourcePatterns) -> Code {
     // bottom right = code.one - top right
     // middle = code.six - code.zero
 
-    if code.one.is_some() && code.seven.is_some() && code.four.is_some() {
-        let one = code.one.as_ref().unwrap();
-        let seven = code.seven.as_ref().unwrap();
-        let four = code.four.as_ref().unwrap();
+    if let (Some(one), Some(seven), Some(four)) =
+        (code.one.clone(), code.seven.clone(), code.four.clone())
+    {
+        let one = &one;
+        let four = &four;
 
-        let top: HashSet<char> = seven.difference(&one).cloned().collect();
+        let top: HashSet<char> = seven.difference(one).cloned().collect();
 
         // Length 6 - 0, 6, 9
         for coded_number in length_six.clone() {
@@ -242,11 +265,8 @@ fn create_decoder(source: SourcePatterns) -> Code {
                     }
                     code.zero = Some(coded_number);
                 }
-                _ => {
-                    println!("codes: {:?}", &code);
-                    println!("Unknown length 6 pattern: {:?}", coded_number);
-                    unreachable!("All length 6 numbers should be in one of the sets");
-                }
+                // All length 6 numbers should be in one of the sets
+                _ => return None,
             }
         }
 
@@ -254,9 +274,8 @@ fn create_decoder(source: SourcePatterns) -> Code {
         // bottom left = code.six - code.nine
         let bottom_left: HashSet<char> = code
             .six
-            .as_ref()
-            .unwrap()
-            .difference(&code.nine.as_ref().unwrap())
+            .as_ref()?
+            .difference(code.nine.as_ref()?)
             .cloned()
             .collect();
 
@@ -286,45 +305,64 @@ fn create_decoder(source: SourcePatterns) -> Code {
                     }
                     code.five = Some(coded_number);
                 }
-                _ => unreachable!("All length 5 numbers should be in one of the sets"),
+                // All length 5 numbers should be in one of the sets
+                _ => return None,
             }
         }
+        Some(code)
     } else {
-        panic!("Not all required patterns were found");
+        // Not all required patterns were found
+        None
     }
+}
 
-    code
+// Splits patterns on whitespace, checking each one is a set of distinct segments a-g
+fn parse_patterns(
+    patterns: &str,
+    line: usize,
+    kind: &'static str,
+    expected: usize,
+) -> Result<Vec<String>, InputParseError> {
+    let patterns: Vec<String> = patterns.split_whitespace().map(String::from).collect();
+
+    if patterns.len() != expected {
+        return Err(InputParseError::WrongPatternCount {
+            line,
+            kind,
+            found: patterns.len(),
+            expected,
+        });
+    }
+    for pattern in &patterns {
+        let valid_segments = pattern.chars().all(|c| ('a'..='g').contains(&c));
+        let distinct = pattern.chars().collect::<HashSet<char>>().len() == pattern.len();
+        if !valid_segments || !distinct || pattern.len() < 2 {
+            return Err(InputParseError::InvalidPattern {
+                line,
+                pattern: pattern.clone(),
+            });
+        }
+    }
+    Ok(patterns)
 }
 
-fn parse_input(input: &str) -> Vec<InputSource> {
+fn parse_input(input: &str) -> Result<Vec<InputSource>, InputParseError> {
     let mut data: Vec<InputSource> = Vec::new();
 
-    for line in input.lines() {
-        let mut source_patterns: SourcePatterns = Default::default();
-        let mut output_patterns: OutputPatterns = Default::default();
-
-        let mut source_pattern_index = 0;
-        let mut output_pattern_index = 0;
-
+    for (i, line) in input.lines().enumerate() {
         match line.split_once("|") {
             Some((source_patterns_str, output_patterns_str)) => {
-                for source_pattern_str in source_patterns_str.trim().split(" ") {
-                    source_patterns[source_pattern_index] = source_pattern_str.trim().to_string();
-                    source_pattern_index += 1;
-                }
-
-                for output_pattern_str in output_patterns_str.trim().split(" ") {
-                    output_patterns[output_pattern_index] = output_pattern_str.trim().to_string();
-                    output_pattern_index += 1;
-                }
-            }
-            None => {
-                panic!("Invalid input");
+                let source_patterns = parse_patterns(source_patterns_str, i + 1, "source", 10)?;
+                let output_patterns = parse_patterns(output_patterns_str, i + 1, "output", 4)?;
+                data.push((
+                    source_patterns.try_into().unwrap(),
+                    output_patterns.try_into().unwrap(),
+                ));
             }
+            None => return Err(InputParseError::MissingSeparator(i + 1)),
         }
-        data.push((source_patterns, output_patterns));
     }
-    data
+    Ok(data)
 }
 
 #[cfg(test)]
@@ -370,6 +408,22 @@ mod tests {
         let expected_result = 61229;
         assert_eq!(result, expected_result);
     }
+
+    #[test]
+    fn prop_decode_encoded_number() {
+        use crate::testing::generate::{day8_entry, SEGMENTS};
+        use crate::testing::prop::{self, ints, permutations};
+        use crate::testing::Rng;
+
+        // (wiring, number, seed used to shuffle the patterns)
+        let strategy = (permutations(7), ints(0..=9999), ints(0..=u64::MAX));
+        prop::check(&strategy, |(wiring, number, seed)| {
+            let wiring: [char; 7] = std::array::from_fn(|i| SEGMENTS[wiring[i]]);
+            let digits = [1000, 100, 10, 1].map(|place| ((number / place) % 10) as u8);
+            let line = day8_entry(&mut Rng::new(*seed), &wiring, digits);
+            part2(&line) == *number as u32
+        });
+    }
 }
 
 // Manual notes for how to work out what is what.